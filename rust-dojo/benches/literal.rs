@@ -0,0 +1,132 @@
+//! 리터럴 검색 벤치마크 (외부 크레이트 없음)
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo bench --bench literal          # 기본 64 MiB
+//! cargo bench --bench literal -- 256   # 입력 크기(MiB) 지정
+//! ```
+//!
+//! 예전 구현(`lines()` 로 나눈 뒤 라인마다 `str::contains`)과
+//! 전체 버퍼를 먼저 훑는 현재 구현의 처리량을 비교합니다.
+
+use std::hint::black_box;
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+
+use minigrep::{search, stream, Pattern, StreamOptions};
+
+const WORDS: &[&str] = &[
+    "the",
+    "request",
+    "handler",
+    "returned",
+    "status",
+    "ok",
+    "user",
+    "session",
+    "cache",
+    "miss",
+    "latency",
+    "ms",
+    "connection",
+    "pool",
+    "worker",
+    "queued",
+    "job",
+    "에러",
+    "재시도",
+    "완료",
+];
+
+/// 로그처럼 보이는 텍스트를 `size` 바이트 정도 만듭니다.
+fn make_haystack(size: usize) -> String {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut text = String::with_capacity(size + 128);
+    let mut line = 0;
+    while text.len() < size {
+        line += 1;
+        text.push_str(&format!(
+            "2024-05-01T12:{:02}:{:02} INFO ",
+            line / 60 % 60,
+            line % 60
+        ));
+        for _ in 0..12 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            text.push_str(WORDS[(state % WORDS.len() as u64) as usize]);
+            text.push(' ');
+        }
+        // 드물게 나오는 리터럴
+        if line % 5000 == 0 {
+            text.push_str("deadlock detected");
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// 예전 `search` 구현
+fn search_by_line<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    contents.lines().filter(|v| v.contains(query)).collect()
+}
+
+/// 가장 빠른 실행 시간을 기준으로 처리량을 출력합니다.
+fn bench(name: &str, bytes: usize, mut run: impl FnMut() -> usize) {
+    let mut best = Duration::MAX;
+    let mut hits = 0;
+    for _ in 0..5 {
+        let started = Instant::now();
+        hits = black_box(run());
+        best = best.min(started.elapsed());
+    }
+    let mib_per_sec = bytes as f64 / (1024.0 * 1024.0) / best.as_secs_f64();
+    println!("{name:<40} {best:>12.2?} {mib_per_sec:>10.1} MiB/s  ({hits} lines)");
+}
+
+fn main() {
+    // `cargo bench` 가 넘기는 `--bench` 같은 플래그는 건너뜁니다.
+    let mib = std::env::args()
+        .skip(1)
+        .find(|arg| !arg.starts_with('-'))
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(64usize);
+    let haystack = make_haystack(mib * 1024 * 1024);
+    let bytes = haystack.len();
+    println!("haystack: {:.1} MiB\n", bytes as f64 / (1024.0 * 1024.0));
+
+    for query in [
+        "deadlock detected",
+        "session",
+        "재시도",
+        "not present anywhere",
+    ] {
+        println!("query: {query:?}");
+        bench("  lines() + str::contains (예전)", bytes, || {
+            search_by_line(query, &haystack).len()
+        });
+        bench("  search (버퍼 전체 검색)", bytes, || {
+            search(query, &haystack).len()
+        });
+        let pattern = Pattern::literal(query);
+        bench(
+            "  stream::search_reader (청크 + LineMatch)",
+            bytes,
+            || {
+                let mut lines = 0;
+                stream::search_reader(
+                    &pattern,
+                    Cursor::new(haystack.as_bytes()),
+                    &StreamOptions::default(),
+                    |_| {
+                        lines += 1;
+                        Ok(())
+                    },
+                )
+                .unwrap();
+                lines
+            },
+        );
+        println!();
+    }
+}
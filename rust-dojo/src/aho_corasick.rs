@@ -0,0 +1,169 @@
+//! Aho-Corasick 다중 리터럴 검색
+//!
+//! 여러 리터럴 패턴을 트라이 하나로 모은 뒤 실패 링크를 미리 따라가 둔 DFA 로 바꿉니다.
+//! 패턴이 몇 개든 입력의 각 바이트는 표를 한 번 찾아보는 것으로 처리되므로,
+//! 금지된 식별자 수백 개를 찾을 때도 입력을 한 번만 훑습니다.
+//!
+//! 상태마다 256 칸짜리 전이 표를 가지므로 메모리는 패턴 바이트 수 × 1 KiB 정도입니다.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::literal::FindAt;
+
+/// 트라이를 만드는 동안 아직 전이가 없다는 표시
+const NONE: u32 = u32::MAX;
+
+/// 패턴 하나의 매치
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatch {
+    /// [`AhoCorasick::new`] 에 넘긴 순서 기준의 패턴 번호
+    pub pattern: usize,
+    /// 입력 기준 바이트 범위
+    pub range: Range<usize>,
+}
+
+/// 컴파일된 다중 리터럴 검색기
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    /// `상태 * 256 + 바이트` 위치에 다음 상태
+    transitions: Vec<u32>,
+    /// 상태마다 그 위치에서 끝나는 패턴 번호들. 긴 패턴이 앞에 옵니다.
+    outputs: Vec<Vec<usize>>,
+    /// 패턴 길이
+    lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> AhoCorasick {
+        let mut transitions = vec![NONE; 256];
+        let mut outputs = vec![Vec::new()];
+        for (id, pattern) in patterns.iter().enumerate() {
+            let mut state = 0;
+            for &byte in pattern.as_ref() {
+                let slot = state * 256 + byte as usize;
+                state = match transitions[slot] {
+                    NONE => {
+                        let next = outputs.len();
+                        transitions[slot] = next as u32;
+                        transitions.extend([NONE; 256]);
+                        outputs.push(Vec::new());
+                        next
+                    }
+                    next => next as usize,
+                };
+            }
+            outputs[state].push(id);
+        }
+
+        // 너비 우선으로 실패 링크를 계산하면서 빈 전이를 실패 상태의 전이로 채웁니다.
+        let mut fail = vec![0; outputs.len()];
+        let mut queue = VecDeque::new();
+        for slot in transitions.iter_mut().take(256) {
+            match *slot {
+                NONE => *slot = 0,
+                next => queue.push_back(next as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            // 실패 상태는 더 얕으므로 이미 출력이 합쳐져 있습니다.
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+            for byte in 0..256 {
+                let via_fail = transitions[fail[state] * 256 + byte];
+                let slot = state * 256 + byte;
+                match transitions[slot] {
+                    NONE => transitions[slot] = via_fail,
+                    next => {
+                        fail[next as usize] = via_fail as usize;
+                        queue.push_back(next as usize);
+                    }
+                }
+            }
+        }
+
+        AhoCorasick {
+            transitions,
+            outputs,
+            lengths: patterns.iter().map(|p| p.as_ref().len()).collect(),
+        }
+    }
+
+    /// 패턴 개수
+    pub fn patterns_len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    #[inline]
+    fn next_state(&self, state: usize, byte: u8) -> usize {
+        self.transitions[state * 256 + byte as usize] as usize
+    }
+
+    fn pattern_match(&self, pattern: usize, end: usize) -> PatternMatch {
+        PatternMatch {
+            pattern,
+            range: end - self.lengths[pattern]..end,
+        }
+    }
+
+    /// `start` 이후에서 가장 먼저 끝나는 매치. 같은 위치에서 끝나면 긴 패턴이 우선입니다.
+    pub fn find_at(&self, haystack: &[u8], start: usize) -> Option<PatternMatch> {
+        let mut state = 0;
+        if let Some(&pattern) = self.outputs[0].first() {
+            return Some(self.pattern_match(pattern, start));
+        }
+        for (i, &byte) in haystack.get(start..)?.iter().enumerate() {
+            state = self.next_state(state, byte);
+            if let Some(&pattern) = self.outputs[state].first() {
+                return Some(self.pattern_match(pattern, start + i + 1));
+            }
+        }
+        None
+    }
+
+    /// 겹치는 것까지 포함한 모든 매치를 끝 위치 순서로 반환합니다.
+    pub fn find_overlapping_iter<'a, 'h>(&'a self, haystack: &'h [u8]) -> OverlappingIter<'a, 'h> {
+        OverlappingIter {
+            automaton: self,
+            haystack,
+            state: 0,
+            pos: 0,
+            output: 0,
+        }
+    }
+}
+
+impl FindAt for AhoCorasick {
+    fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        AhoCorasick::find_at(self, haystack, start).map(|found| found.range.start)
+    }
+}
+
+/// [`AhoCorasick::find_overlapping_iter`] 가 반환하는 반복자
+pub struct OverlappingIter<'a, 'h> {
+    automaton: &'a AhoCorasick,
+    haystack: &'h [u8],
+    state: usize,
+    /// 지금까지 읽은 바이트 수
+    pos: usize,
+    /// 현재 상태의 출력 중 다음에 돌려줄 위치
+    output: usize,
+}
+
+impl Iterator for OverlappingIter<'_, '_> {
+    type Item = PatternMatch;
+
+    fn next(&mut self) -> Option<PatternMatch> {
+        loop {
+            let outputs = &self.automaton.outputs[self.state];
+            if let Some(&pattern) = outputs.get(self.output) {
+                self.output += 1;
+                return Some(self.automaton.pattern_match(pattern, self.pos));
+            }
+            let &byte = self.haystack.get(self.pos)?;
+            self.state = self.automaton.next_state(self.state, byte);
+            self.pos += 1;
+            self.output = 0;
+        }
+    }
+}
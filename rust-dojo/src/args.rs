@@ -0,0 +1,668 @@
+//! GNU 스타일 커맨드라인 파서
+//!
+//! `Config::build` 가 사용하는 옵션 파서입니다. 다음 문법을 지원합니다.
+//!
+//! - 짧은 옵션과 묶음: `-i`, `-inv`
+//! - 값을 받는 짧은 옵션: `-A 3`, `-A3`, `-nA3`
+//! - 긴 옵션: `--count`, `--context=3`, `--context 3`
+//! - `--` 이후의 인자는 모두 위치 인자로 취급
+//! - 단독 `-` 는 위치 인자 (표준 입력)
+//! - 첫 인자가 `rank` 이면 BM25 랭킹 모드 (`minigrep rank QUERY PATH...`)
+//! - 처음 두 인자가 `index build` 이면 트라이그램 색인 생성 (`minigrep index build DIR...`)
+
+use std::error::Error;
+use std::fmt;
+
+use crate::fields::{FieldQuery, FieldSelector};
+use crate::glob::Glob;
+use crate::matcher::{self, ExprError, ExprTerm};
+use crate::{ignore, ColorChoice, Config, Encoding, RankOptions, RankUnit};
+
+/// `--help` 에 출력되는 사용법
+pub const USAGE: &str = "\
+Usage: minigrep [OPTIONS] PATTERN [PATH...]
+       minigrep [OPTIONS] -e PATTERN [-e PATTERN | -f FILE]... [PATH...]
+       minigrep [OPTIONS] (--jsonl-field SPEC | --csv-column SPEC) [PATH...]
+       minigrep rank [OPTIONS] QUERY [PATH...]
+       minigrep index build [OPTIONS] DIR...
+
+PATH 가 없거나 `-` 이면 표준 입력을 읽습니다. -e, -f, --jsonl-field, --csv-column 을
+주면 위치 인자는 모두 PATH 입니다.
+rank 는 라인을 거르는 대신 QUERY 와 BM25 점수가 높은 파일(또는 문단)부터 출력합니다.
+index build 는 DIR 마다 트라이그램 색인(DIR/.minigrep-index)을 만들고, 다시 실행하면
+크기나 수정 시각이 바뀐 파일만 새로 읽습니다. --index 로 검색할 때 이 색인을 씁니다.
+
+Options:
+  -e, --regexp PATTERN        검색할 패턴 (여러 번 지정 가능, 하나라도 맞으면 선택)
+  -f, --file FILE             FILE 의 각 줄을 패턴으로 사용 (빈 줄은 무시)
+      --and, --or, --not      -e / -f 패턴 조합 (우선순위: --not > --and > --or)
+  -i, --ignore-case           대소문자를 구분하지 않고 검색
+  -E, --regex                 PATTERN 을 정규 표현식으로 해석
+  -n, --line-number           라인 번호를 함께 출력
+      --column                첫 매치의 컬럼(1부터, 문자 단위)을 함께 출력
+  -v, --invert-match          매칭되지 않는 라인을 출력
+  -c, --count                 파일마다 매칭된 라인 수만 출력
+  -l, --files-with-matches    매칭된 파일 경로만 출력 (파일마다 첫 매치에서 읽기를 멈춤)
+  -L, --files-without-match   매칭되는 라인이 없는 파일 경로만 출력
+  -w, --word-regexp           단어 단위로만 매칭
+  -U, --multiline             줄바꿈을 넘어 매칭 (^ $ 는 각 줄 기준, 줄바꿈은 \\n 이나 \\s 로)
+  -A, --after-context N       매칭 뒤 N 줄을 함께 출력
+  -B, --before-context N      매칭 앞 N 줄을 함께 출력
+  -C, --context N             매칭 앞뒤 N 줄을 함께 출력
+  -j, --threads N             검색 스레드 수 (기본값: CPU 코어 수)
+      --stats                 검색이 끝난 뒤 매치 수, 검색한 파일과 바이트 수, 걸린 시간을 출력
+      --json                  결과를 JSON Lines 레코드로 출력 (-c, -l 보다 우선)
+  -r, --replace TEMPLATE      매치를 TEMPLATE 으로 치환한 unified diff 출력 ($1, ${name})
+      --write                 --replace 결과를 파일에 직접 기록 (임시 파일 + rename)
+  -g, --glob GLOB             GLOB 에 맞는 파일만 검색, `!GLOB` 은 제외 (여러 번 지정 가능)
+  -t, --type TYPE             TYPE 종류의 파일만 검색 (예: rust, py, md)
+  -T, --type-not TYPE         TYPE 종류의 파일은 제외
+      --hidden                숨김 파일과 디렉토리도 검색
+      --no-ignore             .gitignore / .ignore 규칙을 무시
+      --fuzzy K               편집 거리 K 이내로 PATTERN 과 비슷한 라인을 거리 순으로 출력
+      --index                 DIR 의 트라이그램 색인으로 후보 파일을 먼저 거름 (리터럴 패턴만)
+      --encoding ENC          입력 인코딩: utf-8, utf-16le, utf-16be, euc-kr, cp949
+                              (기본값: BOM 과 내용으로 자동 감지)
+      --binary                바이너리 파일도 원본 바이트로 검색하고 매치 여부만 출력
+      --follow                `tail -F | grep` 처럼 PATH 에 덧붙는 라인을 계속 검색
+                              (마지막 10줄부터, 잘림과 로테이션을 따라감)
+      --jsonl-field KEY=VALUE JSON Lines 레코드의 KEY 값이 VALUE 인 라인만 선택
+                              (KEY~PATTERN 은 값의 일부 매칭, 중첩 키는 a.b)
+      --csv-column N=VALUE    CSV 레코드의 N 번째 열(1부터)이 VALUE 인 라인만 선택
+                              (N~PATTERN 은 값의 일부 매칭)
+      --color[=WHEN]          색상 출력: auto(기본값, 터미널일 때만), always, never
+                              NO_COLOR 환경 변수가 있으면 auto 는 색을 끔
+  -h, --help                  이 도움말을 출력
+
+Rank options:
+      --k1 K1                 TF 포화 파라미터 (기본값: 1.5)
+      --b B                   문서 길이 정규화 파라미터, 0~1 (기본값: 0.75)
+      --paragraphs            파일 대신 빈 줄로 나눈 문단 단위로 순위를 매김
+      --top N                 상위 N 개만 출력, 0 이면 전부 (기본값: 10)";
+
+/// 인자 파싱 에러
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// PATTERN 이나 PATH 가 빠졌습니다.
+    NotEnoughArguments,
+    /// 알 수 없는 옵션
+    UnknownOption(String),
+    /// 값을 받아야 하는 옵션에 값이 없습니다.
+    MissingValue(String),
+    /// 값을 받지 않는 옵션에 `--flag=value` 형태로 값이 주어졌습니다.
+    UnexpectedValue(String),
+    /// 옵션 값의 형식이 잘못되었습니다.
+    InvalidValue { option: String, value: String },
+    /// 다른 옵션과 함께 써야 하는 옵션이 단독으로 주어졌습니다.
+    Requires { option: String, requires: String },
+    /// 함께 쓸 수 없는 조합입니다.
+    Conflicts { option: String, with: String },
+    /// `-e`, `-f`, `--and`, `--or`, `--not` 으로 만든 패턴 식이 잘못되었습니다.
+    Expression(ExprError),
+    /// `--help` 가 요청되었습니다. 에러라기보다는 조기 종료 신호입니다.
+    Help,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::NotEnoughArguments => write!(f, "Not enough arguments"),
+            ConfigError::UnknownOption(option) => write!(f, "unknown option '{option}'"),
+            ConfigError::MissingValue(option) => write!(f, "option '{option}' requires a value"),
+            ConfigError::UnexpectedValue(option) => {
+                write!(f, "option '{option}' does not take a value")
+            }
+            ConfigError::InvalidValue { option, value } => {
+                write!(f, "invalid value '{value}' for option '{option}'")
+            }
+            ConfigError::Requires { option, requires } => {
+                write!(f, "option '{option}' requires '{requires}'")
+            }
+            ConfigError::Conflicts { option, with } => {
+                write!(f, "option '{option}' cannot be used with {with}")
+            }
+            ConfigError::Expression(e) => write!(f, "invalid pattern expression: {e}"),
+            ConfigError::Help => write!(f, "{USAGE}"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Arity {
+    Flag,
+    Value,
+    /// `--color` 처럼 값을 `=` 로만 받고, 없으면 기본값을 씁니다.
+    OptionalValue,
+}
+
+struct OptionSpec {
+    short: Option<char>,
+    long: &'static str,
+    arity: Arity,
+}
+
+const fn flag(short: Option<char>, long: &'static str) -> OptionSpec {
+    OptionSpec {
+        short,
+        long,
+        arity: Arity::Flag,
+    }
+}
+
+const fn value(short: Option<char>, long: &'static str) -> OptionSpec {
+    OptionSpec {
+        short,
+        long,
+        arity: Arity::Value,
+    }
+}
+
+const fn optional_value(long: &'static str) -> OptionSpec {
+    OptionSpec {
+        short: None,
+        long,
+        arity: Arity::OptionalValue,
+    }
+}
+
+/// 지원하는 옵션 목록. 새 옵션은 여기와 [`apply`] 에 함께 추가합니다.
+const OPTIONS: &[OptionSpec] = &[
+    value(Some('e'), "regexp"),
+    value(Some('f'), "file"),
+    flag(None, "and"),
+    flag(None, "or"),
+    flag(None, "not"),
+    flag(Some('i'), "ignore-case"),
+    flag(Some('E'), "regex"),
+    flag(Some('n'), "line-number"),
+    flag(None, "column"),
+    flag(Some('v'), "invert-match"),
+    flag(Some('c'), "count"),
+    flag(Some('l'), "files-with-matches"),
+    flag(Some('L'), "files-without-match"),
+    flag(Some('w'), "word-regexp"),
+    flag(Some('U'), "multiline"),
+    value(Some('A'), "after-context"),
+    value(Some('B'), "before-context"),
+    value(Some('C'), "context"),
+    value(Some('j'), "threads"),
+    flag(None, "json"),
+    flag(None, "stats"),
+    value(Some('r'), "replace"),
+    flag(None, "write"),
+    value(Some('g'), "glob"),
+    value(Some('t'), "type"),
+    value(Some('T'), "type-not"),
+    flag(None, "hidden"),
+    flag(None, "no-ignore"),
+    flag(None, "index"),
+    value(None, "encoding"),
+    flag(None, "binary"),
+    flag(None, "follow"),
+    value(None, "jsonl-field"),
+    value(None, "csv-column"),
+    optional_value("color"),
+    value(None, "fuzzy"),
+    value(None, "k1"),
+    value(None, "b"),
+    flag(None, "paragraphs"),
+    value(None, "top"),
+    flag(Some('h'), "help"),
+];
+
+/// 커맨드라인 인자(프로그램 이름 포함)를 파싱해 [`Config`] 를 만듭니다.
+pub fn parse(args: &[String]) -> Result<Config, ConfigError> {
+    let mut config = Config::default();
+    let mut positionals = Vec::new();
+    let mut rest = args.iter().skip(1);
+    let mut options_done = false;
+    // 서브커맨드는 첫 인자일 때만 인식하므로 `minigrep -n rank FILE` 은 "rank" 를 검색합니다.
+    if args.get(1).is_some_and(|arg| arg == "rank") {
+        config.rank = Some(RankOptions::default());
+        rest.next();
+    } else if args
+        .get(1..3)
+        .is_some_and(|args| args == ["index", "build"])
+    {
+        config.build_index = true;
+        rest.nth(1);
+    }
+
+    while let Some(arg) = rest.next() {
+        if options_done || arg == "-" || !arg.starts_with('-') {
+            positionals.push(arg.clone());
+        } else if arg == "--" {
+            options_done = true;
+        } else if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            let spec = OPTIONS
+                .iter()
+                .find(|spec| spec.long == name)
+                .ok_or_else(|| ConfigError::UnknownOption(arg.clone()))?;
+            let value = match spec.arity {
+                Arity::Flag if inline.is_some() => {
+                    return Err(ConfigError::UnexpectedValue(format!("--{name}")));
+                }
+                Arity::Flag => None,
+                Arity::OptionalValue => inline,
+                Arity::Value => Some(match inline {
+                    Some(value) => value,
+                    None => rest
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| ConfigError::MissingValue(format!("--{name}")))?,
+                }),
+            };
+            apply(&mut config, spec, value.as_deref())?;
+        } else {
+            let cluster = &arg[1..];
+            for (i, c) in cluster.char_indices() {
+                let spec = OPTIONS
+                    .iter()
+                    .find(|spec| spec.short == Some(c))
+                    .ok_or_else(|| ConfigError::UnknownOption(format!("-{c}")))?;
+                if spec.arity == Arity::Flag {
+                    apply(&mut config, spec, None)?;
+                    continue;
+                }
+                // `-A3` 처럼 붙어 있으면 나머지가 값이고, 아니면 다음 인자가 값입니다.
+                let attached = &cluster[i + c.len_utf8()..];
+                let value = if attached.is_empty() {
+                    rest.next()
+                        .cloned()
+                        .ok_or_else(|| ConfigError::MissingValue(format!("-{c}")))?
+                } else {
+                    attached.to_string()
+                };
+                apply(&mut config, spec, Some(&value))?;
+                break;
+            }
+        }
+    }
+
+    let mut positionals = positionals.into_iter();
+    if config.build_index {
+        // 색인 생성에는 패턴이 없으므로 위치 인자는 모두 디렉토리입니다.
+    } else if let (Some(field), true) = (&config.field, config.expr.is_empty()) {
+        // 필드 질의의 값이 패턴이고, `=` 는 필드 값 전체와 같아야 합니다.
+        config.query = match (field.exact, config.regex) {
+            (true, true) => format!(r"\A(?:{})\z", field.value),
+            _ => field.value.clone(),
+        };
+    } else if config.expr.is_empty() {
+        config.query = positionals.next().ok_or(ConfigError::NotEnoughArguments)?;
+    } else {
+        matcher::parse_expr(&config.expr).map_err(ConfigError::Expression)?;
+    }
+    config.paths = positionals.collect();
+    if config.paths.is_empty() {
+        // 색인할 디렉토리는 꼭 있어야 하고, 검색할 PATH 가 없으면 표준 입력을 읽습니다.
+        if config.build_index {
+            return Err(ConfigError::NotEnoughArguments);
+        }
+        config.paths.push(String::from(crate::STDIN_PATH));
+    }
+    if config.write && config.replace.is_none() {
+        return Err(ConfigError::Requires {
+            option: String::from("--write"),
+            requires: String::from("--replace"),
+        });
+    }
+    if config.fuzzy.is_some() {
+        check_fuzzy_conflicts(&config)?;
+    }
+    if config.rank.is_some() {
+        check_rank_conflicts(&config)?;
+    }
+    if config.index {
+        check_index_conflicts(&config)?;
+    }
+    if config.binary {
+        check_binary_conflicts(&config)?;
+    }
+    if config.follow {
+        check_follow_conflicts(&config)?;
+    }
+    if config.multiline {
+        check_multiline_conflicts(&config)?;
+    }
+    if let Some(field) = &config.field {
+        check_field_conflicts(&config, field)?;
+    }
+    if config.files_without_match {
+        check_files_without_match_conflicts(&config)?;
+    }
+    if config.stats {
+        check_stats_conflicts(&config)?;
+    }
+    if config.replace.is_some() {
+        check_replace_conflicts(&config)?;
+    }
+    Ok(config)
+}
+
+/// `--fuzzy` 는 리터럴 질의 하나를 라인 단위로만 비교하므로 함께 쓸 수 없는 옵션들을 거릅니다.
+fn check_fuzzy_conflicts(config: &Config) -> Result<(), ConfigError> {
+    let conflicts = [
+        (config.regex, "--regex"),
+        (!config.expr.is_empty(), "--regexp/--file"),
+        (config.word_regexp, "--word-regexp"),
+        (config.invert_match, "--invert-match"),
+        (
+            config.before_context > 0 || config.after_context > 0,
+            "--context",
+        ),
+        (config.json, "--json"),
+        (config.replace.is_some(), "--replace"),
+    ];
+    first_conflict("--fuzzy", &conflicts)
+}
+
+/// `rank` 는 질의를 토큰 단위로 비교하고 라인이 아닌 문서를 출력하므로 라인 검색 옵션을 거릅니다.
+fn check_rank_conflicts(config: &Config) -> Result<(), ConfigError> {
+    let conflicts = [
+        (config.regex, "--regex"),
+        (!config.expr.is_empty(), "--regexp/--file"),
+        (config.word_regexp, "--word-regexp"),
+        (config.invert_match, "--invert-match"),
+        (config.count, "--count"),
+        (config.files_with_matches, "--files-with-matches"),
+        (
+            config.before_context > 0 || config.after_context > 0,
+            "--context",
+        ),
+        (config.json, "--json"),
+        (config.replace.is_some(), "--replace"),
+        (config.fuzzy.is_some(), "--fuzzy"),
+        (config.index, "--index"),
+    ];
+    first_conflict("rank", &conflicts)
+}
+
+/// 색인으로 거른 파일은 아예 검색하지 않으므로, 매치가 없는 파일도 출력하는 옵션과는 함께 쓸 수 없습니다.
+fn check_index_conflicts(config: &Config) -> Result<(), ConfigError> {
+    let conflicts = [
+        (config.build_index, "index build"),
+        (config.invert_match, "--invert-match"),
+        (config.count, "--count"),
+        (config.fuzzy.is_some(), "--fuzzy"),
+    ];
+    first_conflict("--index", &conflicts)
+}
+
+/// `--binary` 는 라인 검색의 출력만 바꾸므로 다른 모드와는 함께 쓸 수 없습니다.
+/// 트라이그램 색인은 바이너리 파일을 담지 않으므로 `--index` 로 거르면 그 매치가 사라집니다.
+fn check_binary_conflicts(config: &Config) -> Result<(), ConfigError> {
+    let conflicts = [
+        (config.build_index, "index build"),
+        (config.index, "--index"),
+        (config.rank.is_some(), "rank"),
+        (config.fuzzy.is_some(), "--fuzzy"),
+        (config.replace.is_some(), "--replace"),
+    ];
+    first_conflict("--binary", &conflicts)
+}
+
+/// `--follow` 는 끝나지 않는 입력의 라인을 나오는 대로 출력하므로,
+/// 입력이 끝나야 결과를 낼 수 있는 옵션이나 다른 모드와는 함께 쓸 수 없습니다.
+fn check_follow_conflicts(config: &Config) -> Result<(), ConfigError> {
+    let conflicts = [
+        (config.build_index, "index build"),
+        (config.rank.is_some(), "rank"),
+        (config.fuzzy.is_some(), "--fuzzy"),
+        (config.replace.is_some(), "--replace"),
+        (config.count, "--count"),
+        (config.files_with_matches, "--files-with-matches"),
+        (
+            config.before_context > 0 || config.after_context > 0,
+            "--context",
+        ),
+        (config.json, "--json"),
+        (config.index, "--index"),
+        (config.encoding.is_some(), "--encoding"),
+        (config.binary, "--binary"),
+    ];
+    first_conflict("--follow", &conflicts)
+}
+
+fn first_conflict(option: &str, conflicts: &[(bool, &str)]) -> Result<(), ConfigError> {
+    match conflicts.iter().find(|(conflict, _)| *conflict) {
+        Some((_, with)) => Err(ConfigError::Conflicts {
+            option: option.to_string(),
+            with: format!("'{with}'"),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// `--replace` 는 매치가 있는 라인을 치환한 diff 를 출력하므로 라인을 고르거나 출력을 바꾸는
+/// 옵션들을 거릅니다.
+fn check_replace_conflicts(config: &Config) -> Result<(), ConfigError> {
+    // 치환 템플릿의 `$1` 이 어느 패턴의 그룹인지 정할 수 없으므로 패턴은 하나만 받습니다.
+    if !matches!(config.expr[..], [] | [ExprTerm::Pattern(_)]) {
+        return Err(replace_conflict());
+    }
+    let conflicts = [
+        (config.invert_match, "--invert-match"),
+        (
+            config.before_context > 0 || config.after_context > 0,
+            "--context",
+        ),
+        (config.count, "--count"),
+        (config.files_with_matches, "--files-with-matches"),
+        (config.json, "--json"),
+    ];
+    first_conflict("--replace", &conflicts)
+}
+
+/// `-U` 는 패턴 하나를 입력 전체에서 실행하므로 라인 단위로 동작하는 옵션들을 거릅니다.
+fn check_multiline_conflicts(config: &Config) -> Result<(), ConfigError> {
+    if !matches!(config.expr[..], [] | [ExprTerm::Pattern(_)]) {
+        return Err(multiline_conflict());
+    }
+    let conflicts = [
+        (config.invert_match, "--invert-match"),
+        (
+            config.before_context > 0 || config.after_context > 0,
+            "--context",
+        ),
+        (config.fuzzy.is_some(), "--fuzzy"),
+        (config.rank.is_some(), "rank"),
+        (config.replace.is_some(), "--replace"),
+        (config.follow, "--follow"),
+    ];
+    first_conflict("--multiline", &conflicts)
+}
+
+/// 필드 질의는 라인 단위 검색의 조건만 바꾸므로, 패턴을 따로 받거나 라인을 다르게 다루는 모드와는
+/// 함께 쓸 수 없습니다.
+fn check_field_conflicts(config: &Config, field: &FieldQuery) -> Result<(), ConfigError> {
+    let option = match field.selector {
+        FieldSelector::Json(_) => "--jsonl-field",
+        FieldSelector::Csv(_) => "--csv-column",
+    };
+    let conflicts = [
+        (!config.expr.is_empty(), "--regexp/--file"),
+        (config.build_index, "index build"),
+        (config.rank.is_some(), "rank"),
+        (config.fuzzy.is_some(), "--fuzzy"),
+        (config.replace.is_some(), "--replace"),
+        (config.index, "--index"),
+        (config.follow, "--follow"),
+        (config.multiline, "--multiline"),
+    ];
+    first_conflict(option, &conflicts)
+}
+
+/// `-L` 은 `-l` 의 반대이므로 다른 요약 출력과 함께 쓸 수 없고, 매치가 없는 파일까지
+/// 검색해야 하므로 색인으로 파일을 거를 수도 없습니다.
+fn check_files_without_match_conflicts(config: &Config) -> Result<(), ConfigError> {
+    let conflicts = [
+        (config.files_with_matches, "--files-with-matches"),
+        (config.count, "--count"),
+        (config.index, "--index"),
+        (config.rank.is_some(), "rank"),
+        (config.replace.is_some(), "--replace"),
+        (config.follow, "--follow"),
+    ];
+    first_conflict("--files-without-match", &conflicts)
+}
+
+/// `--stats` 는 라인 검색이 끝난 뒤 합계를 출력하므로 끝나지 않거나 다른 결과를 내는 모드와는
+/// 함께 쓸 수 없습니다. `--json` 은 `summary` 레코드에 같은 통계를 담습니다.
+fn check_stats_conflicts(config: &Config) -> Result<(), ConfigError> {
+    let conflicts = [
+        (config.json, "--json"),
+        (config.build_index, "index build"),
+        (config.rank.is_some(), "rank"),
+        (config.fuzzy.is_some(), "--fuzzy"),
+        (config.replace.is_some(), "--replace"),
+        (config.follow, "--follow"),
+    ];
+    first_conflict("--stats", &conflicts)
+}
+
+/// `--multiline` 을 패턴 여러 개와 함께 썼을 때의 에러
+pub(crate) fn multiline_conflict() -> ConfigError {
+    ConfigError::Conflicts {
+        option: String::from("--multiline"),
+        with: String::from("multiple patterns"),
+    }
+}
+
+/// `--replace` 를 패턴 여러 개와 함께 썼을 때의 에러
+pub(crate) fn replace_conflict() -> ConfigError {
+    ConfigError::Conflicts {
+        option: String::from("--replace"),
+        with: String::from("multiple patterns"),
+    }
+}
+
+fn apply(config: &mut Config, spec: &OptionSpec, value: Option<&str>) -> Result<(), ConfigError> {
+    match spec.long {
+        "regexp" => config
+            .expr
+            .push(ExprTerm::Pattern(value.unwrap_or_default().to_string())),
+        "file" => config
+            .expr
+            .push(ExprTerm::File(value.unwrap_or_default().to_string())),
+        "and" => config.expr.push(ExprTerm::And),
+        "or" => config.expr.push(ExprTerm::Or),
+        "not" => config.expr.push(ExprTerm::Not),
+        "ignore-case" => config.case_sensitive = false,
+        "regex" => config.regex = true,
+        "line-number" => config.line_number = true,
+        "column" => config.column = true,
+        "invert-match" => config.invert_match = true,
+        "count" => config.count = true,
+        "files-with-matches" => config.files_with_matches = true,
+        "files-without-match" => config.files_without_match = true,
+        "word-regexp" => config.word_regexp = true,
+        "multiline" => config.multiline = true,
+        "after-context" => config.after_context = parse_number(spec, value)?,
+        "before-context" => config.before_context = parse_number(spec, value)?,
+        "context" => {
+            let n = parse_number(spec, value)?;
+            config.before_context = n;
+            config.after_context = n;
+        }
+        "threads" => config.threads = parse_number(spec, value)?,
+        "json" => config.json = true,
+        "stats" => config.stats = true,
+        "replace" => config.replace = value.map(String::from),
+        "write" => config.write = true,
+        "glob" => {
+            let glob = value.unwrap_or_default();
+            let pattern = glob.strip_prefix('!').unwrap_or(glob);
+            Glob::new(pattern).map_err(|_| invalid_value(spec, glob))?;
+            config.globs.push(glob.to_string());
+        }
+        "type" | "type-not" => {
+            let name = value.unwrap_or_default();
+            if ignore::type_globs(name).is_none() {
+                return Err(invalid_value(spec, name));
+            }
+            match spec.long {
+                "type" => config.types.push(name.to_string()),
+                _ => config.types_not.push(name.to_string()),
+            }
+        }
+        "hidden" => config.hidden = true,
+        "no-ignore" => config.no_ignore = true,
+        "fuzzy" => config.fuzzy = Some(parse_number(spec, value)?),
+        "index" => config.index = true,
+        "encoding" => {
+            let name = value.unwrap_or_default();
+            config.encoding = Some(Encoding::parse(name).ok_or_else(|| invalid_value(spec, name))?);
+        }
+        "binary" => config.binary = true,
+        "follow" => config.follow = true,
+        "jsonl-field" | "csv-column" => {
+            if config.field.is_some() {
+                return Err(ConfigError::Conflicts {
+                    option: format!("--{}", spec.long),
+                    with: String::from("another field query"),
+                });
+            }
+            let spec_value = value.unwrap_or_default();
+            let field = match spec.long {
+                "jsonl-field" => FieldQuery::parse_jsonl(spec_value),
+                _ => FieldQuery::parse_csv(spec_value),
+            };
+            config.field = Some(field.ok_or_else(|| invalid_value(spec, spec_value))?);
+        }
+        "k1" | "b" | "paragraphs" | "top" => {
+            let rank = config.rank.as_mut().ok_or_else(|| ConfigError::Requires {
+                option: format!("--{}", spec.long),
+                requires: String::from("rank"),
+            })?;
+            match spec.long {
+                "k1" => rank.params.k1 = parse_float(spec, value, 0.0..=f64::MAX)?,
+                "b" => rank.params.b = parse_float(spec, value, 0.0..=1.0)?,
+                "paragraphs" => rank.unit = RankUnit::Paragraph,
+                _ => rank.top = parse_number(spec, value)?,
+            }
+        }
+        "color" => {
+            config.color = match value {
+                None => ColorChoice::Auto,
+                Some(value) => {
+                    ColorChoice::parse(value).ok_or_else(|| invalid_value(spec, value))?
+                }
+            }
+        }
+        "help" => return Err(ConfigError::Help),
+        other => unreachable!("option '{other}' is missing from apply()"),
+    }
+    Ok(())
+}
+
+fn parse_number(spec: &OptionSpec, value: Option<&str>) -> Result<usize, ConfigError> {
+    let value = value.unwrap_or_default();
+    value.parse().map_err(|_| invalid_value(spec, value))
+}
+
+/// `range` 안의 유한한 실수만 받습니다.
+fn parse_float(
+    spec: &OptionSpec,
+    value: Option<&str>,
+    range: std::ops::RangeInclusive<f64>,
+) -> Result<f64, ConfigError> {
+    let value = value.unwrap_or_default();
+    match value.parse::<f64>() {
+        Ok(number) if range.contains(&number) => Ok(number),
+        _ => Err(invalid_value(spec, value)),
+    }
+}
+
+fn invalid_value(spec: &OptionSpec, value: &str) -> ConfigError {
+    ConfigError::InvalidValue {
+        option: format!("--{}", spec.long),
+        value: value.to_string(),
+    }
+}
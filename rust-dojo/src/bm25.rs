@@ -0,0 +1,289 @@
+//! BM25 랭킹 검색 (`minigrep rank`)
+//!
+//! 라인을 거르는 대신 문서(파일 또는 문단)를 질의와의 관련도 순으로 정렬합니다.
+//! 공식은 `bm25-dojo` 와 같습니다.
+//!
+//! ```text
+//! score(D, Q) = Σ IDF(q) * f(q, D) * (k1 + 1) / (f(q, D) + k1 * (1 - b + b * |D| / avgdl))
+//! ```
+//!
+//! 다만 IDF 는 `ln(1 + (N - n + 0.5) / (n + 0.5))` (Lucene 방식)를 씁니다.
+//! `bm25-dojo` 처럼 음수를 0 으로 자르면 파일 두세 개짜리 말뭉치에서 절반 이상의
+//! 문서에 나오는 단어가 점수에 전혀 기여하지 못하기 때문입니다.
+
+use std::collections::HashMap;
+use std::ops::{Range, RangeInclusive};
+
+/// BM25 파라미터
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bm25Params {
+    /// TF 포화 정도. 클수록 단어 빈도가 점수에 선형에 가깝게 반영됩니다.
+    pub k1: f64,
+    /// 문서 길이 정규화 정도. 0 이면 정규화하지 않고 1 이면 길이에 완전히 비례해 보정합니다.
+    pub b: f64,
+}
+
+impl Default for Bm25Params {
+    fn default() -> Self {
+        Bm25Params { k1: 1.5, b: 0.75 }
+    }
+}
+
+/// 순위를 매길 단위
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RankUnit {
+    /// 파일 하나가 문서 하나입니다.
+    #[default]
+    File,
+    /// 빈 줄로 나눈 문단 하나가 문서 하나입니다 (`--paragraphs`).
+    Paragraph,
+}
+
+/// `minigrep rank` 설정
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankOptions {
+    pub params: Bm25Params,
+    pub unit: RankUnit,
+    /// 출력할 최대 결과 수. 0 이면 전부 출력합니다 (`--top`).
+    pub top: usize,
+}
+
+impl Default for RankOptions {
+    fn default() -> Self {
+        RankOptions {
+            params: Bm25Params::default(),
+            unit: RankUnit::File,
+            top: 10,
+        }
+    }
+}
+
+/// 토큰(연속된 문자/숫자)들의 바이트 범위.
+/// `bm25-dojo` 의 `[a-z0-9가-힣]+` 를 유니코드 전체의 문자와 숫자로 넓혔습니다.
+pub fn token_spans(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, _) = chars.find(|&(_, c)| c.is_alphanumeric())?;
+        let mut end = text.len();
+        while let Some(&(i, c)) = chars.peek() {
+            if !c.is_alphanumeric() {
+                end = i;
+                break;
+            }
+            chars.next();
+        }
+        Some(start..end)
+    })
+}
+
+/// 텍스트를 소문자 토큰으로 나눕니다.
+pub fn tokenize(text: &str) -> Vec<String> {
+    token_spans(text)
+        .map(|span| text[span].to_lowercase())
+        .collect()
+}
+
+/// 빈 줄로 나뉜 문단 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paragraph {
+    /// 첫 줄과 마지막 줄 번호 (1부터)
+    pub lines: RangeInclusive<usize>,
+    /// 원문 기준 바이트 범위. 마지막 줄의 줄바꿈은 포함하지 않습니다.
+    pub range: Range<usize>,
+}
+
+/// 공백만 있는 줄을 경계로 문단을 나눕니다.
+pub fn paragraphs(text: &str) -> Vec<Paragraph> {
+    let mut paragraphs = Vec::new();
+    let mut current: Option<Paragraph> = None;
+    let mut offset = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        let start = offset;
+        offset += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            paragraphs.extend(current.take());
+            continue;
+        }
+        let end = start + content.len();
+        match &mut current {
+            Some(paragraph) => {
+                paragraph.lines = *paragraph.lines.start()..=i + 1;
+                paragraph.range.end = end;
+            }
+            None => {
+                current = Some(Paragraph {
+                    lines: i + 1..=i + 1,
+                    range: start..end,
+                })
+            }
+        }
+    }
+    paragraphs.extend(current);
+    paragraphs
+}
+
+/// 단어가 나오는 문서와 그 문서 안에서의 빈도
+#[derive(Debug, Clone, Copy)]
+struct Posting {
+    doc: usize,
+    tf: usize,
+}
+
+/// 점수가 매겨진 문서
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scored {
+    pub doc: usize,
+    pub score: f64,
+}
+
+/// 메모리 역색인. 문서 번호는 [`Index::add`] 한 순서대로 0부터 붙습니다.
+#[derive(Debug, Clone, Default)]
+pub struct Index {
+    /// 단어별 포스팅 목록. 문서 번호 오름차순입니다.
+    postings: HashMap<String, Vec<Posting>>,
+    doc_lengths: Vec<usize>,
+    total_length: usize,
+}
+
+impl Index {
+    pub fn new() -> Index {
+        Index::default()
+    }
+
+    /// 문서들로 색인을 만듭니다.
+    pub fn from_documents<'a>(documents: impl IntoIterator<Item = &'a str>) -> Index {
+        let mut index = Index::new();
+        for document in documents {
+            index.add(document);
+        }
+        index
+    }
+
+    /// 문서를 색인하고 문서 번호를 반환합니다.
+    pub fn add(&mut self, text: &str) -> usize {
+        let doc = self.doc_lengths.len();
+        let tokens = tokenize(text);
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for token in &tokens {
+            *counts.entry(token.clone()).or_default() += 1;
+        }
+        for (term, tf) in counts {
+            self.postings
+                .entry(term)
+                .or_default()
+                .push(Posting { doc, tf });
+        }
+        self.doc_lengths.push(tokens.len());
+        self.total_length += tokens.len();
+        doc
+    }
+
+    /// 문서 수 (N)
+    pub fn len(&self) -> usize {
+        self.doc_lengths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.doc_lengths.is_empty()
+    }
+
+    /// 문서의 토큰 수 (|D|)
+    pub fn doc_length(&self, doc: usize) -> usize {
+        self.doc_lengths[doc]
+    }
+
+    /// 평균 문서 길이 (avgdl)
+    pub fn avgdl(&self) -> f64 {
+        match self.len() {
+            0 => 0.0,
+            n => self.total_length as f64 / n as f64,
+        }
+    }
+
+    /// 단어가 나오는 문서 수 (n(q)). `term` 은 소문자 토큰이어야 합니다.
+    pub fn document_frequency(&self, term: &str) -> usize {
+        self.postings.get(term).map_or(0, Vec::len)
+    }
+
+    /// 문서 안에서 단어가 나온 횟수 (f(q, D))
+    pub fn term_frequency(&self, term: &str, doc: usize) -> usize {
+        let Some(postings) = self.postings.get(term) else {
+            return 0;
+        };
+        postings
+            .binary_search_by_key(&doc, |posting| posting.doc)
+            .map_or(0, |i| postings[i].tf)
+    }
+
+    /// 단어의 IDF. 색인에 없는 단어는 0 입니다.
+    pub fn idf(&self, term: &str) -> f64 {
+        let n = self.document_frequency(term) as f64;
+        if n == 0.0 {
+            return 0.0;
+        }
+        (1.0 + (self.len() as f64 - n + 0.5) / (n + 0.5)).ln()
+    }
+
+    /// 문서 하나의 BM25 점수
+    pub fn score(&self, query: &str, doc: usize, params: &Bm25Params) -> f64 {
+        query_terms(query)
+            .iter()
+            .map(|term| {
+                let tf = self.term_frequency(term, doc);
+                self.idf(term) * self.tf_component(tf, doc, params)
+            })
+            .sum()
+    }
+
+    /// 질의 단어가 하나라도 나오는 문서를 점수 내림차순으로 반환합니다.
+    /// 점수가 같으면 먼저 색인한 문서가 앞에 옵니다.
+    pub fn search(&self, query: &str, params: &Bm25Params) -> Vec<Scored> {
+        // 질의 단어의 포스팅만 훑어 점수를 누적합니다.
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for term in query_terms(query) {
+            let Some(postings) = self.postings.get(&term) else {
+                continue;
+            };
+            let idf = self.idf(&term);
+            for posting in postings {
+                *scores.entry(posting.doc).or_default() +=
+                    idf * self.tf_component(posting.tf, posting.doc, params);
+            }
+        }
+        let mut ranked: Vec<Scored> = scores
+            .into_iter()
+            .map(|(doc, score)| Scored { doc, score })
+            .collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.doc.cmp(&b.doc)));
+        ranked
+    }
+
+    /// 길이 정규화를 적용한 TF
+    fn tf_component(&self, tf: usize, doc: usize, params: &Bm25Params) -> f64 {
+        if tf == 0 {
+            return 0.0;
+        }
+        let tf = tf as f64;
+        let avgdl = self.avgdl();
+        // 토큰이 하나도 없는 말뭉치에서는 tf 가 0 이므로 여기까지 오지 않습니다.
+        let length_ratio = self.doc_length(doc) as f64 / avgdl;
+        let norm = params.k1 * (1.0 - params.b + params.b * length_ratio);
+        tf * (params.k1 + 1.0) / (tf + norm)
+    }
+}
+
+/// 질의의 토큰. 같은 단어가 여러 번 나와도 한 번만 셉니다.
+pub fn query_terms(query: &str) -> Vec<String> {
+    let mut terms = tokenize(query);
+    let mut seen = std::collections::HashSet::new();
+    terms.retain(|term| seen.insert(term.clone()));
+    terms
+}
+
+/// `text` 에서 `terms` 중 하나와 같은 토큰의 바이트 범위 (강조용)
+pub fn term_spans(text: &str, terms: &[String]) -> Vec<Range<usize>> {
+    token_spans(text)
+        .filter(|span| terms.contains(&text[span.clone()].to_lowercase()))
+        .collect()
+}
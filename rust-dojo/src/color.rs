@@ -0,0 +1,110 @@
+//! 터미널 색상 출력 (`--color`)
+//!
+//! 색상은 GNU grep 의 기본값을 따릅니다: 매치는 굵은 빨강, 경로는 자주색,
+//! 라인/컬럼 번호는 초록, 구분자(`:`, `-`, `--`)는 청록색입니다.
+//!
+//! `--color=auto`(기본값)는 표준 출력이 터미널일 때만 색을 쓰며,
+//! [`NO_COLOR`](https://no-color.org) 환경 변수가 비어 있지 않으면 끕니다.
+//! `--color=always` 는 `NO_COLOR` 보다 우선합니다.
+
+use std::ffi::OsStr;
+use std::io::IsTerminal;
+
+/// `--color` 값
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// 표준 출력이 터미널이고 `NO_COLOR` 가 없을 때만 색을 씁니다.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// `auto`, `always`, `never` 를 파싱합니다.
+    pub fn parse(value: &str) -> Option<ColorChoice> {
+        match value {
+            "auto" => Some(ColorChoice::Auto),
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            _ => None,
+        }
+    }
+
+    /// 주어진 환경에서 색을 쓸지 정합니다.
+    pub fn enabled(self, is_terminal: bool, no_color: Option<&OsStr>) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_terminal && no_color.is_none_or(|value| value.is_empty()),
+        }
+    }
+
+    /// 현재 프로세스의 표준 출력과 `NO_COLOR` 를 기준으로 색을 쓸지 정합니다.
+    pub fn enabled_for_stdout(self) -> bool {
+        let no_color = std::env::var_os("NO_COLOR");
+        self.enabled(std::io::stdout().is_terminal(), no_color.as_deref())
+    }
+}
+
+/// 출력 요소별 SGR 코드. 빈 문자열이면 색을 입히지 않습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub path: &'static str,
+    pub line_number: &'static str,
+    pub column: &'static str,
+    pub matched: &'static str,
+    pub separator: &'static str,
+}
+
+impl Palette {
+    /// 색 없음
+    pub const PLAIN: Palette = Palette {
+        path: "",
+        line_number: "",
+        column: "",
+        matched: "",
+        separator: "",
+    };
+
+    /// GNU grep 기본 색상
+    pub const ANSI: Palette = Palette {
+        path: "35",
+        line_number: "32",
+        column: "32",
+        matched: "01;31",
+        separator: "36",
+    };
+
+    /// `enabled` 에 따라 [`Palette::ANSI`] 나 [`Palette::PLAIN`] 을 고릅니다.
+    pub fn new(enabled: bool) -> Palette {
+        match enabled {
+            true => Palette::ANSI,
+            false => Palette::PLAIN,
+        }
+    }
+
+    pub fn is_plain(&self) -> bool {
+        *self == Palette::PLAIN
+    }
+}
+
+/// `text` 를 `code` 색으로 `out` 에 덧붙입니다.
+pub fn paint(out: &mut String, code: &str, text: &str) {
+    if code.is_empty() {
+        out.push_str(text);
+    } else {
+        out.push_str("\x1b[");
+        out.push_str(code);
+        out.push('m');
+        out.push_str(text);
+        out.push_str("\x1b[0m");
+    }
+}
+
+/// [`paint`] 의 결과를 새 문자열로 반환합니다.
+pub fn painted(code: &str, text: &str) -> String {
+    let mut out = String::new();
+    paint(&mut out, code, text);
+    out
+}
@@ -0,0 +1,129 @@
+//! 입력 인코딩 감지와 UTF-8 변환 (`--encoding`)
+//!
+//! 패턴 매칭은 UTF-8 바이트를 기준으로 하므로 다른 인코딩의 입력은 읽을 때 UTF-8 로 바꿉니다.
+//! 자동 감지는 입력 앞부분만 봅니다. BOM 이 있으면 BOM 을 따르고, 없으면 UTF-8 로 올바른지,
+//! 아니면 CP949 로 올바른지 차례로 확인합니다. BOM 없는 UTF-16 은 NUL 바이트 때문에
+//! 바이너리와 구별할 수 없으므로 `--encoding` 으로 지정해야 합니다.
+
+use std::fmt;
+
+use crate::cp949;
+
+/// 인코딩 감지에 쓰는 앞부분 바이트 수
+pub const SNIFF_LEN: usize = 8 * 1024;
+
+/// UTF-8 BOM
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const UTF16LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16BE_BOM: &[u8] = b"\xFE\xFF";
+
+/// 입력 인코딩
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// EUC-KR 과 그 확장인 CP949
+    Cp949,
+}
+
+impl Encoding {
+    /// 인코딩 이름을 파싱합니다. 대소문자와 `-`, `_` 는 무시합니다 (`UTF-16LE`, `euc_kr` 등).
+    pub fn parse(name: &str) -> Option<Encoding> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_'))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match normalized.as_str() {
+            "utf8" => Some(Encoding::Utf8),
+            "utf16le" => Some(Encoding::Utf16Le),
+            "utf16be" => Some(Encoding::Utf16Be),
+            "euckr" | "cp949" | "uhc" => Some(Encoding::Cp949),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Utf16Le => "utf-16le",
+            Encoding::Utf16Be => "utf-16be",
+            Encoding::Cp949 => "cp949",
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 앞부분의 BOM 으로 인코딩을 알아냅니다. BOM 의 길이도 함께 반환합니다.
+pub fn sniff_bom(bytes: &[u8]) -> Option<(Encoding, usize)> {
+    [
+        (UTF8_BOM, Encoding::Utf8),
+        (UTF16LE_BOM, Encoding::Utf16Le),
+        (UTF16BE_BOM, Encoding::Utf16Be),
+    ]
+    .into_iter()
+    .find(|(bom, _)| bytes.starts_with(bom))
+    .map(|(bom, encoding)| (encoding, bom.len()))
+}
+
+/// 입력의 앞 [`SNIFF_LEN`] 바이트로 인코딩을 추측합니다. 어느 쪽으로도 올바르지 않으면
+/// UTF-8 로 봅니다.
+///
+/// 앞부분은 문자 중간에서 잘렸을 수 있으므로 끝에 걸친 불완전한 문자는 허용합니다.
+/// NUL 이 있는 바이너리 입력은 바꾸지 않도록 UTF-8 로 둡니다.
+pub fn detect(bytes: &[u8]) -> Encoding {
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if let Some((encoding, _)) = sniff_bom(sample) {
+        return encoding;
+    }
+    if sample.contains(&0) {
+        return Encoding::Utf8;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => Encoding::Utf8,
+        // `error_len` 이 `None` 이면 끝에서 문자가 잘렸을 뿐입니다.
+        Err(e) if e.error_len().is_none() => Encoding::Utf8,
+        Err(_) if cp949::valid_up_to(sample) + 1 >= sample.len() => Encoding::Cp949,
+        Err(_) => Encoding::Utf8,
+    }
+}
+
+/// `bytes` 를 UTF-8 문자열로 바꿉니다. 같은 인코딩의 BOM 은 떼어 내고,
+/// 잘못된 바이트는 `U+FFFD` 로 바꿉니다.
+pub fn decode(bytes: &[u8], encoding: Encoding) -> String {
+    let bytes = match sniff_bom(bytes) {
+        Some((found, len)) if found == encoding => &bytes[len..],
+        _ => bytes,
+    };
+    match encoding {
+        Encoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        Encoding::Utf16Le => decode_utf16(bytes, u16::from_le_bytes),
+        Encoding::Utf16Be => decode_utf16(bytes, u16::from_be_bytes),
+        Encoding::Cp949 => cp949::decode(bytes),
+    }
+}
+
+/// 인코딩을 감지해 UTF-8 문자열로 바꿉니다.
+pub fn decode_auto(bytes: &[u8]) -> String {
+    decode(bytes, detect(bytes))
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let chunks = bytes.chunks_exact(2);
+    // 홀수 길이면 마지막 바이트는 문자를 이루지 못합니다.
+    let odd = !chunks.remainder().is_empty();
+    let units = chunks.map(|pair| unit([pair[0], pair[1]]));
+    let mut text: String = char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+    if odd {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+    text
+}
@@ -0,0 +1,386 @@
+//! 구조화된 로그의 필드 검색 (`--jsonl-field`, `--csv-column`)
+//!
+//! 라인 전체가 아니라 레코드에서 고른 필드 하나에만 패턴을 적용합니다.
+//!
+//! ```text
+//! --jsonl-field level=ERROR        JSON Lines 의 "level" 값이 정확히 ERROR 인 라인
+//! --jsonl-field req.path~/api/     중첩된 "req" 객체의 "path" 값에 /api/ 가 들어 있는 라인
+//! --csv-column 3~timeout           CSV 의 세 번째 열에 timeout 이 들어 있는 라인
+//! ```
+//!
+//! `=` 는 필드 값 전체가 패턴과 같아야 하고, `~` 는 값 안 어디서든 매칭되면 됩니다.
+//! `-i`, `-E`, `-w` 는 필드 값에 그대로 적용됩니다. 레코드는 한 라인이므로 CSV 의 따옴표
+//! 안 줄바꿈은 지원하지 않으며, 필드가 없거나 형식이 잘못된 라인은 매칭되지 않습니다.
+//!
+//! JSON 문자열 값은 이스케이프를 푼 뒤 비교하고, 숫자나 `true` 같은 다른 값은 원문 그대로
+//! 비교합니다. 매치 구간은 원본 라인 기준이며, 이스케이프를 푼 값이라 위치를 옮길 수 없으면
+//! 필드 전체를 매치 구간으로 삼습니다.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::literal::FindAt;
+use crate::Matcher;
+
+/// 검색할 필드
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldSelector {
+    /// JSON 객체의 키 경로. `req.path` 는 `["req", "path"]` 입니다.
+    Json(Vec<String>),
+    /// CSV 의 0부터 시작하는 열 번호
+    Csv(usize),
+}
+
+/// 필드 하나에 적용할 질의
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldQuery {
+    pub selector: FieldSelector,
+    /// `=`: 필드 값 전체가 패턴과 같아야 합니다. `~` 면 값의 일부만 매칭돼도 됩니다.
+    pub exact: bool,
+    /// 필드 값에 적용할 패턴
+    pub value: String,
+}
+
+impl FieldQuery {
+    /// `KEY=VALUE` 나 `KEY~PATTERN` 형식의 `--jsonl-field` 값을 파싱합니다.
+    pub fn parse_jsonl(spec: &str) -> Option<FieldQuery> {
+        let (key, exact, value) = split_spec(spec)?;
+        let path: Vec<String> = key.split('.').map(String::from).collect();
+        if path.iter().any(String::is_empty) {
+            return None;
+        }
+        Some(FieldQuery {
+            selector: FieldSelector::Json(path),
+            exact,
+            value: value.to_string(),
+        })
+    }
+
+    /// `N=VALUE` 나 `N~PATTERN` 형식의 `--csv-column` 값을 파싱합니다. `N` 은 1부터 셉니다.
+    pub fn parse_csv(spec: &str) -> Option<FieldQuery> {
+        let (column, exact, value) = split_spec(spec)?;
+        let column: usize = column.parse().ok()?;
+        Some(FieldQuery {
+            selector: FieldSelector::Csv(column.checked_sub(1)?),
+            exact,
+            value: value.to_string(),
+        })
+    }
+
+    /// 라인에서 선택한 필드를 찾습니다.
+    pub fn find_field<'l>(&self, line: &'l [u8]) -> Option<Field<'l>> {
+        match &self.selector {
+            FieldSelector::Json(path) => json_member(line, 0, path),
+            FieldSelector::Csv(index) => csv_column(line, *index),
+        }
+    }
+
+    /// 필드 값에 `inner` 를 적용하는 [`Matcher`] 를 만듭니다.
+    pub fn matcher<'a, M: Matcher + ?Sized>(&'a self, inner: &'a M) -> FieldMatcher<'a, M> {
+        FieldMatcher { query: self, inner }
+    }
+}
+
+/// 필드 이름과 값 사이의 첫 `=` 나 `~` 에서 나눕니다. 이름과 값이 비어 있으면 안 됩니다.
+fn split_spec(spec: &str) -> Option<(&str, bool, &str)> {
+    let at = spec.find(['=', '~'])?;
+    let (name, value) = (&spec[..at], &spec[at + 1..]);
+    if name.is_empty() || value.is_empty() {
+        return None;
+    }
+    Some((name, spec.as_bytes()[at] == b'=', value))
+}
+
+/// 라인에서 찾은 필드
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field<'l> {
+    /// 원본 라인에서 필드 값이 차지하는 바이트 범위 (따옴표 제외)
+    pub span: Range<usize>,
+    /// 따옴표와 이스케이프를 푼 값. 원본과 같으면 원본을 빌립니다.
+    pub value: Cow<'l, [u8]>,
+}
+
+/// 선택한 필드에만 패턴을 적용하는 [`Matcher`]
+#[derive(Debug, Clone, Copy)]
+pub struct FieldMatcher<'a, M: ?Sized> {
+    query: &'a FieldQuery,
+    inner: &'a M,
+}
+
+impl<M: Matcher + ?Sized> FieldMatcher<'_, M> {
+    /// 필드 값 기준의 매치 구간들. 조건에 맞지 않으면 `None` 입니다.
+    fn value_spans(&self, value: &[u8]) -> Option<Vec<Range<usize>>> {
+        if !self.query.exact {
+            return self
+                .inner
+                .is_match_line(value)
+                .then(|| self.inner.find_spans(value));
+        }
+        let spans = self.inner.find_spans(value);
+        (spans.first() == Some(&(0..value.len()))).then_some(spans)
+    }
+}
+
+impl<M: Matcher + ?Sized> Matcher for FieldMatcher<'_, M> {
+    fn is_match_line(&self, line: &[u8]) -> bool {
+        self.query
+            .find_field(line)
+            .is_some_and(|field| self.value_spans(&field.value).is_some())
+    }
+
+    fn find_spans(&self, line: &[u8]) -> Vec<Range<usize>> {
+        let Some(field) = self.query.find_field(line) else {
+            return Vec::new();
+        };
+        let Some(spans) = self.value_spans(&field.value) else {
+            return Vec::new();
+        };
+        match field.value {
+            Cow::Borrowed(_) => spans
+                .into_iter()
+                .map(|span| span.start + field.span.start..span.end + field.span.start)
+                .collect(),
+            Cow::Owned(_) => vec![field.span],
+        }
+    }
+
+    fn buffer_finder(&self) -> Option<&dyn FindAt> {
+        None
+    }
+}
+
+// =============================================================================
+// JSON
+// =============================================================================
+
+fn skip_ws(line: &[u8], mut pos: usize) -> usize {
+    while matches!(line.get(pos), Some(b' ' | b'\t' | b'\r' | b'\n')) {
+        pos += 1;
+    }
+    pos
+}
+
+fn expect(line: &[u8], pos: usize, byte: u8) -> Option<usize> {
+    (line.get(pos) == Some(&byte)).then_some(pos + 1)
+}
+
+/// `pos` 의 객체에서 `path` 를 따라 내려간 값을 찾습니다.
+fn json_member<'l>(line: &'l [u8], pos: usize, path: &[String]) -> Option<Field<'l>> {
+    let (wanted, rest) = path.split_first()?;
+    let mut pos = skip_ws(line, expect(line, skip_ws(line, pos), b'{')?);
+    if line.get(pos) == Some(&b'}') {
+        return None;
+    }
+    loop {
+        let key = json_string(line, pos)?;
+        pos = skip_ws(line, expect(line, skip_ws(line, key.span.end + 1), b':')?);
+        if *key.value == *wanted.as_bytes() {
+            return match rest {
+                [] => json_value(line, pos),
+                _ => json_member(line, pos, rest),
+            };
+        }
+        pos = skip_ws(line, skip_value(line, pos)?);
+        pos = skip_ws(line, expect(line, pos, b',')?);
+    }
+}
+
+/// `pos` 의 값을 필드로 만듭니다. 문자열은 따옴표 안쪽, 나머지는 원문 전체입니다.
+fn json_value(line: &[u8], pos: usize) -> Option<Field<'_>> {
+    if line.get(pos) == Some(&b'"') {
+        return json_string(line, pos);
+    }
+    let end = skip_value(line, pos)?;
+    Some(Field {
+        span: pos..end,
+        value: Cow::Borrowed(&line[pos..end]),
+    })
+}
+
+/// `pos` 의 문자열을 읽습니다. 필드의 `span` 은 따옴표 안쪽입니다.
+fn json_string(line: &[u8], pos: usize) -> Option<Field<'_>> {
+    let start = expect(line, pos, b'"')?;
+    let mut i = start;
+    let mut escaped = false;
+    loop {
+        match *line.get(i)? {
+            b'"' => break,
+            b'\\' => {
+                escaped = true;
+                i += 2;
+            }
+            _ => i += 1,
+        }
+    }
+    let raw = &line[start..i];
+    let value = match escaped {
+        true => Cow::Owned(unescape(raw)?),
+        false => Cow::Borrowed(raw),
+    };
+    Some(Field {
+        span: start..i,
+        value,
+    })
+}
+
+/// JSON 문자열의 이스케이프를 풉니다. 짝이 맞지 않는 서로게이트는 U+FFFD 가 됩니다.
+fn unescape(raw: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] != b'\\' {
+            out.push(raw[i]);
+            i += 1;
+            continue;
+        }
+        let simple = match *raw.get(i + 1)? {
+            b'"' => b'"',
+            b'\\' => b'\\',
+            b'/' => b'/',
+            b'b' => 0x08,
+            b'f' => 0x0C,
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b'u' => {
+                let unit = hex4(raw.get(i + 2..i + 6)?)?;
+                i += 6;
+                let low = match (0xD800..0xDC00).contains(&unit) && raw[i..].starts_with(b"\\u") {
+                    true => raw.get(i + 2..i + 6).and_then(hex4),
+                    false => None,
+                };
+                let c = match low {
+                    Some(low @ 0xDC00..0xE000) => {
+                        i += 6;
+                        char::from_u32(0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00))
+                    }
+                    _ => char::from_u32(unit),
+                };
+                let mut buf = [0; 4];
+                let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+                out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                continue;
+            }
+            _ => return None,
+        };
+        out.push(simple);
+        i += 2;
+    }
+    Some(out)
+}
+
+fn hex4(digits: &[u8]) -> Option<u32> {
+    std::str::from_utf8(digits)
+        .ok()
+        .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+}
+
+/// `pos` 의 값을 건너뛴 다음 위치. 객체와 배열은 괄호 짝만 맞춰 건너뜁니다.
+fn skip_value(line: &[u8], pos: usize) -> Option<usize> {
+    match *line.get(pos)? {
+        b'"' => json_string(line, pos).map(|field| field.span.end + 1),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut i = pos;
+            loop {
+                match *line.get(i)? {
+                    b'"' => {
+                        i = json_string(line, i)?.span.end + 1;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+        }
+        _ => {
+            let end = line[pos..]
+                .iter()
+                .position(|b| b",}] \t\r\n".contains(b))
+                .map_or(line.len(), |i| pos + i);
+            (end > pos).then_some(end)
+        }
+    }
+}
+
+// =============================================================================
+// CSV
+// =============================================================================
+
+/// `index` 번째 열을 찾습니다. 따옴표로 감싼 열은 `""` 를 `"` 로 풉니다.
+fn csv_column(line: &[u8], index: usize) -> Option<Field<'_>> {
+    let mut pos = 0;
+    for column in 0.. {
+        let (field, end) = csv_cell(line, pos);
+        if column == index {
+            return Some(field);
+        }
+        if end >= line.len() {
+            break;
+        }
+        pos = end + 1;
+    }
+    None
+}
+
+/// `pos` 에서 시작하는 열과, 그 열 뒤의 `,` 위치(없으면 라인 길이)
+fn csv_cell(line: &[u8], pos: usize) -> (Field<'_>, usize) {
+    let next_comma = |from: usize| {
+        line[from.min(line.len())..]
+            .iter()
+            .position(|&b| b == b',')
+            .map_or(line.len(), |i| from + i)
+    };
+    if line.get(pos) != Some(&b'"') {
+        let end = next_comma(pos);
+        let field = Field {
+            span: pos..end,
+            value: Cow::Borrowed(&line[pos..end]),
+        };
+        return (field, end);
+    }
+    let start = pos + 1;
+    let mut i = start;
+    let mut escaped = false;
+    // 닫는 따옴표가 없으면 라인 끝까지를 값으로 봅니다.
+    while i < line.len() {
+        match (line[i], line.get(i + 1)) {
+            (b'"', Some(b'"')) => {
+                escaped = true;
+                i += 2;
+            }
+            (b'"', _) => break,
+            _ => i += 1,
+        }
+    }
+    let raw = &line[start..i];
+    let value = match escaped {
+        true => {
+            let mut out = Vec::with_capacity(raw.len());
+            let mut bytes = raw.iter();
+            while let Some(&b) = bytes.next() {
+                if b == b'"' {
+                    // `""` 의 두 번째 따옴표
+                    bytes.next();
+                }
+                out.push(b);
+            }
+            Cow::Owned(out)
+        }
+        false => Cow::Borrowed(raw),
+    };
+    (
+        Field {
+            span: start..i,
+            value,
+        },
+        next_comma(i + 1),
+    )
+}
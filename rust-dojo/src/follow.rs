@@ -0,0 +1,234 @@
+//! 파일 따라가기 (`--follow`)
+//!
+//! `tail -F | grep` 처럼 파일 끝에 덧붙는 내용을 계속 읽으며 검색합니다.
+//! 처음에는 마지막 [`INITIAL_LINES`] 줄부터 읽고, 그 뒤로는 [`POLL_INTERVAL`] 마다
+//! 파일을 다시 확인합니다.
+//!
+//! - 파일이 읽은 위치보다 짧아지면 잘린 것으로 보고 처음부터 다시 읽습니다.
+//! - 경로가 다른 파일을 가리키게 되면(로그 로테이션) 이전 파일에 남은 내용을 마저 읽은 뒤
+//!   새 파일을 처음부터 읽습니다. 같은 파일인지는 유닉스에서 장치와 inode 번호로 비교합니다.
+//! - 파일이 아직 없거나 잠시 사라져도 다시 생길 때까지 기다립니다.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::stream;
+
+/// 파일을 다시 확인하는 간격
+pub const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 처음 열었을 때 기존 내용 가운데 검색할 마지막 줄 수 (`tail` 의 기본값)
+pub const INITIAL_LINES: usize = 10;
+
+/// 따라가는 중에 생긴 일
+#[derive(Debug)]
+pub enum Event<'a> {
+    /// 완성된 라인 하나. 줄 끝 문자는 뺀 내용입니다.
+    Line { number: usize, bytes: &'a [u8] },
+    /// 파일이 잘려 처음부터 다시 읽습니다.
+    Truncated,
+    /// 경로가 새 파일을 가리켜 새 파일을 처음부터 읽습니다.
+    Replaced,
+    /// 기다리던 파일이 생겨 처음부터 읽습니다.
+    Appeared,
+    /// 파일을 열 수 없어 다시 생길 때까지 기다립니다. 기다리기 시작할 때 한 번만 알립니다.
+    Waiting(io::Error),
+}
+
+/// 파일 하나를 따라가는 상태
+#[derive(Debug)]
+pub struct Follower {
+    path: PathBuf,
+    file: Option<fs::File>,
+    identity: Option<FileId>,
+    /// 다음에 읽을 위치
+    position: u64,
+    /// 마지막으로 내보낸 라인 번호
+    line_number: usize,
+    /// 아직 줄바꿈이 오지 않은 마지막 라인 조각
+    partial: Vec<u8>,
+    /// 첫 확인을 마쳤는지 여부. 첫 확인 때 있던 파일만 기존 내용을 건너뜁니다.
+    started: bool,
+    waiting: bool,
+}
+
+impl Follower {
+    pub fn new(path: impl AsRef<Path>) -> Follower {
+        Follower {
+            path: path.as_ref().to_path_buf(),
+            file: None,
+            identity: None,
+            position: 0,
+            line_number: 0,
+            partial: Vec::new(),
+            started: false,
+            waiting: false,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 지난번 이후 덧붙은 내용을 읽어 완성된 라인마다 `emit` 을 호출합니다.
+    /// 기다리지 않고 바로 돌아오므로 호출하는 쪽에서 [`POLL_INTERVAL`] 만큼 쉬어야 합니다.
+    pub fn poll<F>(&mut self, mut emit: F) -> io::Result<()>
+    where
+        F: FnMut(Event<'_>) -> io::Result<()>,
+    {
+        let opened = self.file.is_some() || self.open(&mut emit)?;
+        self.started = true;
+        if !opened {
+            return Ok(());
+        }
+        self.read_appended(&mut emit)?;
+
+        // 경로가 다른 파일을 가리키면 남은 조각을 마지막 라인으로 내보내고 새 파일로 옮깁니다.
+        // 경로에 아무 파일도 없으면 새 파일이 생길 때까지 이전 파일을 계속 읽습니다.
+        let replaced = match fs::metadata(&self.path) {
+            Ok(metadata) => file_id(&metadata) != self.identity,
+            Err(_) => false,
+        };
+        if replaced {
+            self.flush_partial(&mut emit)?;
+            self.file = None;
+            if self.open(&mut emit)? {
+                emit(Event::Replaced)?;
+                self.read_appended(&mut emit)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 파일을 엽니다. 열지 못하면 `Waiting` 을 알리고 `false` 를 반환합니다.
+    fn open<F>(&mut self, emit: &mut F) -> io::Result<bool>
+    where
+        F: FnMut(Event<'_>) -> io::Result<()>,
+    {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) => {
+                if !self.waiting {
+                    self.waiting = true;
+                    emit(Event::Waiting(e))?;
+                }
+                return Ok(false);
+            }
+        };
+        let metadata = file.metadata()?;
+        if !metadata.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}: not a regular file", self.path.display()),
+            ));
+        }
+        self.identity = file_id(&metadata);
+        (self.position, self.line_number) = match self.started {
+            true => (0, 0),
+            false => tail_start(&file)?,
+        };
+        self.partial.clear();
+        self.file = Some(file);
+        if self.waiting {
+            self.waiting = false;
+            emit(Event::Appeared)?;
+        }
+        Ok(true)
+    }
+
+    /// 읽은 위치 뒤의 내용을 읽습니다. 파일이 그보다 짧아졌으면 처음부터 읽습니다.
+    fn read_appended<F>(&mut self, emit: &mut F) -> io::Result<()>
+    where
+        F: FnMut(Event<'_>) -> io::Result<()>,
+    {
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+        if file.metadata()?.len() < self.position {
+            self.position = 0;
+            self.line_number = 0;
+            self.partial.clear();
+            emit(Event::Truncated)?;
+        }
+        file.seek(SeekFrom::Start(self.position))?;
+        let mut appended = Vec::new();
+        self.position += file.read_to_end(&mut appended)? as u64;
+
+        let mut rest = appended.as_slice();
+        while let Some(end) = rest.iter().position(|&b| b == b'\n') {
+            self.partial.extend_from_slice(&rest[..=end]);
+            rest = &rest[end + 1..];
+            self.line_number += 1;
+            emit(Event::Line {
+                number: self.line_number,
+                bytes: stream::trim_line_end(&self.partial),
+            })?;
+            self.partial.clear();
+        }
+        self.partial.extend_from_slice(rest);
+        Ok(())
+    }
+
+    /// 이전 파일이 줄바꿈 없이 끝났으면 마지막 조각을 라인으로 내보냅니다.
+    fn flush_partial<F>(&mut self, emit: &mut F) -> io::Result<()>
+    where
+        F: FnMut(Event<'_>) -> io::Result<()>,
+    {
+        if self.partial.is_empty() {
+            return Ok(());
+        }
+        self.line_number += 1;
+        emit(Event::Line {
+            number: self.line_number,
+            bytes: stream::trim_line_end(&self.partial),
+        })?;
+        self.partial.clear();
+        Ok(())
+    }
+}
+
+/// 기존 내용의 마지막 [`INITIAL_LINES`] 줄이 시작하는 위치와 그 앞의 라인 수
+fn tail_start(file: &fs::File) -> io::Result<(u64, usize)> {
+    let mut reader = io::BufReader::new(file);
+    let mut starts = VecDeque::with_capacity(INITIAL_LINES + 1);
+    let mut buf = Vec::new();
+    let mut offset = 0;
+    let mut lines = 0;
+    loop {
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf)?;
+        if read == 0 {
+            break;
+        }
+        starts.push_back(offset);
+        if starts.len() > INITIAL_LINES {
+            starts.pop_front();
+        }
+        offset += read as u64;
+        lines += 1;
+    }
+    let start = starts.front().copied().unwrap_or(offset);
+    Ok((start, lines - starts.len()))
+}
+
+/// 같은 파일인지 비교하기 위한 (장치, inode) 번호
+#[cfg(unix)]
+type FileId = (u64, u64);
+
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// inode 가 없는 플랫폼에서는 로테이션을 감지하지 않고 잘림만 감지합니다.
+#[cfg(not(unix))]
+type FileId = ();
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<FileId> {
+    None
+}
@@ -0,0 +1,198 @@
+//! 근사 검색 (`--fuzzy K`)
+//!
+//! 질의와 편집 거리(Levenshtein, 삽입/삭제/치환 각 1)가 K 이하인 부분 문자열이 있는 라인을 찾습니다.
+//! 거리는 바이트가 아닌 문자 단위로 셉니다.
+//!
+//! 질의가 64 글자 이하면 Myers 의 비트 병렬 알고리즘으로 DP 표의 한 열을 `u64` 두 개로
+//! 표현해 글자마다 상수 번의 비트 연산으로 갱신합니다. 더 긴 질의는 같은 결과를 내는
+//! 일반 DP(Sellers)로 계산합니다.
+
+use std::ops::Range;
+
+use crate::unicode;
+
+/// 비트 병렬로 처리할 수 있는 최대 질의 길이
+const WORD_BITS: usize = 64;
+
+/// 근사 매치 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyFound {
+    /// 질의와 매치 구간 사이의 편집 거리
+    pub distance: usize,
+    /// 매치 구간의 바이트 범위
+    pub range: Range<usize>,
+}
+
+/// 컴파일된 근사 검색 질의
+#[derive(Debug, Clone)]
+pub struct FuzzyPattern {
+    query: Vec<char>,
+    max_distance: usize,
+    case_insensitive: bool,
+    /// ASCII 문자별로 질의에서 나타나는 위치의 비트 마스크
+    ascii_masks: [u64; 128],
+    /// ASCII 가 아닌 문자의 비트 마스크
+    other_masks: Vec<(char, u64)>,
+}
+
+impl FuzzyPattern {
+    /// `case_insensitive` 면 [`unicode::simple_fold`] 로 접은 문자끼리 비교합니다.
+    pub fn new(query: &str, max_distance: usize, case_insensitive: bool) -> FuzzyPattern {
+        let fold = |c: char| match case_insensitive {
+            true => unicode::simple_fold(c),
+            false => c,
+        };
+        let query: Vec<char> = query.chars().map(fold).collect();
+        let mut ascii_masks = [0; 128];
+        let mut other_masks: Vec<(char, u64)> = Vec::new();
+        for (i, &c) in query.iter().enumerate().take(WORD_BITS) {
+            let bit = 1 << i;
+            if c.is_ascii() {
+                ascii_masks[c as usize] |= bit;
+            } else if let Some(entry) = other_masks.iter_mut().find(|(o, _)| *o == c) {
+                entry.1 |= bit;
+            } else {
+                other_masks.push((c, bit));
+            }
+        }
+        FuzzyPattern {
+            query,
+            max_distance,
+            case_insensitive,
+            ascii_masks,
+            other_masks,
+        }
+    }
+
+    /// 허용하는 최대 편집 거리
+    pub fn max_distance(&self) -> usize {
+        self.max_distance
+    }
+
+    fn fold(&self, c: char) -> char {
+        match self.case_insensitive {
+            true => unicode::simple_fold(c),
+            false => c,
+        }
+    }
+
+    fn mask(&self, c: char) -> u64 {
+        let c = self.fold(c);
+        if c.is_ascii() {
+            return self.ascii_masks[c as usize];
+        }
+        self.other_masks
+            .iter()
+            .find(|&&(o, _)| o == c)
+            .map_or(0, |&(_, mask)| mask)
+    }
+
+    /// `text` 의 부분 문자열과 질의 사이의 최소 편집 거리.
+    /// [`FuzzyPattern::max_distance`] 보다 크면 `None` 입니다.
+    pub fn distance(&self, text: &str) -> Option<usize> {
+        self.find(text).map(|found| found.distance)
+    }
+
+    /// 편집 거리가 가장 작은 매치. 거리가 같으면 가장 먼저 나오는 매치입니다.
+    /// 같은 매치가 거리 D 만큼의 글자 안에서 여러 번 끝날 수 있으므로(`recie`, `recieve`)
+    /// 그 가운데 가장 늦게 끝나는 위치를 씁니다.
+    pub fn find(&self, text: &str) -> Option<FuzzyFound> {
+        let (distance, end) = self.best_end(text)?;
+        let start = self.best_start(text, end, distance);
+        Some(FuzzyFound {
+            distance,
+            range: start..end,
+        })
+    }
+
+    /// 최소 거리와 그 매치가 끝나는 바이트 위치
+    fn best_end(&self, text: &str) -> Option<(usize, usize)> {
+        let m = self.query.len();
+        // 빈 부분 문자열과의 거리는 질의 길이입니다.
+        let mut best = (m, 0);
+        // 최소 거리가 처음 나온 위치(글자 수)와 지금까지 읽은 글자 수
+        let mut anchor = 0;
+        let mut read = 0;
+        let mut consider = |score: usize, end: usize| {
+            read += 1;
+            if score < best.0 {
+                best = (score, end);
+                anchor = read;
+            } else if score == best.0 && read - anchor <= score {
+                best.1 = end;
+            }
+        };
+        if m == 0 {
+            return Some((0, 0));
+        }
+        if m <= WORD_BITS {
+            // Myers (1999): 세로 방향 차이를 양수(pv)/음수(mv) 비트 벡터로 표현합니다.
+            let high = 1u64 << (m - 1);
+            let mut pv = u64::MAX;
+            let mut mv = 0u64;
+            let mut score = m;
+            for (i, c) in text.char_indices() {
+                let eq = self.mask(c);
+                let xv = eq | mv;
+                let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+                let mut ph = mv | !(xh | pv);
+                let mut mh = pv & xh;
+                if ph & high != 0 {
+                    score += 1;
+                } else if mh & high != 0 {
+                    score -= 1;
+                }
+                // 첫 행은 항상 0 이므로(어디서든 매치를 시작할 수 있음) 새 비트는 0 입니다.
+                ph <<= 1;
+                mh <<= 1;
+                pv = mh | !(xv | ph);
+                mv = ph & xv;
+                consider(score, i + c.len_utf8());
+            }
+        } else {
+            let mut column: Vec<usize> = (0..=m).collect();
+            for (i, c) in text.char_indices() {
+                // 접은 글자는 UTF-8 길이가 다를 수 있으므로 비교에만 씁니다.
+                let folded = self.fold(c);
+                let mut diagonal = column[0];
+                for (j, &q) in self.query.iter().enumerate() {
+                    let substitution = diagonal + usize::from(q != folded);
+                    diagonal = column[j + 1];
+                    column[j + 1] = substitution.min(column[j] + 1).min(diagonal + 1);
+                }
+                consider(column[m], i + c.len_utf8());
+            }
+        }
+        (best.0 <= self.max_distance).then_some(best)
+    }
+
+    /// `end` 에서 끝나고 질의와의 거리가 `distance` 인 구간의 시작 위치.
+    /// 뒤집은 질의를 `end` 부터 거꾸로 정렬해, 거리가 같으면 길이가 질의에 가장 가까운 구간을 고릅니다.
+    fn best_start(&self, text: &str, end: usize, distance: usize) -> usize {
+        let m = self.query.len();
+        let window: Vec<(usize, char)> = text[..end]
+            .char_indices()
+            .rev()
+            .take(m + distance)
+            .collect();
+        // row[j]: 질의의 마지막 i 글자와 `end` 앞 j 글자 사이의 거리
+        let mut row: Vec<usize> = (0..=window.len()).collect();
+        for (i, &q) in self.query.iter().rev().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for (j, &(_, c)) in window.iter().enumerate() {
+                let substitution = diagonal + usize::from(q != self.fold(c));
+                diagonal = row[j + 1];
+                row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+            }
+        }
+        let length = (0..row.len())
+            .filter(|&j| row[j] == distance)
+            .min_by_key(|&j| j.abs_diff(m))
+            .unwrap_or(0);
+        match length {
+            0 => end,
+            n => window[n - 1].0,
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! 글롭(glob) 패턴
+//!
+//! `--glob`, `--type`, `.gitignore` 가 공통으로 사용하는 경로 패턴입니다.
+//! 글롭을 정규식으로 번역한 뒤 [`Pattern`] 엔진으로 매칭하며,
+//! 경로 구분자는 항상 `/` 로 취급합니다.
+//!
+//! 지원 문법:
+//! - `*`: `/` 를 제외한 0개 이상의 문자
+//! - `?`: `/` 를 제외한 문자 하나
+//! - `**`: 경로 구분자를 포함한 0개 이상의 디렉토리 (`**/a`, `a/**`, `a/**/b`)
+//! - `[abc]`, `[a-z]`, `[!a-z]`: 문자 클래스 (`/` 는 포함하지 않음)
+//! - `{rs,toml}`: 대안
+//! - `\*`: 이스케이프
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{Pattern, PatternError};
+
+/// 글롭 파싱 에러
+#[derive(Debug, Clone, PartialEq)]
+pub enum GlobError {
+    /// 닫히지 않은 `[`
+    UnclosedClass(usize),
+    /// 닫히지 않은 `{`
+    UnclosedAlternates(usize),
+    /// 열리지 않은 `}`
+    UnopenedAlternates(usize),
+    /// 번역된 정규식을 컴파일하지 못했습니다.
+    Pattern(PatternError),
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobError::UnclosedClass(pos) => write!(f, "unclosed '[' at {pos}"),
+            GlobError::UnclosedAlternates(pos) => write!(f, "unclosed '{{' at {pos}"),
+            GlobError::UnopenedAlternates(pos) => write!(f, "unopened '}}' at {pos}"),
+            GlobError::Pattern(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for GlobError {}
+
+/// 컴파일된 글롭
+#[derive(Debug, Clone)]
+pub struct Glob {
+    source: String,
+    pattern: Pattern,
+}
+
+impl Glob {
+    /// 글롭을 컴파일합니다. 경로 전체가 매칭되어야 합니다.
+    pub fn new(glob: &str) -> Result<Glob, GlobError> {
+        let regex = translate(glob)?;
+        let pattern = Pattern::new(&regex).map_err(GlobError::Pattern)?;
+        Ok(Glob {
+            source: glob.to_string(),
+            pattern,
+        })
+    }
+
+    /// 글롭 원문
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// `/` 로 구분된 경로 전체가 글롭에 매칭되는지 확인합니다.
+    pub fn is_match(&self, path: &str) -> bool {
+        self.pattern.is_match(path)
+    }
+}
+
+/// 글롭을 `\A ... \z` 로 고정된 정규식으로 번역합니다.
+fn translate(glob: &str) -> Result<String, GlobError> {
+    let chars: Vec<(usize, char)> = glob.char_indices().collect();
+    let mut regex = String::from(r"\A");
+    let mut alternates = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        match c {
+            '*' if chars.get(i + 1).map(|&(_, c)| c) == Some('*') => {
+                let at_start = i == 0 || chars[i - 1].1 == '/';
+                let next = chars.get(i + 2).map(|&(_, c)| c);
+                if at_start && next == Some('/') {
+                    // `**/`: 0개 이상의 디렉토리
+                    regex.push_str("(?:.*/)?");
+                    i += 3;
+                    continue;
+                }
+                if at_start && next.is_none() {
+                    // 끝의 `**`: 그 아래 모든 것
+                    regex.push_str(".*");
+                    i += 2;
+                    continue;
+                }
+                // 그 밖의 `**` 는 `*` 와 같습니다.
+                regex.push_str("[^/]*");
+                i += 2;
+                continue;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                i = translate_class(&chars, i, &mut regex)?;
+                continue;
+            }
+            '{' => {
+                alternates.push(offset);
+                regex.push_str("(?:");
+            }
+            ',' if !alternates.is_empty() => regex.push('|'),
+            '}' => {
+                alternates
+                    .pop()
+                    .ok_or(GlobError::UnopenedAlternates(offset))?;
+                regex.push(')');
+            }
+            '\\' => {
+                i += 1;
+                if let Some(&(_, escaped)) = chars.get(i) {
+                    push_literal(&mut regex, escaped);
+                }
+            }
+            c => push_literal(&mut regex, c),
+        }
+        i += 1;
+    }
+    if let Some(&offset) = alternates.last() {
+        return Err(GlobError::UnclosedAlternates(offset));
+    }
+    regex.push_str(r"\z");
+    Ok(regex)
+}
+
+/// `chars[start]` 의 `[` 부터 클래스를 번역하고 `]` 다음 위치를 반환합니다.
+fn translate_class(
+    chars: &[(usize, char)],
+    start: usize,
+    regex: &mut String,
+) -> Result<usize, GlobError> {
+    let mut i = start + 1;
+    regex.push('[');
+    if matches!(chars.get(i), Some((_, '!' | '^'))) {
+        // 부정 클래스도 경로 구분자는 넘지 않습니다.
+        regex.push_str("^/");
+        i += 1;
+    }
+    let mut first = true;
+    loop {
+        let &(_, c) = chars
+            .get(i)
+            .ok_or(GlobError::UnclosedClass(chars[start].0))?;
+        i += 1;
+        match c {
+            ']' if !first => break,
+            '\\' => {
+                let &(_, escaped) = chars
+                    .get(i)
+                    .ok_or(GlobError::UnclosedClass(chars[start].0))?;
+                i += 1;
+                push_class_char(regex, escaped);
+            }
+            '-' if !first && chars.get(i).is_some_and(|&(_, c)| c != ']') => regex.push('-'),
+            c => push_class_char(regex, c),
+        }
+        first = false;
+    }
+    regex.push(']');
+    Ok(i)
+}
+
+fn push_literal(regex: &mut String, c: char) {
+    if c.is_ascii_punctuation() {
+        regex.push('\\');
+    }
+    regex.push(c);
+}
+
+fn push_class_char(regex: &mut String, c: char) {
+    if matches!(c, '\\' | ']' | '[' | '^' | '-') {
+        regex.push('\\');
+    }
+    regex.push(c);
+}
@@ -0,0 +1,625 @@
+//! gzip 압축 해제 (RFC 1951 DEFLATE, RFC 1952 gzip)
+//!
+//! 외부 크레이트 없이 압축된 로그를 검색하기 위한 최소 구현입니다. 압축은 하지 않으며,
+//! zstd 같은 다른 형식은 아직 알아보지 못하고 바이너리 파일로 건너뜁니다.
+//!
+//! 허프만 부호는 다음 9 비트로 바로 찾는 표를 먼저 보고, 더 긴 부호만 zlib 의 `puff` 처럼
+//! 길이별 부호 개수를 따라 한 비트씩 풉니다.
+//!
+//! [`GzDecoder`] 는 입력을 조금씩 읽으면서 풀기 때문에 큰 압축 파일도 거리 참조에 필요한
+//! 최근 32 KiB 와 읽는 쪽에 넘겨줄 덩어리만 메모리에 둡니다.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Read};
+
+/// gzip 파일의 첫 두 바이트
+pub const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// 표로 바로 찾는 허프만 부호의 최대 길이
+const FAST_BITS: u32 = 9;
+/// DEFLATE 거리 참조가 닿을 수 있는 최대 거리
+const WINDOW: usize = 32 * 1024;
+/// 한 번에 풀어 두는 양. 읽는 쪽이 다 가져가면 다음 덩어리를 풉니다.
+const CHUNK: usize = 64 * 1024;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// 동적 블록에서 코드 길이 부호의 길이가 나오는 순서
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// 압축 해제 에러
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InflateError {
+    /// 스트림이 끝나기 전에 입력이 끝났습니다.
+    UnexpectedEof,
+    /// gzip 헤더가 잘못되었거나 지원하지 않는 압축 방식입니다.
+    InvalidHeader,
+    /// 블록 종류 3 은 예약되어 있습니다.
+    InvalidBlockType,
+    /// 비압축 블록의 길이와 그 보수가 맞지 않습니다.
+    StoredLengthMismatch,
+    /// 허프만 부호 표나 부호가 잘못되었습니다.
+    InvalidCode,
+    /// 거리가 지금까지 푼 데이터보다 깁니다.
+    InvalidDistance,
+    /// 트레일러의 CRC-32 가 풀린 데이터와 다릅니다.
+    ChecksumMismatch,
+    /// 트레일러의 길이가 풀린 데이터와 다릅니다.
+    SizeMismatch,
+}
+
+impl fmt::Display for InflateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            InflateError::UnexpectedEof => "unexpected end of compressed data",
+            InflateError::InvalidHeader => "invalid gzip header",
+            InflateError::InvalidBlockType => "invalid deflate block type",
+            InflateError::StoredLengthMismatch => "stored block length mismatch",
+            InflateError::InvalidCode => "invalid huffman code",
+            InflateError::InvalidDistance => "distance too far back",
+            InflateError::ChecksumMismatch => "gzip checksum mismatch",
+            InflateError::SizeMismatch => "gzip length mismatch",
+        };
+        f.write_str(message)
+    }
+}
+
+impl Error for InflateError {}
+
+/// gzip 으로 시작하는 데이터인지 확인합니다.
+pub fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&MAGIC)
+}
+
+/// gzip 데이터를 풉니다. 여러 멤버를 이어 붙인 파일(`cat a.gz b.gz`)은 차례로 이어서 풉니다.
+/// 마지막 멤버 뒤에 gzip 이 아닌 바이트(테이프 패딩 등)가 있으면 무시합니다.
+pub fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, InflateError> {
+    Inflater::new(bytes, true).read_all()
+}
+
+/// raw DEFLATE 스트림을 풉니다.
+pub fn inflate(bytes: &[u8]) -> Result<Vec<u8>, InflateError> {
+    Inflater::new(bytes, false).read_all()
+}
+
+/// 읽으면서 gzip 을 푸는 리더. [`gunzip`] 과 같이 여러 멤버를 이어서 풀고 뒤의 쓰레기는 무시합니다.
+///
+/// 압축 해제 에러는 [`io::ErrorKind::InvalidData`] 로, 입력을 읽다 난 에러는 그대로 돌려줍니다.
+pub struct GzDecoder<R> {
+    inflater: Inflater<R>,
+}
+
+impl<R: BufRead> GzDecoder<R> {
+    pub fn new(inner: R) -> GzDecoder<R> {
+        GzDecoder {
+            inflater: Inflater::new(inner, true),
+        }
+    }
+}
+
+impl<R: BufRead> Read for GzDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for GzDecoder<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let inflater = &mut self.inflater;
+        if inflater.pos == inflater.out.len() {
+            if let Err(e) = inflater.fill() {
+                let e = match inflater.bits.error.take() {
+                    Some(e) => e,
+                    None => io::Error::new(io::ErrorKind::InvalidData, e),
+                };
+                return Err(e);
+            }
+        }
+        Ok(&inflater.out[inflater.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let inflater = &mut self.inflater;
+        inflater.pos = (inflater.pos + amt).min(inflater.out.len());
+    }
+}
+
+/// 압축 해제가 어디까지 진행됐는지
+enum State {
+    /// gzip 멤버 헤더를 읽을 차례
+    Header,
+    /// 다음 DEFLATE 블록의 헤더를 읽을 차례
+    Block,
+    /// 비압축 블록에서 남은 바이트 수
+    Stored(u32),
+    /// 허프만 부호 블록. 리터럴/길이 부호와 거리 부호입니다.
+    Codes(Box<(Huffman, Huffman)>),
+    /// gzip 멤버 트레일러를 읽을 차례
+    Trailer,
+    Done,
+}
+
+/// 조금씩 푸는 DEFLATE 디코더. `gzip` 이면 멤버 헤더와 트레일러도 읽습니다.
+struct Inflater<R> {
+    bits: BitReader<R>,
+    gzip: bool,
+    state: State,
+    /// 지금 블록이 마지막 블록인지 여부
+    last: bool,
+    /// 푼 데이터. `pos` 앞은 이미 넘겨줬고 거리 참조를 위해 최근 [`WINDOW`] 만큼 남겨 둡니다.
+    out: Vec<u8>,
+    pos: usize,
+    /// 현재 멤버가 `out` 에서 시작하는 위치. 이보다 앞은 거리 참조 대상이 아닙니다.
+    start: usize,
+    /// 현재 멤버에서 지금까지 푼 데이터의 CRC-32 와 길이
+    crc: u32,
+    size: u32,
+}
+
+impl<R: BufRead> Inflater<R> {
+    fn new(inner: R, gzip: bool) -> Inflater<R> {
+        Inflater {
+            bits: BitReader::new(inner),
+            gzip,
+            state: if gzip { State::Header } else { State::Block },
+            last: false,
+            out: Vec::new(),
+            pos: 0,
+            start: 0,
+            crc: 0,
+            size: 0,
+        }
+    }
+
+    /// 끝까지 풀어 한 번에 돌려줍니다.
+    fn read_all(mut self) -> Result<Vec<u8>, InflateError> {
+        let mut all = Vec::new();
+        loop {
+            self.fill()?;
+            if self.pos == self.out.len() {
+                return Ok(all);
+            }
+            all.extend_from_slice(&self.out[self.pos..]);
+            self.pos = self.out.len();
+        }
+    }
+
+    /// 넘겨주지 않은 데이터가 [`CHUNK`] 이상 쌓이거나 스트림이 끝날 때까지 풉니다.
+    fn fill(&mut self) -> Result<(), InflateError> {
+        // 이미 넘겨준 데이터는 거리 참조에 필요한 만큼만 남깁니다.
+        let drop = self.pos.min(self.out.len().saturating_sub(WINDOW));
+        if drop >= WINDOW {
+            self.out.drain(..drop);
+            self.pos -= drop;
+            self.start = self.start.saturating_sub(drop);
+        }
+        let limit = self.pos + CHUNK;
+        while self.out.len() < limit {
+            let before = self.out.len();
+            self.state = match std::mem::replace(&mut self.state, State::Done) {
+                State::Header => {
+                    read_header(&mut self.bits)?;
+                    self.start = self.out.len();
+                    self.crc = 0;
+                    self.size = 0;
+                    State::Block
+                }
+                State::Block => {
+                    self.last = self.bits.read(1)? == 1;
+                    match self.bits.read(2)? {
+                        0 => State::Stored(stored_length(&mut self.bits)?),
+                        1 => State::Codes(Box::new(fixed_tables())),
+                        2 => State::Codes(Box::new(dynamic_tables(&mut self.bits)?)),
+                        _ => return Err(InflateError::InvalidBlockType),
+                    }
+                }
+                State::Stored(len) => {
+                    let room = u32::try_from(limit - before).unwrap_or(u32::MAX);
+                    for _ in 0..len.min(room) {
+                        self.out.push(self.bits.read(8)? as u8);
+                    }
+                    match len.saturating_sub(room) {
+                        0 => self.after_block(),
+                        left => State::Stored(left),
+                    }
+                }
+                State::Codes(tables) => {
+                    let (litlen, distance) = &*tables;
+                    let done = codes(
+                        &mut self.bits,
+                        &mut self.out,
+                        self.start,
+                        litlen,
+                        distance,
+                        limit,
+                    )?;
+                    match done {
+                        true => self.after_block(),
+                        false => State::Codes(tables),
+                    }
+                }
+                State::Trailer => {
+                    self.bits.align();
+                    let crc = self.bits.read(16)? | self.bits.read(16)? << 16;
+                    let size = self.bits.read(16)? | self.bits.read(16)? << 16;
+                    if self.crc != crc {
+                        return Err(InflateError::ChecksumMismatch);
+                    }
+                    // ISIZE 는 원래 길이를 2^32 로 나눈 나머지입니다.
+                    if self.size != size {
+                        return Err(InflateError::SizeMismatch);
+                    }
+                    match self.bits.is_gzip_next() {
+                        true => State::Header,
+                        false => State::Done,
+                    }
+                }
+                State::Done => break,
+            };
+            let decoded = &self.out[before..];
+            self.crc = crc32_update(self.crc, decoded);
+            self.size = self.size.wrapping_add(decoded.len() as u32);
+        }
+        Ok(())
+    }
+
+    fn after_block(&self) -> State {
+        match (self.last, self.gzip) {
+            (false, _) => State::Block,
+            (true, true) => State::Trailer,
+            (true, false) => State::Done,
+        }
+    }
+}
+
+/// gzip 헤더를 읽고 DEFLATE 스트림 앞까지 건너뜁니다.
+fn read_header<R: BufRead>(bits: &mut BitReader<R>) -> Result<(), InflateError> {
+    const FHCRC: u32 = 0x02;
+    const FEXTRA: u32 = 0x04;
+    const FNAME: u32 = 0x08;
+    const FCOMMENT: u32 = 0x10;
+
+    let mut fixed = [0u32; 10];
+    for byte in &mut fixed {
+        *byte = bits.read(8)?;
+    }
+    // 압축 방식은 8(DEFLATE)만 정의되어 있고, 예약된 플래그 비트는 0 이어야 합니다.
+    let [id1, id2, method, flags, ..] = fixed;
+    if [id1, id2] != MAGIC.map(u32::from) || method != 8 || flags & 0xe0 != 0 {
+        return Err(InflateError::InvalidHeader);
+    }
+    if flags & FEXTRA != 0 {
+        let len = bits.read(16)?;
+        for _ in 0..len {
+            bits.read(8)?;
+        }
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            while bits.read(8)? != 0 {}
+        }
+    }
+    if flags & FHCRC != 0 {
+        bits.read(16)?;
+    }
+    Ok(())
+}
+
+/// 비압축 블록의 길이를 읽습니다. 내용은 바이트 경계부터 이어집니다.
+fn stored_length<R: BufRead>(bits: &mut BitReader<R>) -> Result<u32, InflateError> {
+    bits.align();
+    let len = bits.read(16)?;
+    let complement = bits.read(16)?;
+    if len != !complement & 0xffff {
+        return Err(InflateError::StoredLengthMismatch);
+    }
+    Ok(len)
+}
+
+/// 고정 허프만 부호 (블록 종류 1)
+fn fixed_tables() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    let litlen = Huffman::new(&lengths).expect("fixed literal/length code is valid");
+    let distance = Huffman::new(&[5; 30]).expect("fixed distance code is valid");
+    (litlen, distance)
+}
+
+/// 동적 허프만 부호 (블록 종류 2). 부호 길이들도 허프만 부호로 압축되어 있습니다.
+fn dynamic_tables<R: BufRead>(bits: &mut BitReader<R>) -> Result<(Huffman, Huffman), InflateError> {
+    let litlen_count = bits.read(5)? as usize + 257;
+    let distance_count = bits.read(5)? as usize + 1;
+    let code_length_count = bits.read(4)? as usize + 4;
+    if litlen_count > 286 || distance_count > 30 {
+        return Err(InflateError::InvalidCode);
+    }
+
+    let mut code_lengths = [0u8; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[symbol] = bits.read(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths)?;
+
+    let mut lengths = vec![0u8; litlen_count + distance_count];
+    let mut i = 0;
+    while i < lengths.len() {
+        let symbol = code_length_code.decode(bits)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths[..i].last().ok_or(InflateError::InvalidCode)?;
+                (previous, 3 + bits.read(2)? as usize)
+            }
+            17 => (0, 3 + bits.read(3)? as usize),
+            _ => (0, 11 + bits.read(7)? as usize),
+        };
+        let run = lengths
+            .get_mut(i..i + repeat)
+            .ok_or(InflateError::InvalidCode)?;
+        run.fill(value);
+        i += repeat;
+    }
+    // 블록 끝(256) 부호가 없으면 블록을 끝낼 수 없습니다.
+    if lengths[256] == 0 {
+        return Err(InflateError::InvalidCode);
+    }
+    let litlen = Huffman::new(&lengths[..litlen_count])?;
+    let distance = Huffman::new(&lengths[litlen_count..])?;
+    Ok((litlen, distance))
+}
+
+/// 허프만 부호로 압축된 블록을 `out` 이 `limit` 에 닿을 때까지 풉니다.
+/// 블록이 끝났으면 `true` 를 반환합니다. `start` 앞의 데이터는 거리 참조 대상이 아닙니다.
+fn codes<R: BufRead>(
+    bits: &mut BitReader<R>,
+    out: &mut Vec<u8>,
+    start: usize,
+    litlen: &Huffman,
+    distance: &Huffman,
+    limit: usize,
+) -> Result<bool, InflateError> {
+    while out.len() < limit {
+        let symbol = litlen.decode(bits)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(true),
+            _ => {
+                let index = symbol - 257;
+                let base = *LENGTH_BASE.get(index).ok_or(InflateError::InvalidCode)?;
+                let len = usize::from(base) + bits.read(u32::from(LENGTH_EXTRA[index]))? as usize;
+                let index = distance.decode(bits)? as usize;
+                let base = *DISTANCE_BASE.get(index).ok_or(InflateError::InvalidCode)?;
+                let back =
+                    usize::from(base) + bits.read(u32::from(DISTANCE_EXTRA[index]))? as usize;
+                if back > out.len() - start {
+                    return Err(InflateError::InvalidDistance);
+                }
+                // 거리보다 긴 복사는 방금 쓴 바이트를 다시 읽으므로 한 바이트씩 복사합니다.
+                let from = out.len() - back;
+                for k in 0..len {
+                    out.push(out[from + k]);
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// 정규(canonical) 허프만 부호
+struct Huffman {
+    /// 길이별 부호 개수
+    counts: [u16; 16],
+    /// 부호 순서대로 나열한 심볼
+    symbols: Vec<u16>,
+    /// 다음 [`FAST_BITS`] 비트 → `심볼 | 길이 << 16`. 0 이면 더 긴 부호입니다.
+    fast: Vec<u32>,
+}
+
+impl Huffman {
+    /// 심볼별 부호 길이(0 은 사용하지 않는 심볼)로 부호를 만듭니다.
+    /// 부호가 남는(불완전한) 표는 허용하지만 넘치는 표는 거부합니다.
+    fn new(lengths: &[u8]) -> Result<Huffman, InflateError> {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[usize::from(len)] += 1;
+        }
+        counts[0] = 0;
+        let mut left: i32 = 1;
+        for &count in &counts[1..] {
+            left = left * 2 - i32::from(count);
+            if left < 0 {
+                return Err(InflateError::InvalidCode);
+            }
+        }
+
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[usize::from(offsets[usize::from(len)])] = symbol as u16;
+                offsets[usize::from(len)] += 1;
+            }
+        }
+
+        // 같은 길이의 부호는 심볼 순서대로 연속된 값을 가집니다.
+        let mut fast = vec![0u32; 1 << FAST_BITS];
+        let mut code = 0u32;
+        let mut index = 0;
+        for len in 1..=FAST_BITS {
+            for _ in 0..counts[len as usize] {
+                // 부호는 상위 비트부터 저장되므로 비트를 뒤집어 표의 위치로 씁니다.
+                let reversed = code.reverse_bits() >> (32 - len);
+                let entry = u32::from(symbols[index]) | len << 16;
+                for slot in (reversed as usize..fast.len()).step_by(1 << len) {
+                    fast[slot] = entry;
+                }
+                code += 1;
+                index += 1;
+            }
+            code <<= 1;
+        }
+        Ok(Huffman {
+            counts,
+            symbols,
+            fast,
+        })
+    }
+
+    fn decode<R: BufRead>(&self, bits: &mut BitReader<R>) -> Result<u16, InflateError> {
+        let entry = self.fast[bits.peek(FAST_BITS) as usize];
+        if entry != 0 {
+            bits.consume(entry >> 16)?;
+            return Ok(entry as u16);
+        }
+        // 긴 부호: 한 비트씩 읽으며 길이별 첫 부호와 비교합니다.
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for len in 1..16 {
+            code |= bits.read(1)? as i32;
+            let count = i32::from(self.counts[len]);
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(InflateError::InvalidCode)
+    }
+}
+
+/// 하위 비트부터 읽는 비트 스트림
+struct BitReader<R> {
+    inner: R,
+    buffer: u64,
+    count: u32,
+    /// 입력을 읽다 난 I/O 에러. 압축 해제 쪽에서는 입력이 끝난 것처럼 보입니다.
+    error: Option<io::Error>,
+}
+
+impl<R: BufRead> BitReader<R> {
+    fn new(inner: R) -> BitReader<R> {
+        BitReader {
+            inner,
+            buffer: 0,
+            count: 0,
+            error: None,
+        }
+    }
+
+    fn refill(&mut self) {
+        while self.count <= 56 && self.error.is_none() {
+            let bytes = match self.inner.fill_buf() {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.error = Some(e);
+                    return;
+                }
+            };
+            if bytes.is_empty() {
+                return;
+            }
+            let n = bytes.len().min(((64 - self.count) / 8) as usize);
+            for &b in &bytes[..n] {
+                self.buffer |= u64::from(b) << self.count;
+                self.count += 8;
+            }
+            self.inner.consume(n);
+        }
+    }
+
+    /// 다음 `n` 비트. 입력이 모자라면 나머지는 0 으로 채웁니다.
+    fn peek(&mut self, n: u32) -> u32 {
+        if self.count < n {
+            self.refill();
+        }
+        (self.buffer & ((1 << n) - 1)) as u32
+    }
+
+    fn consume(&mut self, n: u32) -> Result<(), InflateError> {
+        if n > self.count {
+            return Err(InflateError::UnexpectedEof);
+        }
+        self.buffer >>= n;
+        self.count -= n;
+        Ok(())
+    }
+
+    fn read(&mut self, n: u32) -> Result<u32, InflateError> {
+        let value = self.peek(n);
+        self.consume(n)?;
+        Ok(value)
+    }
+
+    /// 바이트 경계까지 남은 비트를 버립니다.
+    fn align(&mut self) {
+        let drop = self.count % 8;
+        self.buffer >>= drop;
+        self.count -= drop;
+    }
+
+    /// 바이트 경계에서 다음 두 바이트가 gzip 매직인지 확인합니다.
+    fn is_gzip_next(&mut self) -> bool {
+        let next = self.peek(16);
+        self.count >= 16 && next == u32::from(u16::from_le_bytes(MAGIC))
+    }
+}
+
+/// CRC-32 (IEEE 802.3, gzip 트레일러)
+pub fn crc32(bytes: &[u8]) -> u32 {
+    crc32_update(0, bytes)
+}
+
+/// `crc` 까지 계산한 CRC-32 에 `bytes` 를 이어서 계산합니다.
+fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut k = 0;
+            while k < 8 {
+                crc = if crc & 1 != 0 {
+                    0xedb8_8320 ^ (crc >> 1)
+                } else {
+                    crc >> 1
+                };
+                k += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    };
+    !bytes.iter().fold(!crc, |crc, &b| {
+        TABLE[((crc ^ u32::from(b)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
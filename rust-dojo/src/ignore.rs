@@ -0,0 +1,308 @@
+//! 검색할 파일 고르기
+//!
+//! 디렉토리를 순회할 때 어떤 파일을 건너뛸지 정하는 규칙들입니다.
+//!
+//! - [`Gitignore`]: `.gitignore` / `.ignore` 파일 하나의 규칙
+//! - [`Overrides`]: `--glob` 으로 준 포함(`*.rs`)/제외(`!target/**`) 규칙
+//! - [`FileTypes`]: `--type rust`, `--type-not md` 같은 파일 종류 규칙
+//!
+//! 우선순위는 `--glob` > 무시 파일 > `--type` 순이며, 숨김 파일 처리는 [`crate::walk`] 가 합니다.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::glob::{Glob, GlobError};
+
+/// 규칙 검사 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Match {
+    /// 해당하는 규칙이 없습니다.
+    None,
+    /// 건너뜁니다.
+    Ignore,
+    /// 반드시 포함합니다 (`!` 로 다시 포함하거나 `--glob` 포함 규칙에 맞음).
+    Whitelist,
+}
+
+impl Match {
+    pub fn is_ignore(self) -> bool {
+        self == Match::Ignore
+    }
+
+    pub fn is_none(self) -> bool {
+        self == Match::None
+    }
+}
+
+/// 파일 선택 규칙을 만들 때의 에러
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterError {
+    /// 잘못된 글롭
+    InvalidGlob { glob: String, error: GlobError },
+    /// 알 수 없는 파일 종류
+    UnknownType(String),
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterError::InvalidGlob { glob, error } => write!(f, "invalid glob '{glob}': {error}"),
+            FilterError::UnknownType(name) => write!(f, "unknown file type '{name}'"),
+        }
+    }
+}
+
+impl Error for FilterError {}
+
+// =============================================================================
+// .gitignore / .ignore
+// =============================================================================
+
+#[derive(Debug, Clone)]
+struct Rule {
+    glob: Glob,
+    /// `!pattern`: 앞에서 무시된 경로를 다시 포함합니다.
+    negated: bool,
+    /// `pattern/`: 디렉토리에만 적용됩니다.
+    dir_only: bool,
+}
+
+/// 무시 파일 하나의 규칙. 경로는 파일이 있는 디렉토리 기준으로 해석합니다.
+#[derive(Debug, Clone)]
+pub struct Gitignore {
+    dir: PathBuf,
+    rules: Vec<Rule>,
+}
+
+impl Gitignore {
+    /// `dir` 안의 무시 파일 `name` 을 읽습니다. 파일이 없으면 `None` 입니다.
+    pub fn from_dir(dir: &Path, name: &str) -> io::Result<Option<Gitignore>> {
+        match fs::read(dir.join(name)) {
+            Ok(bytes) => Ok(Some(Gitignore::parse(
+                dir,
+                &String::from_utf8_lossy(&bytes),
+            ))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// `dir` 에 있는 무시 파일의 내용을 파싱합니다. 잘못된 줄은 git 처럼 건너뜁니다.
+    pub fn parse(dir: &Path, text: &str) -> Gitignore {
+        let rules = text.lines().filter_map(parse_rule).collect();
+        Gitignore {
+            dir: dir.to_path_buf(),
+            rules,
+        }
+    }
+
+    /// 이 파일이 적용되는 디렉토리
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// `path` 에 대한 판정. 여러 규칙이 맞으면 마지막 규칙이 이깁니다.
+    /// `path` 가 [`Gitignore::dir`] 아래에 있지 않으면 [`Match::None`] 입니다.
+    pub fn matched(&self, path: &Path, is_dir: bool) -> Match {
+        let Ok(relative) = path.strip_prefix(&self.dir) else {
+            return Match::None;
+        };
+        let relative = slash_path(relative);
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && rule.glob.is_match(&relative))
+            .map_or(Match::None, |rule| match rule.negated {
+                true => Match::Whitelist,
+                false => Match::Ignore,
+            })
+    }
+}
+
+fn parse_rule(line: &str) -> Option<Rule> {
+    if line.starts_with('#') {
+        return None;
+    }
+    // 이스케이프되지 않은 끝 공백은 무시합니다.
+    let mut line = line.trim_end_matches(['\r']);
+    while line.ends_with(' ') && !line.ends_with("\\ ") {
+        line = &line[..line.len() - 1];
+    }
+    let (negated, line) = match line.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (
+            false,
+            line.strip_prefix("\\")
+                .filter(|r| r.starts_with(['!', '#']))
+                .unwrap_or(line),
+        ),
+    };
+    let (dir_only, line) = match line.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    if line.is_empty() {
+        return None;
+    }
+    let glob = compile_path_glob(line).ok()?;
+    Some(Rule {
+        glob,
+        negated,
+        dir_only,
+    })
+}
+
+// =============================================================================
+// --glob
+// =============================================================================
+
+/// `--glob` 규칙. 포함 규칙이 하나라도 있으면 어느 포함 규칙에도 맞지 않는 파일은 건너뜁니다.
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    /// `(글롭, 포함 여부)`
+    globs: Vec<(Glob, bool)>,
+    has_include: bool,
+}
+
+impl Overrides {
+    /// `!` 로 시작하면 제외, 아니면 포함 규칙입니다.
+    /// `/` 가 없는 글롭은 파일 이름에, 있으면 검색 루트 기준 경로에 매칭됩니다.
+    pub fn new<S: AsRef<str>>(globs: &[S]) -> Result<Overrides, FilterError> {
+        let mut overrides = Overrides::default();
+        for glob in globs {
+            let glob = glob.as_ref();
+            let (include, pattern) = match glob.strip_prefix('!') {
+                Some(rest) => (false, rest),
+                None => (true, glob),
+            };
+            let compiled =
+                compile_path_glob(pattern).map_err(|error| FilterError::InvalidGlob {
+                    glob: glob.to_string(),
+                    error,
+                })?;
+            overrides.has_include |= include;
+            overrides.globs.push((compiled, include));
+        }
+        Ok(overrides)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.globs.is_empty()
+    }
+
+    /// 검색 루트 기준 경로 `relative` 에 대한 판정. 마지막으로 맞은 규칙이 이깁니다.
+    pub fn matched(&self, relative: &str, is_dir: bool) -> Match {
+        let found = self
+            .globs
+            .iter()
+            .rev()
+            .find(|(glob, _)| glob.is_match(relative));
+        match found {
+            Some((_, true)) => Match::Whitelist,
+            Some((_, false)) => Match::Ignore,
+            // 포함 규칙은 파일에만 적용하므로 디렉토리는 계속 내려갑니다.
+            None if self.has_include && !is_dir => Match::Ignore,
+            None => Match::None,
+        }
+    }
+}
+
+// =============================================================================
+// --type
+// =============================================================================
+
+/// 내장 파일 종류와 해당 글롭들
+pub const FILE_TYPES: &[(&str, &[&str])] = &[
+    ("c", &["*.c", "*.h"]),
+    ("cpp", &["*.cpp", "*.cc", "*.cxx", "*.hpp", "*.hh", "*.hxx"]),
+    ("css", &["*.css", "*.scss"]),
+    ("go", &["*.go"]),
+    ("html", &["*.html", "*.htm"]),
+    ("java", &["*.java"]),
+    ("js", &["*.js", "*.mjs", "*.cjs", "*.jsx"]),
+    ("json", &["*.json", "*.jsonl"]),
+    ("kotlin", &["*.kt", "*.kts"]),
+    ("log", &["*.log"]),
+    ("markdown", &["*.md", "*.markdown"]),
+    ("md", &["*.md", "*.markdown"]),
+    ("py", &["*.py", "*.pyi"]),
+    ("rust", &["*.rs"]),
+    ("sh", &["*.sh", "*.bash", "*.zsh"]),
+    ("sql", &["*.sql"]),
+    ("toml", &["*.toml", "Cargo.lock"]),
+    ("ts", &["*.ts", "*.tsx", "*.mts", "*.cts"]),
+    ("txt", &["*.txt"]),
+    ("yaml", &["*.yaml", "*.yml"]),
+];
+
+/// 파일 종류 이름으로 글롭 목록을 찾습니다.
+pub fn type_globs(name: &str) -> Option<&'static [&'static str]> {
+    FILE_TYPES
+        .iter()
+        .find(|(type_name, _)| *type_name == name)
+        .map(|(_, globs)| *globs)
+}
+
+/// `--type` / `--type-not` 규칙. 파일 이름에만 적용됩니다.
+#[derive(Debug, Clone, Default)]
+pub struct FileTypes {
+    selected: Vec<Glob>,
+    negated: Vec<Glob>,
+}
+
+impl FileTypes {
+    pub fn new<S: AsRef<str>>(select: &[S], negate: &[S]) -> Result<FileTypes, FilterError> {
+        Ok(FileTypes {
+            selected: compile_types(select)?,
+            negated: compile_types(negate)?,
+        })
+    }
+
+    /// 파일 이름 `name` 에 대한 판정
+    pub fn matched(&self, name: &str) -> Match {
+        if self.negated.iter().any(|glob| glob.is_match(name)) {
+            Match::Ignore
+        } else if self.selected.is_empty() {
+            Match::None
+        } else if self.selected.iter().any(|glob| glob.is_match(name)) {
+            Match::Whitelist
+        } else {
+            Match::Ignore
+        }
+    }
+}
+
+fn compile_types<S: AsRef<str>>(names: &[S]) -> Result<Vec<Glob>, FilterError> {
+    let mut globs = Vec::new();
+    for name in names {
+        let name = name.as_ref();
+        let patterns =
+            type_globs(name).ok_or_else(|| FilterError::UnknownType(name.to_string()))?;
+        for pattern in patterns {
+            globs.push(Glob::new(pattern).expect("built-in type globs are valid"));
+        }
+    }
+    Ok(globs)
+}
+
+/// gitignore 와 같은 규칙으로 경로 글롭을 컴파일합니다.
+/// 앞이나 중간에 `/` 가 있으면 기준 디렉토리에 고정되고, 없으면 어느 깊이의 이름에나 맞습니다.
+pub fn compile_path_glob(pattern: &str) -> Result<Glob, GlobError> {
+    match pattern.strip_prefix('/') {
+        Some(anchored) => Glob::new(anchored),
+        None if pattern.contains('/') => Glob::new(pattern),
+        None => Glob::new(&format!("**/{pattern}")),
+    }
+}
+
+/// 경로를 `/` 로 구분된 문자열로 바꿉니다.
+pub fn slash_path(path: &Path) -> String {
+    let parts: Vec<_> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    parts.join("/")
+}
@@ -0,0 +1,273 @@
+//! JSON Lines 출력 (`--json`)
+//!
+//! 한 줄에 JSON 객체 하나씩, ripgrep 과 같은 모양의 레코드를 출력합니다.
+//!
+//! - `begin`: 파일의 첫 결과 직전
+//! - `match` / `context`: 결과 라인 하나
+//! - `end`: 파일의 마지막 결과 뒤, 그 파일의 통계 포함
+//! - `summary`: 모든 검색이 끝난 뒤 전체 통계
+//!
+//! 경로와 라인은 UTF-8 이면 `{"text": "..."}`, 아니면 원본 바이트를 base64 로 인코딩한
+//! `{"bytes": "..."}` 로 표현하므로 어떤 입력이든 손실 없이 되살릴 수 있습니다.
+//! 라인 텍스트에는 줄 끝 문자(`\n`, `\r\n`)가 포함되지 않습니다.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::searcher::Sink;
+use crate::{LineMatch, StreamSummary};
+
+/// 검색 통계. `end` 레코드에는 파일 하나의, `summary` 레코드에는 전체 합계가 들어갑니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    /// 검색에 걸린 시간
+    pub elapsed: Duration,
+    /// 검색한 입력 수
+    pub searches: u64,
+    /// 결과가 하나 이상 있었던 입력 수
+    pub searches_with_match: u64,
+    /// 읽은 바이트 수
+    pub bytes_searched: u64,
+    /// 결과로 선택된 라인 수
+    pub matched_lines: u64,
+    /// 매치 구간 수
+    pub matches: u64,
+}
+
+impl Stats {
+    /// 입력 하나의 검색 결과로 통계를 만듭니다.
+    pub fn from_summary(summary: &StreamSummary, elapsed: Duration) -> Self {
+        Stats {
+            elapsed,
+            searches: 1,
+            searches_with_match: u64::from(summary.matched_lines > 0),
+            bytes_searched: summary.bytes_read,
+            matched_lines: summary.matched_lines,
+            matches: summary.matches,
+        }
+    }
+
+    /// 다른 통계를 더합니다. `elapsed` 도 더해지므로 전체 경과 시간은 따로 기록합니다.
+    pub fn add(&mut self, other: &Stats) {
+        self.elapsed += other.elapsed;
+        self.searches += other.searches;
+        self.searches_with_match += other.searches_with_match;
+        self.bytes_searched += other.bytes_searched;
+        self.matched_lines += other.matched_lines;
+        self.matches += other.matches;
+    }
+}
+
+/// `begin` 레코드를 씁니다.
+pub fn write_begin(out: &mut dyn Write, path: &[u8]) -> io::Result<()> {
+    let mut record = String::from(r#"{"type":"begin","data":{"path":"#);
+    push_data(&mut record, path);
+    record.push_str("}}");
+    writeln!(out, "{record}")
+}
+
+/// 결과 라인 하나를 `match` 또는 `context` 레코드로 씁니다.
+pub fn write_line(
+    out: &mut dyn Write,
+    path: &[u8],
+    line: &LineMatch,
+    is_match: bool,
+) -> io::Result<()> {
+    let kind = if is_match { "match" } else { "context" };
+    let bytes = line.bytes();
+    let mut record = format!(r#"{{"type":"{kind}","data":{{"path":"#);
+    push_data(&mut record, path);
+    record.push_str(r#","lines":"#);
+    push_data(&mut record, bytes);
+    record.push_str(&format!(
+        r#","line_number":{},"submatches":["#,
+        line.line_number
+    ));
+    for (i, span) in line.matches.iter().enumerate() {
+        if i > 0 {
+            record.push(',');
+        }
+        record.push_str(r#"{"match":"#);
+        push_data(&mut record, &bytes[span.bytes.clone()]);
+        record.push_str(&format!(
+            r#","start":{},"end":{}}}"#,
+            span.bytes.start, span.bytes.end
+        ));
+    }
+    record.push_str("]}}");
+    writeln!(out, "{record}")
+}
+
+/// `end` 레코드를 씁니다.
+pub fn write_end(out: &mut dyn Write, path: &[u8], stats: &Stats) -> io::Result<()> {
+    let mut record = String::from(r#"{"type":"end","data":{"path":"#);
+    push_data(&mut record, path);
+    record.push_str(r#","stats":"#);
+    push_stats(&mut record, stats);
+    record.push_str("}}");
+    writeln!(out, "{record}")
+}
+
+/// 모든 검색이 끝난 뒤 `summary` 레코드를 씁니다.
+/// `elapsed_total` 은 벽시계 기준 전체 시간이고, `stats.elapsed` 는 파일별 시간의 합입니다.
+pub fn write_summary(
+    out: &mut dyn Write,
+    elapsed_total: Duration,
+    stats: &Stats,
+) -> io::Result<()> {
+    let mut record = String::from(r#"{"type":"summary","data":{"elapsed_total":"#);
+    push_duration(&mut record, elapsed_total);
+    record.push_str(r#","stats":"#);
+    push_stats(&mut record, stats);
+    record.push_str("}}");
+    writeln!(out, "{record}")
+}
+
+/// 입력 하나의 결과를 JSON Lines 로 쓰는 [`Sink`].
+/// 결과가 있는 입력만 `begin` ~ `end` 레코드를 씁니다.
+#[derive(Debug)]
+pub struct JsonPrinter<W: Write> {
+    out: W,
+    path: Vec<u8>,
+    search_binary: bool,
+    started: Instant,
+    begun: bool,
+    stats: Option<Stats>,
+}
+
+impl<W: Write> JsonPrinter<W> {
+    /// `path` 는 레코드에 넣을 경로 바이트입니다. 경과 시간은 지금부터 잽니다.
+    pub fn new(out: W, path: Vec<u8>) -> Self {
+        JsonPrinter {
+            out,
+            path,
+            search_binary: false,
+            started: Instant::now(),
+            begun: false,
+            stats: None,
+        }
+    }
+
+    /// 바이너리 입력도 검색합니다 (`--binary`).
+    pub fn search_binary(mut self, search_binary: bool) -> Self {
+        self.search_binary = search_binary;
+        self
+    }
+
+    /// 검색이 끝난 입력의 통계. 건너뛴 입력이면 `None` 입니다.
+    pub fn stats(&self) -> Option<Stats> {
+        self.stats
+    }
+
+    fn write_line(&mut self, line: &LineMatch, is_match: bool) -> io::Result<()> {
+        if !self.begun {
+            write_begin(&mut self.out, &self.path)?;
+            self.begun = true;
+        }
+        write_line(&mut self.out, &self.path, line, is_match)
+    }
+}
+
+impl<W: Write> Sink for JsonPrinter<W> {
+    fn matched(&mut self, line: &LineMatch) -> io::Result<()> {
+        self.write_line(line, true)
+    }
+
+    fn context(&mut self, line: &LineMatch) -> io::Result<()> {
+        self.write_line(line, false)
+    }
+
+    fn binary_detected(&mut self) -> io::Result<bool> {
+        Ok(self.search_binary)
+    }
+
+    fn finish(&mut self, summary: &StreamSummary) -> io::Result<()> {
+        let stats = Stats::from_summary(summary, self.started.elapsed());
+        if self.begun {
+            write_end(&mut self.out, &self.path, &stats)?;
+        }
+        self.stats = Some(stats);
+        Ok(())
+    }
+}
+
+/// 문자열을 JSON 문자열 리터럴(따옴표 포함)로 만듭니다.
+pub fn escape_str(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{08}' => escaped.push_str("\\b"),
+            '\u{0C}' => escaped.push_str("\\f"),
+            c if c < ' ' || c == '\u{7F}' => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// 표준 base64 (RFC 4648, 패딩 포함)
+pub fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// UTF-8 이면 `{"text":...}`, 아니면 `{"bytes":...}` 를 붙입니다.
+fn push_data(record: &mut String, bytes: &[u8]) {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => {
+            record.push_str(r#"{"text":"#);
+            record.push_str(&escape_str(text));
+        }
+        Err(_) => {
+            record.push_str(r#"{"bytes":""#);
+            record.push_str(&base64(bytes));
+            record.push('"');
+        }
+    }
+    record.push('}');
+}
+
+fn push_duration(record: &mut String, elapsed: Duration) {
+    record.push_str(&format!(
+        r#"{{"secs":{},"nanos":{},"human":"{:.6}s"}}"#,
+        elapsed.as_secs(),
+        elapsed.subsec_nanos(),
+        elapsed.as_secs_f64()
+    ));
+}
+
+fn push_stats(record: &mut String, stats: &Stats) {
+    record.push_str(r#"{"elapsed":"#);
+    push_duration(record, stats.elapsed);
+    record.push_str(&format!(
+        r#","searches":{},"searches_with_match":{},"bytes_searched":{},"matched_lines":{},"matches":{}}}"#,
+        stats.searches,
+        stats.searches_with_match,
+        stats.bytes_searched,
+        stats.matched_lines,
+        stats.matches
+    ));
+}
@@ -1,5 +1,60 @@
 use std::error::Error;
+use std::fmt;
 use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub mod aho_corasick;
+pub mod args;
+pub mod bm25;
+pub mod color;
+pub mod cp949;
+pub mod encoding;
+pub mod fields;
+pub mod follow;
+pub mod fuzzy;
+pub mod glob;
+pub mod gzip;
+pub mod ignore;
+pub mod json;
+pub mod literal;
+pub mod matcher;
+pub mod multiline;
+pub mod parallel;
+pub mod pattern;
+pub mod replace;
+pub mod searcher;
+pub mod stream;
+pub mod tar;
+pub mod trigram;
+pub mod unicode;
+pub mod walk;
+
+pub use args::ConfigError;
+pub use bm25::{Bm25Params, RankOptions, RankUnit};
+pub use color::{ColorChoice, Palette};
+pub use encoding::Encoding;
+pub use fields::FieldQuery;
+pub use follow::Follower;
+pub use fuzzy::FuzzyPattern;
+pub use glob::{Glob, GlobError};
+pub use ignore::{FileTypes, FilterError, Overrides};
+pub use matcher::{ExprError, ExprTerm, Matcher, PatternSet};
+pub use pattern::{Pattern, PatternError, PatternOptions};
+pub use replace::{Template, TemplateError};
+pub use searcher::{Collector, Printer, Searcher, Sink};
+pub use stream::{StreamItem, StreamOptions, StreamSummary};
+pub use trigram::TrigramIndex;
+pub use walk::WalkOptions;
+
+/// 표준 입력을 뜻하는 경로 인자
+pub const STDIN_PATH: &str = "-";
+
+/// 떨어진 컨텍스트 블록 사이에 출력되는 구분선
+pub const GROUP_SEPARATOR: &str = "--";
 
 // =============================================================================
 // 임무 1: read_file 함수를 구현하세요
@@ -8,14 +63,15 @@ use std::fs;
 // &str 은 문자열 슬라이스 임 => 데이터를 가지고 있는게 아니라 어딘가에 있는걸 참조하는 것
 // =============================================================================
 pub fn read_file(_filename: &str) -> String {
-    fs::read_to_string(_filename).unwrap_or_else(|_| String::from("찾을 수 없습니다."))
+    read_file_safe(_filename).unwrap_or_else(|_| String::from("찾을 수 없습니다."))
 }
 
 // =============================================================================
 // 임무 2: read_file_safe, find_first_match 함수를 구현하세요
 // =============================================================================
+/// UTF-8 이 아닌 파일(UTF-16, EUC-KR/CP949)은 인코딩을 감지해 UTF-8 로 바꿔 읽습니다.
 pub fn read_file_safe(_filename: &str) -> Result<String, std::io::Error> {
-    fs::read_to_string(_filename)
+    fs::read(_filename).map(|bytes| encoding::decode_auto(&bytes))
 }
 
 pub fn find_first_match<'a>(_contents: &'a str, _query: &str) -> Option<&'a str> {
@@ -25,36 +81,181 @@ pub fn find_first_match<'a>(_contents: &'a str, _query: &str) -> Option<&'a str>
 // =============================================================================
 // 임무 3: Config 구조체를 구현하세요
 // =============================================================================
+#[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     pub query: String,
-    pub filename: String,
+    /// `-e`, `-f`, `--and`, `--or`, `--not` 로 준 패턴 식.
+    /// 비어 있지 않으면 `query` 대신 이 식으로 검색하고, 위치 인자는 모두 경로가 됩니다.
+    pub expr: Vec<ExprTerm>,
+    pub paths: Vec<String>,
     pub case_sensitive: bool,
+    pub regex: bool,
+    pub line_number: bool,
+    pub column: bool,
+    pub invert_match: bool,
+    pub count: bool,
+    pub files_with_matches: bool,
+    /// 매칭되는 라인이 없는 파일 경로만 출력합니다 (`-L`).
+    pub files_without_match: bool,
+    pub word_regexp: bool,
+    pub before_context: usize,
+    pub after_context: usize,
+    /// 검색 스레드 수. 0 이면 CPU 코어 수만큼 사용합니다.
+    pub threads: usize,
+    /// 결과를 JSON Lines 레코드로 출력합니다 (`--json`).
+    pub json: bool,
+    /// 매치를 치환할 템플릿 (`--replace`). 지정하면 검색 대신 치환 모드로 동작합니다.
+    pub replace: Option<String>,
+    /// 치환 결과를 diff 로 보여주는 대신 파일에 씁니다 (`--write`).
+    pub write: bool,
+    /// 파일 선택 글롭 (`--glob`). `!` 로 시작하면 제외합니다.
+    pub globs: Vec<String>,
+    /// 검색할 파일 종류 (`--type`)
+    pub types: Vec<String>,
+    /// 제외할 파일 종류 (`--type-not`)
+    pub types_not: Vec<String>,
+    /// 숨김 파일도 검색합니다 (`--hidden`).
+    pub hidden: bool,
+    /// `.gitignore` / `.ignore` 를 따르지 않습니다 (`--no-ignore`).
+    pub no_ignore: bool,
+    /// 색상 출력 여부 (`--color`)
+    pub color: ColorChoice,
+    /// 편집 거리 K 이내의 근사 검색 (`--fuzzy K`). 결과는 거리 순으로 정렬됩니다.
+    pub fuzzy: Option<usize>,
+    /// `minigrep rank` 모드. `query` 와 BM25 점수가 높은 파일이나 문단을 순서대로 출력합니다.
+    pub rank: Option<RankOptions>,
+    /// `minigrep index build` 모드. `paths` 의 디렉토리마다 트라이그램 색인을 만들거나 갱신합니다.
+    pub build_index: bool,
+    /// 디렉토리의 트라이그램 색인으로 검색할 파일을 먼저 거릅니다 (`--index`).
+    pub index: bool,
+    /// 입력 인코딩 (`--encoding`). `None` 이면 입력마다 BOM 과 내용으로 감지합니다.
+    pub encoding: Option<Encoding>,
+    /// 바이너리 파일도 검색하고 라인 대신 "binary file matches" 를 출력합니다 (`--binary`).
+    pub binary: bool,
+    /// `tail -F` 처럼 `paths` 의 파일에 덧붙는 라인을 계속 검색합니다 (`--follow`).
+    pub follow: bool,
+    /// 패턴이 줄바꿈을 넘어 여러 줄에 매칭될 수 있습니다 (`-U`).
+    pub multiline: bool,
+    /// 레코드의 필드 하나에만 패턴을 적용합니다 (`--jsonl-field`, `--csv-column`).
+    /// 이때 `query` 는 필드 값에 적용할 패턴입니다.
+    pub field: Option<FieldQuery>,
+    /// 검색이 끝난 뒤 전체 통계를 출력합니다 (`--stats`).
+    pub stats: bool,
 }
 
-impl Config {
-    pub fn build(_args: &[String]) -> Result<Config, &'static str> {
-        if _args.len() < 3 {
-            return Err("Not enough arguments");
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            query: String::new(),
+            expr: Vec::new(),
+            paths: Vec::new(),
+            case_sensitive: true,
+            regex: false,
+            line_number: false,
+            column: false,
+            invert_match: false,
+            count: false,
+            files_with_matches: false,
+            files_without_match: false,
+            word_regexp: false,
+            before_context: 0,
+            after_context: 0,
+            threads: 0,
+            json: false,
+            replace: None,
+            write: false,
+            globs: Vec::new(),
+            types: Vec::new(),
+            types_not: Vec::new(),
+            hidden: false,
+            no_ignore: false,
+            color: ColorChoice::Auto,
+            fuzzy: None,
+            rank: None,
+            build_index: false,
+            index: false,
+            encoding: None,
+            binary: false,
+            follow: false,
+            multiline: false,
+            field: None,
+            stats: false,
         }
+    }
+}
 
-        let case_sensitive = match _args.get(3) {
-            Some(args) => args == "true",
-            None => true,
-        };
-
-        Ok(Config {
-            query: _args[1].clone(),
-            filename: _args[2].clone(),
-            case_sensitive,
-        })
+impl Config {
+    /// GNU 스타일 옵션을 파싱합니다. 자세한 문법은 [`args`] 모듈을 참고하세요.
+    pub fn build(_args: &[String]) -> Result<Config, ConfigError> {
+        args::parse(_args)
     }
 
     pub fn display_info(&self) -> String {
         format!(
-            "query: {}, filename: {}, case_sensitive: {}",
-            self.query, self.filename, self.case_sensitive
+            "query: {}, paths: {}, case_sensitive: {}, regex: {}",
+            self.query,
+            self.paths.join(" "),
+            self.case_sensitive,
+            self.regex
         )
     }
+
+    /// 디렉토리를 순회할 때 적용할 파일 선택 규칙을 만듭니다.
+    pub fn walk_options(&self) -> Result<WalkOptions, FilterError> {
+        Ok(WalkOptions {
+            hidden: self.hidden,
+            no_ignore: self.no_ignore,
+            overrides: Overrides::new(&self.globs)?,
+            types: FileTypes::new(&self.types, &self.types_not)?,
+        })
+    }
+
+    /// 설정에 맞는 검색 패턴을 컴파일합니다.
+    pub fn pattern(&self) -> Result<Pattern, PatternError> {
+        Pattern::with_options(&self.query, &self.pattern_options())
+    }
+
+    /// 패턴 식까지 포함한 검색 조건을 만듭니다. `-f` 로 준 파일은 여기서 읽습니다.
+    pub fn matcher(&self) -> Result<PatternSet, Box<dyn Error>> {
+        match self.expr.is_empty() {
+            true => Ok(PatternSet::single(self.pattern()?)),
+            false => PatternSet::build(&self.expr, &self.pattern_options()),
+        }
+    }
+
+    /// 설정대로 검색하는 [`Searcher`] 를 만듭니다.
+    pub fn searcher(&self) -> Result<Searcher, Box<dyn Error>> {
+        Ok(self.searcher_with(self.matcher()?))
+    }
+
+    fn searcher_with(&self, matcher: PatternSet) -> Searcher {
+        let options = StreamOptions {
+            invert: self.invert_match,
+            before: self.before_context,
+            after: self.after_context,
+            // 매치 여부만 필요하면 첫 매치에서 읽기를 멈춥니다. `--json` 은 모든 라인을 출력합니다.
+            max_count: (self.lists_files() && !self.json).then_some(1),
+        };
+        Searcher::new(matcher)
+            .with_options(options)
+            .with_multiline(self.multiline)
+            .with_encoding(self.encoding)
+            .with_field(self.field.clone())
+    }
+
+    /// 라인 대신 파일 경로만 출력하는지 여부 (`-l`, `-L`)
+    fn lists_files(&self) -> bool {
+        self.files_with_matches || self.files_without_match
+    }
+
+    fn pattern_options(&self) -> PatternOptions {
+        PatternOptions {
+            regex: self.regex,
+            case_insensitive: !self.case_sensitive,
+            word: self.word_regexp,
+            multiline: self.multiline,
+        }
+    }
 }
 
 // =============================================================================
@@ -62,70 +263,1504 @@ impl Config {
 // =============================================================================
 pub trait SearchResult {
     fn format(&self) -> String;
+
+    /// 출력 옵션에 맞춰 포맷합니다. 기본 구현은 [`SearchResult::format`] 과 같습니다.
+    fn format_styled(&self, _style: &OutputStyle) -> String {
+        self.format()
+    }
+
+    /// 출력 옵션과 색상을 함께 적용합니다. 기본 구현은 색 없이 [`SearchResult::format_styled`] 와 같습니다.
+    fn format_colored(&self, style: &OutputStyle, _palette: &Palette) -> String {
+        self.format_styled(style)
+    }
+
+    /// 결과를 정렬할 때의 순위. 작을수록 앞에 오며, 기본값은 모두 0 (원래 순서 유지)입니다.
+    fn rank(&self) -> usize {
+        0
+    }
+
+    /// 앞 결과와 이 결과 사이에 넣을 구분선. `ContextBlock` 은 `--` 를 사용합니다.
+    fn group_separator(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// `run` 이 결과를 출력할 때 사용하는 옵션
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OutputStyle {
+    /// 파일 경로를 앞에 붙입니다.
+    pub with_path: bool,
+    /// 라인 번호를 앞에 붙입니다.
+    pub line_number: bool,
+    /// 첫 매치의 컬럼(1부터 시작, 문자 단위)을 붙입니다.
+    pub column: bool,
+}
+
+/// 한 라인 안에서 매칭된 구간
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchSpan {
+    /// 라인 시작 기준 바이트 범위
+    pub bytes: Range<usize>,
+    /// 라인 시작 기준 문자(char) 범위
+    pub chars: Range<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct LineMatch {
     pub line_number: usize,
+    /// 마지막 줄 번호. `-U` 로 찾은 여러 줄 매치가 아니면 `line_number` 와 같습니다.
+    pub end_line_number: usize,
+    /// 라인 내용. 여러 줄 매치면 줄바꿈(`\n`)으로 이어진 여러 줄입니다.
     pub content: String,
+    pub path: Option<String>,
+    pub matches: Vec<MatchSpan>,
+    /// 원본 라인이 올바른 UTF-8 이 아닐 때의 원본 바이트.
+    /// 이때 `content` 는 손실 변환된 문자열이고, `matches` 의 바이트 범위는 이 원본 기준입니다.
+    pub raw_bytes: Option<Vec<u8>>,
 }
 
 impl LineMatch {
     pub fn new(_line_number: usize, _content: &str) -> Self {
         LineMatch {
             line_number: _line_number,
+            end_line_number: _line_number + _content.matches('\n').count(),
             content: _content.to_owned(),
+            path: None,
+            matches: Vec::new(),
+            raw_bytes: None,
         }
     }
+
+    /// `pattern` 이 라인 안에서 매칭되는 모든 구간을 함께 기록합니다.
+    /// `line_number` 는 원본 파일 기준 1부터 시작하는 번호입니다.
+    pub fn with_matches(line_number: usize, content: &str, pattern: &Pattern) -> Self {
+        LineMatch::from_bytes(line_number, content.as_bytes(), Some(pattern))
+    }
+
+    /// 원본 바이트에서 라인을 만듭니다. UTF-8 이 아니어도 됩니다.
+    /// `pattern` 이 주어지면 매치 구간을 원본 바이트 기준으로 기록합니다.
+    pub fn from_bytes(line_number: usize, bytes: &[u8], pattern: Option<&Pattern>) -> Self {
+        let spans = pattern
+            .into_iter()
+            .flat_map(|pattern| pattern.find_iter(bytes));
+        LineMatch::with_spans(line_number, bytes, spans)
+    }
+
+    /// 원본 바이트와 이미 찾아 둔 매치 구간들로 라인을 만듭니다.
+    /// 구간은 시작 위치 순서여야 하며 빈 구간은 건너뜁니다.
+    pub fn with_spans<I>(line_number: usize, bytes: &[u8], spans: I) -> Self
+    where
+        I: IntoIterator<Item = Range<usize>>,
+    {
+        let content = String::from_utf8_lossy(bytes);
+        let mut line_match = LineMatch::new(line_number, &content);
+        if std::str::from_utf8(bytes).is_err() {
+            line_match.raw_bytes = Some(bytes.to_vec());
+        }
+
+        let mut chars_seen = 0;
+        let mut bytes_seen = 0;
+        for found in spans {
+            if found.is_empty() {
+                continue;
+            }
+            // 앞쪽 구간부터 순서대로 오므로 문자 수는 누적해서 셉니다.
+            chars_seen += count_chars(&bytes[bytes_seen..found.start]);
+            let char_len = count_chars(&bytes[found.clone()]);
+            line_match.matches.push(MatchSpan {
+                bytes: found.clone(),
+                chars: chars_seen..chars_seen + char_len,
+            });
+            chars_seen += char_len;
+            bytes_seen = found.end;
+        }
+        line_match
+    }
+
+    /// 원본 라인 바이트
+    pub fn bytes(&self) -> &[u8] {
+        self.raw_bytes.as_deref().unwrap_or(self.content.as_bytes())
+    }
+
+    /// 첫 매치의 1부터 시작하는 문자 컬럼
+    pub fn column(&self) -> Option<usize> {
+        self.matches.first().map(|span| span.chars.start + 1)
+    }
+
+    /// 결과 앞에 붙일 파일 경로를 지정합니다.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_owned());
+        self
+    }
+}
+
+/// 손실 변환 기준의 문자 수 (잘못된 바이트 시퀀스는 U+FFFD 한 글자)
+fn count_chars(bytes: &[u8]) -> usize {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.chars().count(),
+        Err(_) => String::from_utf8_lossy(bytes).chars().count(),
+    }
 }
 
 impl SearchResult for LineMatch {
     fn format(&self) -> String {
-        format!("[Line{}], {}", self.line_number, self.content)
+        let lines = match self.end_line_number > self.line_number {
+            true => format!("{}-{}", self.line_number, self.end_line_number),
+            false => self.line_number.to_string(),
+        };
+        match (&self.path, self.column()) {
+            (Some(path), Some(column)) => {
+                format!("{}:{}:{}:{}", path, lines, column, self.content)
+            }
+            (Some(path), None) => format!("{}:{}:{}", path, lines, self.content),
+            (None, _) => format!("[Line{}], {}", lines, self.content),
+        }
+    }
+
+    fn format_styled(&self, style: &OutputStyle) -> String {
+        self.format_colored(style, &Palette::PLAIN)
+    }
+
+    fn format_colored(&self, style: &OutputStyle, palette: &Palette) -> String {
+        let mut out = String::new();
+        self.push_prefix(&mut out, style, palette, ':', self.line_number);
+        if let (true, Some(column)) = (style.column, self.column()) {
+            color::paint(&mut out, palette.column, &column.to_string());
+            color::paint(&mut out, palette.separator, ":");
+        }
+        if self.end_line_number == self.line_number {
+            self.push_highlighted(&mut out, palette.matched);
+            return out;
+        }
+        // 여러 줄 매치는 줄마다 자기 라인 번호를 붙입니다.
+        let mut body = String::new();
+        self.push_highlighted(&mut body, palette.matched);
+        for (i, line) in body.split('\n').enumerate() {
+            if i > 0 {
+                out.push('\n');
+                self.push_prefix(&mut out, style, palette, ':', self.line_number + i);
+            }
+            out.push_str(line);
+        }
+        out
+    }
+}
+
+impl LineMatch {
+    /// 컨텍스트 라인으로 포맷합니다. grep 처럼 `:` 대신 `-` 로 구분합니다.
+    fn format_context(&self, style: &OutputStyle, palette: &Palette) -> String {
+        let mut out = String::new();
+        self.push_prefix(&mut out, style, palette, '-', self.line_number);
+        out.push_str(&self.content);
+        out
+    }
+
+    /// 경로와 라인 번호를 `separator` 로 구분해 붙입니다 (매치는 `:`, 컨텍스트는 `-`).
+    fn push_prefix(
+        &self,
+        out: &mut String,
+        style: &OutputStyle,
+        palette: &Palette,
+        separator: char,
+        line_number: usize,
+    ) {
+        let separator = separator.to_string();
+        if let (true, Some(path)) = (style.with_path, &self.path) {
+            color::paint(out, palette.path, path);
+            color::paint(out, palette.separator, &separator);
+        }
+        if style.line_number {
+            color::paint(out, palette.line_number, &line_number.to_string());
+            color::paint(out, palette.separator, &separator);
+        }
+    }
+
+    /// 매치 구간에 색을 입혀 내용을 붙입니다.
+    /// `content` 는 손실 변환됐을 수 있으므로 바이트가 아닌 문자 범위를 기준으로 합니다.
+    fn push_highlighted(&self, out: &mut String, code: &str) {
+        if code.is_empty() || self.matches.is_empty() {
+            out.push_str(&self.content);
+            return;
+        }
+        let offsets: Vec<usize> = self
+            .content
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(self.content.len()))
+            .collect();
+        let byte_at = |chars: usize| offsets[chars.min(offsets.len() - 1)];
+        let mut copied = 0;
+        for span in &self.matches {
+            let (start, end) = (byte_at(span.chars.start), byte_at(span.chars.end));
+            out.push_str(&self.content[copied..start]);
+            // 여러 줄 매치는 줄바꿈 앞에서 색을 닫아 다음 줄의 접두사에 색이 번지지 않게 합니다.
+            for (i, piece) in self.content[start..end].split('\n').enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                if !piece.is_empty() {
+                    color::paint(out, code, piece);
+                }
+            }
+            copied = end;
+        }
+        out.push_str(&self.content[copied..]);
+    }
+}
+
+/// 컨텍스트 블록 안의 한 라인
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockLine {
+    /// 패턴에 매칭된 라인
+    Match(LineMatch),
+    /// `-A/-B/-C` 로 함께 출력되는 주변 라인
+    Context(LineMatch),
+}
+
+impl BlockLine {
+    pub fn line(&self) -> &LineMatch {
+        match self {
+            BlockLine::Match(line) | BlockLine::Context(line) => line,
+        }
+    }
+
+    pub fn is_match(&self) -> bool {
+        matches!(self, BlockLine::Match(_))
+    }
+}
+
+/// 매치와 그 주변 컨텍스트를 묶은 연속된 라인 그룹
+///
+/// 겹치거나 맞닿은 컨텍스트는 하나의 블록으로 합쳐지고,
+/// 서로 떨어진 블록 사이에는 `--` 구분선이 출력됩니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextBlock {
+    pub path: Option<String>,
+    pub lines: Vec<BlockLine>,
+}
+
+impl ContextBlock {
+    pub fn new() -> Self {
+        ContextBlock {
+            path: None,
+            lines: Vec::new(),
+        }
+    }
+
+    /// 결과 앞에 붙일 파일 경로를 지정합니다.
+    pub fn with_path(mut self, path: &str) -> Self {
+        for line in &mut self.lines {
+            let (BlockLine::Match(line) | BlockLine::Context(line)) = line;
+            line.path = Some(path.to_owned());
+        }
+        self.path = Some(path.to_owned());
+        self
+    }
+
+    pub fn first_line_number(&self) -> Option<usize> {
+        self.lines.first().map(|line| line.line().line_number)
+    }
+
+    pub fn last_line_number(&self) -> Option<usize> {
+        self.lines.last().map(|line| line.line().line_number)
+    }
+}
+
+impl Default for ContextBlock {
+    fn default() -> Self {
+        ContextBlock::new()
+    }
+}
+
+impl SearchResult for ContextBlock {
+    fn format(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| match line {
+                BlockLine::Match(m) => m.format(),
+                // grep 처럼 컨텍스트 라인은 `:` 대신 `-` 로 구분합니다.
+                BlockLine::Context(c) => match &c.path {
+                    Some(path) => format!("{}-{}-{}", path, c.line_number, c.content),
+                    None => format!("[Line{}]- {}", c.line_number, c.content),
+                },
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn format_styled(&self, style: &OutputStyle) -> String {
+        self.format_colored(style, &Palette::PLAIN)
+    }
+
+    fn format_colored(&self, style: &OutputStyle, palette: &Palette) -> String {
+        self.lines
+            .iter()
+            .map(|line| match line {
+                BlockLine::Match(m) => m.format_colored(style, palette),
+                BlockLine::Context(c) => c.format_context(style, palette),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn group_separator(&self) -> Option<&str> {
+        Some(GROUP_SEPARATOR)
+    }
+}
+
+/// 근사 검색(`--fuzzy`)으로 찾은 라인
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub line: LineMatch,
+    /// 질의와 가장 가까운 구간 사이의 편집 거리
+    pub distance: usize,
+}
+
+impl SearchResult for FuzzyMatch {
+    fn format(&self) -> String {
+        self.line.format()
+    }
+
+    fn format_styled(&self, style: &OutputStyle) -> String {
+        self.line.format_styled(style)
+    }
+
+    fn format_colored(&self, style: &OutputStyle, palette: &Palette) -> String {
+        self.line.format_colored(style, palette)
+    }
+
+    fn rank(&self) -> usize {
+        self.distance
+    }
+}
+
+/// `minigrep rank` 결과 하나. 파일 전체이거나 문단 하나입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedPassage {
+    pub path: String,
+    /// BM25 점수
+    pub score: f64,
+    /// 문단 단위일 때 문단의 첫 줄과 마지막 줄 번호
+    pub lines: Option<std::ops::RangeInclusive<usize>>,
+    /// 문단 단위일 때 문단의 원문. 파일 단위면 비어 있습니다.
+    pub text: String,
+    /// `text` 안에서 질의 단어와 같은 토큰의 바이트 범위
+    pub matches: Vec<Range<usize>>,
+}
+
+impl SearchResult for RankedPassage {
+    fn format(&self) -> String {
+        self.format_colored(&OutputStyle::default(), &Palette::PLAIN)
+    }
+
+    /// 파일 단위는 `path:score`, 문단 단위는 `path:first-last:score` 다음 줄부터 문단 원문입니다.
+    fn format_colored(&self, _style: &OutputStyle, palette: &Palette) -> String {
+        let mut out = String::new();
+        color::paint(&mut out, palette.path, &self.path);
+        color::paint(&mut out, palette.separator, ":");
+        if let Some(lines) = &self.lines {
+            let lines = format!("{}-{}", lines.start(), lines.end());
+            color::paint(&mut out, palette.line_number, &lines);
+            color::paint(&mut out, palette.separator, ":");
+        }
+        out.push_str(&format!("{:.4}", self.score));
+        if self.lines.is_none() {
+            return out;
+        }
+        out.push('\n');
+        let mut last = 0;
+        for span in &self.matches {
+            out.push_str(&self.text[last..span.start]);
+            color::paint(&mut out, palette.matched, &self.text[span.clone()]);
+            last = span.end;
+        }
+        out.push_str(&self.text[last..]);
+        out
+    }
+
+    fn group_separator(&self) -> Option<&str> {
+        self.lines.as_ref().map(|_| GROUP_SEPARATOR)
     }
 }
 
+/// 말뭉치 `(경로, 내용)` 을 색인하고 BM25 점수 순으로 상위 결과를 반환합니다.
+/// IDF 와 평균 길이는 모든 파일(문단 단위면 모든 문단)을 합친 말뭉치 기준입니다.
+pub fn rank_corpus(
+    query: &str,
+    corpus: &[(String, String)],
+    options: &RankOptions,
+) -> Vec<RankedPassage> {
+    // 문서 번호 -> (말뭉치 번호, 문단)
+    let mut units = Vec::new();
+    let mut index = bm25::Index::new();
+    for (i, (_, contents)) in corpus.iter().enumerate() {
+        match options.unit {
+            RankUnit::File => {
+                index.add(contents);
+                units.push((i, None));
+            }
+            RankUnit::Paragraph => {
+                for paragraph in bm25::paragraphs(contents) {
+                    index.add(&contents[paragraph.range.clone()]);
+                    units.push((i, Some(paragraph)));
+                }
+            }
+        }
+    }
+
+    let terms = bm25::query_terms(query);
+    let mut ranked = index.search(query, &options.params);
+    if options.top > 0 {
+        ranked.truncate(options.top);
+    }
+    ranked
+        .into_iter()
+        .map(|scored| {
+            let (i, paragraph) = &units[scored.doc];
+            let (path, contents) = &corpus[*i];
+            let text = match paragraph {
+                Some(paragraph) => contents[paragraph.range.clone()].to_string(),
+                None => String::new(),
+            };
+            RankedPassage {
+                path: path.clone(),
+                score: scored.score,
+                lines: paragraph.as_ref().map(|paragraph| paragraph.lines.clone()),
+                matches: bm25::term_spans(&text, &terms),
+                text,
+            }
+        })
+        .collect()
+}
+
+/// [`SearchResult::rank`] 순서로 정렬합니다. 순위가 같으면 원래 순서를 유지합니다.
+pub fn rank_results<T: SearchResult>(results: &mut [T]) {
+    results.sort_by_key(|result| result.rank());
+}
+
 pub fn format_results<T: SearchResult>(_results: &[T]) -> String {
-    _results
-        .iter()
-        .map(|result| result.format())
-        .collect::<Vec<String>>()
-        .join("\n")
+    join_results(_results, &Palette::PLAIN, |result| result.format())
+}
+
+/// [`format_results`] 와 같지만 출력 옵션을 적용합니다.
+pub fn format_results_styled<T: SearchResult>(results: &[T], style: &OutputStyle) -> String {
+    join_results(results, &Palette::PLAIN, |result| {
+        result.format_styled(style)
+    })
+}
+
+/// [`format_results_styled`] 와 같지만 매치, 경로, 라인 번호에 색을 입힙니다.
+pub fn format_results_colored<T: SearchResult>(
+    results: &[T],
+    style: &OutputStyle,
+    palette: &Palette,
+) -> String {
+    join_results(results, palette, |result| {
+        result.format_colored(style, palette)
+    })
+}
+
+fn join_results<T: SearchResult>(
+    results: &[T],
+    palette: &Palette,
+    format: impl Fn(&T) -> String,
+) -> String {
+    let mut lines = Vec::with_capacity(results.len());
+    for (i, result) in results.iter().enumerate() {
+        if let (true, Some(separator)) = (i > 0, result.group_separator()) {
+            lines.push(color::painted(palette.separator, separator));
+        }
+        lines.push(format(result));
+    }
+    lines.join("\n")
 }
 
 // =============================================================================
 // 임무 5: search, search_case_insensitive 함수를 구현하세요
 // 생명주기 어노테이션에 주목하세요!
 // =============================================================================
+/// 라인을 하나씩 나누는 대신 전체 버퍼에서 질의를 찾고, 찾은 위치 주변에서만 라인 경계를 찾습니다.
+/// 결과는 `lines()` + `contains` 와 같습니다.
 pub fn search<'a>(_query: &str, _contents: &'a str) -> Vec<&'a str> {
-    _contents.lines().filter(|v| v.contains(_query)).collect()
+    // 줄바꿈 문자가 든 질의는 라인 경계를 넘어 매칭될 수 있으니 라인 단위로 확인합니다.
+    if _query.contains(['\n', '\r']) {
+        return _contents.lines().filter(|v| v.contains(_query)).collect();
+    }
+    let finder = literal::Finder::new(_query.as_bytes());
+    literal::matching_lines(&finder, _contents.as_bytes())
+        .map(|line| match &_contents[line.start..] {
+            // `lines()` 처럼 `\r\n` 의 `\r` 만 제거합니다.
+            rest if rest[line.len()..].starts_with('\n') => {
+                let text = &rest[..line.len()];
+                text.strip_suffix('\r').unwrap_or(text)
+            }
+            rest => &rest[..line.len()],
+        })
+        .collect()
 }
 
+/// 질의는 한 번만 접어 두고, 라인은 할당 없이 글자 단위로 접으며 비교합니다.
+/// 규칙은 [`unicode::simple_fold`] 를 참고하세요.
 pub fn search_case_insensitive<'a>(_query: &str, _contents: &'a str) -> Vec<&'a str> {
+    let query: Vec<char> = _query.chars().map(unicode::simple_fold).collect();
     _contents
         .lines()
-        .filter(|v| v.to_lowercase().contains(&_query.to_lowercase()))
+        .filter(|v| unicode::contains_folded(v, &query))
         .collect()
 }
 
+/// 컴파일된 [`Pattern`] 으로 매칭되는 라인들을 찾습니다.
+pub fn search_pattern<'a>(pattern: &Pattern, contents: &'a str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| pattern.is_match(line))
+        .collect()
+}
+
+/// 매칭된 라인을 원본 라인 번호(1부터)와 매치 구간과 함께 반환합니다.
+/// `invert` 가 true 면 매칭되지 않는 라인을 반환합니다 (`-v`).
+pub fn search_lines(pattern: &Pattern, contents: &str, invert: bool) -> Vec<LineMatch> {
+    let options = StreamOptions {
+        invert,
+        ..StreamOptions::default()
+    };
+    let mut results = Vec::new();
+    stream::search_reader(pattern, contents.as_bytes(), &options, |item| {
+        if let StreamItem::Line(line) = item {
+            results.push(line);
+        }
+        Ok(())
+    })
+    .expect("searching an in-memory string cannot fail");
+    results
+}
+
+/// 질의와 편집 거리가 가까운 라인을 거리 순으로 반환합니다.
+/// 매치 구간에는 가장 가까운 부분 문자열 하나가 기록됩니다.
+pub fn search_fuzzy(pattern: &FuzzyPattern, contents: &str) -> Vec<FuzzyMatch> {
+    let mut results: Vec<FuzzyMatch> = contents
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| fuzzy_line(pattern, i + 1, line.as_bytes()))
+        .collect();
+    rank_results(&mut results);
+    results
+}
+
+/// 라인 하나를 근사 검색합니다. 올바른 UTF-8 이 아닌 라인은 손실 변환한 뒤 비교하고 구간은 기록하지 않습니다.
+fn fuzzy_line(pattern: &FuzzyPattern, line_number: usize, bytes: &[u8]) -> Option<FuzzyMatch> {
+    let text = String::from_utf8_lossy(bytes);
+    let found = pattern.find(&text)?;
+    let spans = match &text {
+        std::borrow::Cow::Borrowed(_) => Some(found.range),
+        std::borrow::Cow::Owned(_) => None,
+    };
+    Some(FuzzyMatch {
+        line: LineMatch::with_spans(line_number, bytes, spans),
+        distance: found.distance,
+    })
+}
+
+/// 매치 앞뒤로 `before`/`after` 줄의 컨텍스트를 붙여 블록 단위로 반환합니다.
+///
+/// 컨텍스트가 겹치거나 맞닿은 매치들은 하나의 [`ContextBlock`] 으로 합쳐집니다.
+pub fn search_context(
+    pattern: &Pattern,
+    contents: &str,
+    invert: bool,
+    before: usize,
+    after: usize,
+) -> Vec<ContextBlock> {
+    let options = StreamOptions {
+        invert,
+        before,
+        after,
+        ..StreamOptions::default()
+    };
+    let mut blocks = Vec::new();
+    stream::search_reader(pattern, contents.as_bytes(), &options, |item| {
+        match item {
+            StreamItem::Block(block) => blocks.push(block),
+            // 컨텍스트가 0 이면 라인 하나짜리 블록으로 감쌉니다.
+            StreamItem::Line(line) => blocks.push(ContextBlock {
+                path: None,
+                lines: vec![BlockLine::Match(line)],
+            }),
+        }
+        Ok(())
+    })
+    .expect("searching an in-memory string cannot fail");
+    blocks
+}
+
 // =============================================================================
 // 최종 임무: run 함수로 모든 것을 통합하세요
 // =============================================================================
 pub fn run(_config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = read_file(&_config.filename);
-    let results = if _config.case_sensitive {
-        search(&_config.query, &contents)
-    } else {
-        search_case_insensitive(&_config.query, &contents)
+    if _config.build_index {
+        return build_indexes(&_config).map_err(Into::into);
+    }
+    let matcher = _config.matcher()?;
+    if _config.follow {
+        // 따라갈 파일은 아직 없을 수도 있으므로 입력 목록을 만들지 않습니다.
+        return match follow_paths(&_config, &matcher) {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            result => result.map_err(Into::into),
+        };
+    }
+    let mut failures = Failures::default();
+    let mut inputs = collect_inputs(&_config, &_config.walk_options()?, &mut failures);
+    if _config.index {
+        inputs = inputs.and_then(|inputs| narrow_with_index(&_config, &matcher, inputs));
+    }
+    let failures = &mut failures;
+    let result = match &_config.replace {
+        None if _config.rank.is_some() => {
+            let options = _config.rank.unwrap_or_default();
+            inputs.and_then(|inputs| rank_paths(&_config, &options, &inputs, failures))
+        }
+        None if _config.fuzzy.is_some() => {
+            let pattern = FuzzyPattern::new(
+                &_config.query,
+                _config.fuzzy.unwrap_or_default(),
+                !_config.case_sensitive,
+            );
+            inputs.and_then(|inputs| fuzzy_paths(&_config, &pattern, &inputs, failures))
+        }
+        Some(template) => {
+            let pattern = matcher.as_single().ok_or_else(args::replace_conflict)?;
+            let template = Template::new(template, pattern)?;
+            inputs.and_then(|inputs| replace_paths(&_config, pattern, &template, &inputs, failures))
+        }
+        None => inputs.and_then(|inputs| search_paths(&_config, &matcher, &inputs, failures)),
+    };
+    match result {
+        // `minigrep ... | head` 처럼 출력 쪽이 먼저 닫히면 조용히 끝냅니다.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        Err(e) => Err(e.into()),
+        Ok(()) if !failures.errors.is_empty() => Err(SkippedInputs {
+            errors: std::mem::take(&mut failures.errors),
+        }
+        .into()),
+        Ok(()) => Ok(()),
+    }
+}
+
+/// 일부 입력을 검색하지 못했을 때 [`run`] 이 나머지 입력을 다 검색한 뒤 돌려주는 에러.
+/// 입력마다의 에러는 이미 표준 에러로 알렸으므로 `grep` 처럼 종료 코드 2 로만 알리면 됩니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedInputs {
+    /// 검색하지 못한 입력마다의 에러 메시지 (`경로: 에러`)
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for SkippedInputs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not search {}", self.errors.join(", "))
+    }
+}
+
+impl Error for SkippedInputs {}
+
+/// 검색하지 못한 입력의 에러를 알리고 모아 둡니다.
+#[derive(Debug, Default)]
+struct Failures {
+    errors: Vec<String>,
+}
+
+impl Failures {
+    /// `grep` 처럼 입력 하나의 에러를 표준 에러에 알리고 다음 입력으로 넘어갑니다.
+    /// 출력 쪽이 닫힌 에러는 검색을 멈춰야 하므로 그대로 돌려줍니다.
+    fn report(&mut self, e: io::Error) -> io::Result<()> {
+        if e.kind() == io::ErrorKind::BrokenPipe {
+            return Err(e);
+        }
+        eprintln!("minigrep: {e}");
+        self.errors.push(e.to_string());
+        Ok(())
+    }
+}
+
+/// tar 아카이브 멤버 경로 앞에 붙는 구분자 (`logs.tar!app/server.log`)
+pub const ARCHIVE_SEPARATOR: char = '!';
+
+/// 검색할 입력 하나
+enum Input {
+    Stdin,
+    File(PathBuf),
+    /// tar 아카이브. 검색할 때 멤버를 하나씩 풀어 읽습니다.
+    Archive(PathBuf),
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match self {
+            Input::Stdin => String::from("(standard input)"),
+            Input::File(path) | Input::Archive(path) => path.display().to_string(),
+        }
+    }
+
+    /// 입력을 열어 검색할 내용마다 `f` 를 부릅니다. 보통은 한 번이고,
+    /// 아카이브는 멤버를 하나씩 풀어 가며 부르므로 멤버를 미리 메모리에 모아 두지 않습니다.
+    /// `encoding` 이 `None` 이면 앞부분으로 인코딩을 감지합니다.
+    fn for_each_source<F>(&self, encoding: Option<Encoding>, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(Source<'_>, Opened<'_>) -> io::Result<()>,
+    {
+        let reader: Box<dyn BufRead> = match self {
+            Input::Stdin => Box::new(io::stdin().lock()),
+            Input::File(path) => open_file(path)?,
+            Input::Archive(path) => {
+                let name = self.name();
+                let mut archive = tar::Archive::new(decompress(open_file(path)?)?);
+                while let Some(member) = archive.next_member().map_err(|e| named(e, &name))? {
+                    let source = Source {
+                        input: self,
+                        member: Some(member.path.clone()),
+                    };
+                    let reader = Box::new(io::BufReader::new(member));
+                    let opened = open_reader(reader, &source.name(), encoding)?;
+                    f(source, opened)?;
+                }
+                return Ok(());
+            }
+        };
+        let source = Source {
+            input: self,
+            member: None,
+        };
+        let opened = open_reader(reader, &source.name(), encoding)?;
+        f(source, opened)
+    }
+}
+
+/// 검색 결과가 나온 곳. 아카이브 멤버는 아카이브 경로 뒤에 멤버 경로를 붙여 부릅니다.
+struct Source<'a> {
+    input: &'a Input,
+    member: Option<String>,
+}
+
+impl Source<'_> {
+    fn name(&self) -> String {
+        match &self.member {
+            Some(member) => format!("{}{ARCHIVE_SEPARATOR}{member}", self.input.name()),
+            None => self.input.name(),
+        }
+    }
+
+    /// `--json` 레코드에 넣을 경로. 유닉스에서는 OS 경로 바이트를 그대로 사용합니다.
+    fn path_bytes(&self) -> Vec<u8> {
+        let mut bytes = match self.input {
+            Input::Stdin => self.input.name().into_bytes(),
+            Input::File(path) | Input::Archive(path) => os_path_bytes(path),
+        };
+        if let Some(member) = &self.member {
+            bytes.push(ARCHIVE_SEPARATOR as u8);
+            bytes.extend_from_slice(member.as_bytes());
+        }
+        bytes
+    }
+}
+
+/// 열린 입력
+struct Opened<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    /// 압축을 풀었거나 인코딩을 바꾸는 등 원본 바이트와 다른 내용을 읽는지 여부.
+    /// 이런 입력은 `--write` 로 고쳐 쓰면 원본 형식이 깨집니다.
+    transformed: bool,
+}
+
+#[cfg(unix)]
+fn os_path_bytes(path: &Path) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(not(unix))]
+fn os_path_bytes(path: &Path) -> Vec<u8> {
+    path.to_string_lossy().into_owned().into_bytes()
+}
+
+fn open_file(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    fs::File::open(path)
+        .map(|file| Box::new(io::BufReader::new(file)) as Box<dyn BufRead>)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+/// gzip 으로 압축되어 있으면 풀고, UTF-8 이 아니면 UTF-8 로 바꿔 읽습니다.
+/// 읽다가 난 데이터 에러에는 `name` 을 붙입니다.
+fn open_reader<'a>(
+    mut reader: Box<dyn BufRead + 'a>,
+    name: &str,
+    encoding: Option<Encoding>,
+) -> io::Result<Opened<'a>> {
+    let compressed = gzip::is_gzip(reader.fill_buf().map_err(|e| named(e, name))?);
+    let mut reader = Named::new(decompress(reader)?, name);
+    let encoding = match encoding {
+        Some(encoding) => encoding,
+        None => encoding::detect(reader.fill_buf()?),
     };
+    if encoding == Encoding::Utf8 {
+        // UTF-8 은 그대로 스트리밍하고 BOM 만 건너뜁니다.
+        let has_bom = reader.fill_buf()?.starts_with(encoding::UTF8_BOM);
+        if has_bom {
+            reader.consume(encoding::UTF8_BOM.len());
+        }
+        return Ok(Opened {
+            reader: Box::new(reader),
+            transformed: compressed || has_bom,
+        });
+    }
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let text = encoding::decode(&bytes, encoding);
+    Ok(Opened {
+        reader: Box::new(io::Cursor::new(text.into_bytes())),
+        transformed: true,
+    })
+}
+
+/// gzip 으로 시작하는 입력이면 읽으면서 풀고, 아니면 그대로 돌려줍니다.
+fn decompress<'a>(mut reader: Box<dyn BufRead + 'a>) -> io::Result<Box<dyn BufRead + 'a>> {
+    if !gzip::is_gzip(reader.fill_buf()?) {
+        return Ok(reader);
+    }
+    Ok(Box::new(gzip::GzDecoder::new(reader)))
+}
+
+/// 입력을 읽다 난 에러에 입력 이름을 붙입니다.
+fn named(e: io::Error, name: &str) -> io::Error {
+    io::Error::new(e.kind(), format!("{name}: {e}"))
+}
+
+/// 읽다가 난 에러에 [`named`] 로 입력 이름을 붙이는 리더
+struct Named<R> {
+    inner: R,
+    name: String,
+}
+
+impl<R> Named<R> {
+    fn new(inner: R, name: &str) -> Named<R> {
+        Named {
+            inner,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl<R: BufRead> Read for Named<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf).map_err(|e| named(e, &self.name))
+    }
+}
+
+impl<R: BufRead> BufRead for Named<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let name = &self.name;
+        self.inner.fill_buf().map_err(|e| named(e, name))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
 
-    let line_matched = results
+/// 이름이 `.tar`, `.tar.gz`, `.tgz` 로 끝나고 첫 헤더가 tar 인 파일은 멤버마다 검색합니다.
+/// 이름만 그렇고 tar 가 아니면 보통 파일로 검색합니다. 첫 블록만 (풀어서) 읽어 봅니다.
+fn is_archive(path: &Path) -> io::Result<bool> {
+    let name = path.to_string_lossy();
+    if ![".tar", ".tar.gz", ".tgz"]
         .iter()
-        .enumerate()
-        .map(|(i, line)| LineMatch::new(i, line))
-        .collect::<Vec<LineMatch>>();
+        .any(|ext| name.ends_with(ext))
+    {
+        return Ok(false);
+    }
+    let reader = Named::new(decompress(open_file(path)?)?, &path.display().to_string());
+    let mut head = Vec::new();
+    reader.take(512).read_to_end(&mut head)?;
+    Ok(tar::is_tar(&head))
+}
+
+/// 명령행의 경로들을 검색할 입력 목록으로 펼칩니다.
+/// 없거나 읽을 수 없는 경로는 `failures` 에 알리고 나머지 경로를 계속 펼칩니다.
+fn collect_inputs(
+    config: &Config,
+    walk: &WalkOptions,
+    failures: &mut Failures,
+) -> io::Result<Vec<Input>> {
+    // `-` 는 명령행에서의 위치 그대로 표준 입력으로 검색합니다.
+    let mut inputs = Vec::new();
+    for arg in &config.paths {
+        if arg == STDIN_PATH {
+            inputs.push(Input::Stdin);
+        } else {
+            for file in walk::collect_files_reporting(&[arg], walk, |e| failures.report(e))? {
+                match is_archive(&file) {
+                    Ok(true) => inputs.push(Input::Archive(file)),
+                    Ok(false) => inputs.push(Input::File(file)),
+                    Err(e) => failures.report(e)?,
+                }
+            }
+        }
+    }
+    Ok(inputs)
+}
+
+/// `minigrep index build`. 디렉토리마다 이전 색인을 재사용해 색인을 갱신하고 요약을 출력합니다.
+fn build_indexes(config: &Config) -> io::Result<()> {
+    let walk = config
+        .walk_options()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    for dir in &config.paths {
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{dir}: not a directory"),
+            ));
+        }
+        // 색인 파일이 없거나 읽을 수 없으면 처음부터 만듭니다.
+        let previous = TrigramIndex::load(root).ok();
+        let mut files = walk::collect_files_with(&[root], &walk)?;
+        // `--hidden` 이면 색인 파일 자신도 목록에 들어옵니다.
+        files.retain(|file| file.strip_prefix(root).ok() != Some(Path::new(trigram::INDEX_FILE)));
+        let (index, stats) = TrigramIndex::build(root, &files, previous.as_ref())?;
+        index.save(root)?;
+        writeln!(
+            out,
+            "{dir}: indexed {} files ({} updated, {} removed), {} trigrams",
+            stats.files,
+            stats.updated,
+            stats.removed,
+            index.trigram_count()
+        )?;
+    }
+    out.flush()
+}
+
+/// `--index`. 색인이 있는 디렉토리 아래의 파일 가운데 질의 리터럴이 있을 수 없는 파일을 뺍니다.
+/// 색인에 없거나 색인한 뒤 바뀐 파일, 명령행에 직접 준 파일과 표준 입력은 그대로 둡니다.
+fn narrow_with_index(
+    config: &Config,
+    matcher: &PatternSet,
+    inputs: Vec<Input>,
+) -> io::Result<Vec<Input>> {
+    // 정규식처럼 꼭 들어 있어야 할 리터럴을 모르면 거를 수 없습니다.
+    let Some(literals) = matcher.required_literals() else {
+        return Ok(inputs);
+    };
+    // 색인은 인코딩을 자동 감지해 만들었으므로 `--encoding` 으로 다르게 읽으면 맞지 않습니다.
+    if config.encoding.is_some() {
+        return Ok(inputs);
+    }
+    let mut roots = Vec::new();
+    for dir in config.paths.iter().map(Path::new).filter(|p| p.is_dir()) {
+        let index = TrigramIndex::load(dir).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("{e} (run `minigrep index build {}` first)", dir.display()),
+            )
+        })?;
+        let Some(candidates) = index.candidates(&literals) else {
+            return Ok(inputs);
+        };
+        let positions: std::collections::HashMap<PathBuf, usize> = index
+            .files()
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| (entry.path.clone(), id))
+            .collect();
+        roots.push((dir, index, positions, candidates));
+    }
+
+    let keep = |path: &Path| -> bool {
+        for (root, index, positions, candidates) in &roots {
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            let Some(&id) = positions.get(relative) else {
+                return true;
+            };
+            let fresh = fs::metadata(path).is_ok_and(|m| index.files()[id].is_fresh(&m));
+            return !fresh || candidates[id];
+        }
+        true
+    };
+    Ok(inputs
+        .into_iter()
+        .filter(|input| match input {
+            Input::File(path) => keep(path),
+            Input::Stdin | Input::Archive(_) => true,
+        })
+        .collect())
+}
+
+fn thread_count(config: &Config) -> usize {
+    match config.threads {
+        0 => parallel::default_threads(),
+        n => n,
+    }
+}
+
+/// 여러 파일을 검색할 때는 `grep -r` 처럼 경로를 앞에 붙입니다. 경로를 여러 개 주었으면
+/// 그중 일부를 읽지 못해 하나만 남았어도 붙입니다.
+/// 아카이브 멤버는 어느 아카이브에서 나왔는지 보이도록 항상 경로를 붙입니다.
+fn shows_path(config: &Config, inputs: &[Input]) -> bool {
+    inputs.len() > 1
+        || config.paths.len() > 1
+        || config.paths.iter().any(|p| Path::new(p).is_dir())
+        || inputs
+            .iter()
+            .any(|input| matches!(input, Input::Archive(_)))
+}
+
+fn search_paths(
+    config: &Config,
+    matcher: &PatternSet,
+    inputs: &[Input],
+    failures: &mut Failures,
+) -> io::Result<()> {
+    let show_path = shows_path(config, inputs);
+    let job = SearchJob {
+        config,
+        searcher: config.searcher_with(matcher.clone()),
+        // JSON 레코드에는 색을 넣지 않습니다.
+        palette: Palette::new(!config.json && config.color.enabled_for_stdout()),
+        style: OutputStyle {
+            with_path: show_path,
+            line_number: config.line_number,
+            column: config.column,
+        },
+    };
+    let threads = thread_count(config);
+
+    let started = Instant::now();
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    let mut printed_block = false;
+    let mut total = json::Stats::default();
+
+    if threads == 1 || inputs.len() == 1 {
+        // 한 스레드라면 버퍼링 없이 바로 출력합니다.
+        for input in inputs {
+            match job.search_input(input, &mut out, printed_block) {
+                Ok(searched) => {
+                    printed_block |= searched.has_blocks;
+                    total.add(&searched.stats);
+                }
+                Err(e) => {
+                    out.flush()?;
+                    failures.report(e)?;
+                }
+            }
+        }
+    } else {
+        // 파일마다 결과를 버퍼에 모은 뒤 경로 순서대로 출력합니다.
+        // 압축이 깨진 파일처럼 도중에 실패한 입력도 그 앞까지 찾은 결과는 한 스레드일 때처럼 출력합니다.
+        let render = |input: &Input| -> (Vec<u8>, io::Result<Searched>) {
+            let mut buf = Vec::new();
+            let searched = job.search_input(input, &mut buf, false);
+            (buf, searched)
+        };
+        parallel::ordered_map(inputs, threads, render, |(buf, searched)| {
+            let searched = match searched {
+                Ok(searched) => searched,
+                Err(e) => {
+                    // 에러 메시지가 그 앞의 결과보다 먼저 보이지 않도록 결과부터 내보냅니다.
+                    out.write_all(&buf)?;
+                    out.flush()?;
+                    return failures.report(e);
+                }
+            };
+            if printed_block && searched.has_blocks {
+                writeln!(out, "{}", job.separator())?;
+            }
+            printed_block |= searched.has_blocks;
+            total.add(&searched.stats);
+            out.write_all(&buf)
+        })?;
+    }
+
+    if config.json {
+        json::write_summary(&mut out, started.elapsed(), &total)?;
+    }
+    if config.stats {
+        write_stats(&mut out, started.elapsed(), &total)?;
+    }
+    out.flush()
+}
+
+/// `--stats` 의 통계를 출력합니다. `elapsed_total` 은 벽시계 기준 전체 시간입니다.
+fn write_stats(
+    out: &mut dyn Write,
+    elapsed_total: Duration,
+    stats: &json::Stats,
+) -> io::Result<()> {
+    writeln!(out)?;
+    writeln!(out, "{} matches", stats.matches)?;
+    writeln!(out, "{} matched lines", stats.matched_lines)?;
+    writeln!(out, "{} files contained matches", stats.searches_with_match)?;
+    writeln!(out, "{} files searched", stats.searches)?;
+    writeln!(out, "{} bytes searched", stats.bytes_searched)?;
+    writeln!(out, "{:.6} seconds", elapsed_total.as_secs_f64())
+}
+
+/// `--fuzzy` 모드. 모든 입력의 결과를 모아 편집 거리 순으로 출력합니다.
+/// `-c`, `-l`, `-L` 은 정렬 없이 입력 순서대로 출력합니다.
+fn fuzzy_paths(
+    config: &Config,
+    pattern: &FuzzyPattern,
+    inputs: &[Input],
+    failures: &mut Failures,
+) -> io::Result<()> {
+    let style = OutputStyle {
+        with_path: shows_path(config, inputs),
+        line_number: config.line_number,
+        column: config.column,
+    };
+    let palette = Palette::new(config.color.enabled_for_stdout());
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+
+    // 아카이브는 멤버마다 결과를 따로 모읍니다.
+    let search = |input: &Input| -> io::Result<Vec<(String, Vec<FuzzyMatch>)>> {
+        let mut searched = Vec::new();
+        input.for_each_source(config.encoding, |source, opened| {
+            let path = source.name();
+            let mut reader = opened.reader;
+            let mut results = Vec::new();
+            if walk::is_binary(reader.fill_buf()?) {
+                searched.push((path, results));
+                return Ok(());
+            }
+            let mut buf = Vec::new();
+            let mut line_number = 0;
+            loop {
+                buf.clear();
+                if reader.read_until(b'\n', &mut buf)? == 0 {
+                    break;
+                }
+                line_number += 1;
+                let line = stream::trim_line_end(&buf);
+                if let Some(mut found) = fuzzy_line(pattern, line_number, line) {
+                    found.line.path = Some(path.clone());
+                    results.push(found);
+                }
+            }
+            searched.push((path, results));
+            Ok(())
+        })?;
+        Ok(searched)
+    };
+
+    let mut all = Vec::new();
+    parallel::ordered_map(inputs, thread_count(config), search, |searched| {
+        let searched = match searched {
+            Ok(searched) => searched,
+            Err(e) => return failures.report(e),
+        };
+        for (path, results) in searched {
+            let painted_path = color::painted(palette.path, &path);
+            if config.lists_files() {
+                if results.is_empty() == config.files_without_match {
+                    writeln!(out, "{}", painted_path)?;
+                }
+            } else if config.count {
+                if style.with_path {
+                    let separator = color::painted(palette.separator, ":");
+                    writeln!(out, "{}{}{}", painted_path, separator, results.len())?;
+                } else {
+                    writeln!(out, "{}", results.len())?;
+                }
+            } else {
+                all.extend(results);
+            }
+        }
+        Ok(())
+    })?;
+
+    rank_results(&mut all);
+    for result in &all {
+        writeln!(out, "{}", result.format_colored(&style, &palette))?;
+    }
+    out.flush()
+}
+
+/// `--follow` 모드. 파일마다 덧붙는 라인을 검색해 매치가 나오는 대로 출력합니다.
+/// 에러가 나거나 출력 쪽이 닫힐 때까지 끝나지 않습니다.
+fn follow_paths(config: &Config, matcher: &PatternSet) -> io::Result<()> {
+    if config.paths.iter().any(|path| path == STDIN_PATH) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--follow cannot read standard input",
+        ));
+    }
+    let style = OutputStyle {
+        with_path: config.paths.len() > 1,
+        line_number: config.line_number,
+        column: config.column,
+    };
+    let palette = Palette::new(config.color.enabled_for_stdout());
+    let mut followers: Vec<Follower> = config.paths.iter().map(Follower::new).collect();
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    loop {
+        for follower in &mut followers {
+            let path = follower.path().display().to_string();
+            follower.poll(|event| match event {
+                follow::Event::Line { number, bytes } => {
+                    if matcher.is_match_line(bytes) == config.invert_match {
+                        return Ok(());
+                    }
+                    let line = match config.invert_match {
+                        true => LineMatch::from_bytes(number, bytes, None),
+                        false => LineMatch::with_spans(number, bytes, matcher.find_spans(bytes)),
+                    };
+                    let line = line.with_path(&path);
+                    writeln!(out, "{}", line.format_colored(&style, &palette))
+                }
+                // 알림은 검색 결과와 섞이지 않도록 표준 에러로 보냅니다.
+                follow::Event::Truncated => {
+                    eprintln!("minigrep: {path}: file truncated");
+                    Ok(())
+                }
+                follow::Event::Replaced => {
+                    eprintln!("minigrep: {path}: has been replaced; following new file");
+                    Ok(())
+                }
+                follow::Event::Appeared => {
+                    eprintln!("minigrep: {path}: has appeared; following new file");
+                    Ok(())
+                }
+                follow::Event::Waiting(e) => {
+                    eprintln!("minigrep: {path}: {e}; waiting for it to appear");
+                    Ok(())
+                }
+            })?;
+        }
+        out.flush()?;
+        thread::sleep(follow::POLL_INTERVAL);
+    }
+}
+
+/// `minigrep rank` 모드. 모든 입력을 읽어 하나의 말뭉치로 색인한 뒤 점수 순으로 출력합니다.
+fn rank_paths(
+    config: &Config,
+    options: &RankOptions,
+    inputs: &[Input],
+    failures: &mut Failures,
+) -> io::Result<()> {
+    // 아카이브 멤버는 각각 하나의 문서입니다.
+    let read = |input: &Input| -> io::Result<Vec<(String, String)>> {
+        let mut documents = Vec::new();
+        input.for_each_source(config.encoding, |source, mut opened| {
+            let mut contents = Vec::new();
+            opened.reader.read_to_end(&mut contents)?;
+            if !walk::is_binary(&contents) {
+                let contents = String::from_utf8_lossy(&contents).into_owned();
+                documents.push((source.name(), contents));
+            }
+            Ok(())
+        })?;
+        Ok(documents)
+    };
+    let mut corpus = Vec::new();
+    parallel::ordered_map(
+        inputs,
+        thread_count(config),
+        read,
+        |document| match document {
+            Ok(document) => {
+                corpus.extend(document);
+                Ok(())
+            }
+            Err(e) => failures.report(e),
+        },
+    )?;
+
+    let palette = Palette::new(config.color.enabled_for_stdout());
+    let results = rank_corpus(&config.query, &corpus, options);
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    if !results.is_empty() {
+        let style = OutputStyle::default();
+        writeln!(
+            out,
+            "{}",
+            format_results_colored(&results, &style, &palette)
+        )?;
+    }
+    out.flush()
+}
 
-    let formatted_results = format_results(&line_matched);
-    println!("{}", formatted_results);
-    Ok(())
+/// `--replace` 모드. 파일마다 diff 를 출력하거나 `--write` 면 파일을 고쳐 씁니다.
+fn replace_paths(
+    config: &Config,
+    pattern: &Pattern,
+    template: &Template,
+    inputs: &[Input],
+    failures: &mut Failures,
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    let render = |input: &Input| -> io::Result<Vec<u8>> {
+        let mut rendered = Vec::new();
+        input.for_each_source(config.encoding, |source, mut opened| {
+            let mut contents = Vec::new();
+            opened.reader.read_to_end(&mut contents)?;
+            if walk::is_binary(&contents) {
+                return Ok(());
+            }
+            let rewrite = replace::Rewrite::new(pattern, template, &contents);
+            match input {
+                Input::File(path) if config.write => {
+                    if rewrite.changed_lines() > 0 {
+                        // 압축을 풀거나 UTF-8 로 바꾼 내용을 그대로 쓰면 원본 형식이 바뀌므로
+                        // 고쳐 쓰지 않습니다.
+                        if opened.transformed {
+                            return Err(read_only(&source));
+                        }
+                        replace::write_atomic(path, &rewrite.apply())?;
+                    }
+                }
+                Input::Archive(_) if config.write => {
+                    if rewrite.changed_lines() > 0 {
+                        return Err(read_only(&source));
+                    }
+                }
+                // 표준 입력은 고쳐 쓸 파일이 없으므로 `sed` 처럼 치환 결과를 그대로 출력합니다.
+                Input::Stdin if config.write => rendered.extend(rewrite.apply()),
+                _ => rendered.extend(rewrite.unified_diff(&source.name())),
+            }
+            Ok(())
+        })?;
+        Ok(rendered)
+    };
+    parallel::ordered_map(
+        inputs,
+        thread_count(config),
+        render,
+        |rendered| match rendered {
+            Ok(rendered) => out.write_all(&rendered),
+            Err(e) => failures.report(e),
+        },
+    )?;
+    out.flush()
+}
+
+/// `--write` 로 고쳐 쓸 수 없는 압축 파일, 아카이브 멤버, UTF-8 이 아닌 파일
+fn read_only(source: &Source) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "{}: cannot write to compressed, archived or non-UTF-8 input",
+            source.name()
+        ),
+    )
+}
+
+/// 입력 하나를 검색한 결과
+struct Searched {
+    /// `--` 구분선이 필요한 컨텍스트 블록을 출력했는지 여부
+    has_blocks: bool,
+    /// `--json` 의 `end` / `summary` 레코드에 쓰이는 통계
+    stats: json::Stats,
+}
+
+/// 한 번의 `run` 에서 모든 입력에 공통으로 쓰이는 검색 설정
+struct SearchJob<'a> {
+    config: &'a Config,
+    searcher: Searcher,
+    style: OutputStyle,
+    palette: Palette,
+}
+
+impl SearchJob<'_> {
+    /// 떨어진 블록 사이의 구분선
+    fn separator(&self) -> String {
+        color::painted(self.palette.separator, GROUP_SEPARATOR)
+    }
+
+    /// 입력 하나를 검색해 `out` 에 씁니다. 아카이브는 멤버를 차례로 검색합니다.
+    /// `printed_block` 은 앞에서 블록이 출력됐는지 여부입니다.
+    fn search_input(
+        &self,
+        input: &Input,
+        out: &mut dyn Write,
+        mut printed_block: bool,
+    ) -> io::Result<Searched> {
+        let mut searched = Searched {
+            has_blocks: false,
+            stats: json::Stats::default(),
+        };
+        input.for_each_source(self.config.encoding, |source, opened| {
+            let found = self.search_source(&source, opened.reader, out, printed_block)?;
+            printed_block |= found.has_blocks;
+            searched.has_blocks |= found.has_blocks;
+            searched.stats.add(&found.stats);
+            Ok(())
+        })?;
+        Ok(searched)
+    }
+
+    /// 열린 입력 하나(아카이브라면 멤버 하나)를 검색해 `out` 에 씁니다.
+    fn search_source(
+        &self,
+        source: &Source,
+        reader: Box<dyn BufRead + '_>,
+        out: &mut dyn Write,
+        printed_block: bool,
+    ) -> io::Result<Searched> {
+        let config = self.config;
+        let started = Instant::now();
+        let path = source.name();
+        if config.json {
+            let mut printer =
+                json::JsonPrinter::new(out, source.path_bytes()).search_binary(config.binary);
+            self.searcher.search_at(reader, Some(&path), &mut printer)?;
+            return Ok(Searched {
+                has_blocks: false,
+                stats: printer.stats().unwrap_or_default(),
+            });
+        }
+        let mut printer = Printer::new(&mut *out, self.style, self.palette)
+            .with_context(config.before_context > 0 || config.after_context > 0)
+            .after_block(printed_block)
+            .quiet(config.count || config.lists_files())
+            .search_binary(config.binary);
+        let summary = self.searcher.search_at(reader, Some(&path), &mut printer)?;
+        let (has_blocks, binary) = (printer.has_blocks(), printer.saw_binary());
+        if binary && !config.binary {
+            return Ok(Searched {
+                has_blocks: false,
+                stats: json::Stats::default(),
+            });
+        }
+
+        let painted_path = color::painted(self.palette.path, &path);
+        if config.lists_files() {
+            if (summary.matched_lines == 0) == config.files_without_match {
+                writeln!(out, "{}", painted_path)?;
+            }
+        } else if config.count {
+            if self.style.with_path {
+                let separator = color::painted(self.palette.separator, ":");
+                writeln!(
+                    out,
+                    "{}{}{}",
+                    painted_path, separator, summary.matched_lines
+                )?;
+            } else {
+                writeln!(out, "{}", summary.matched_lines)?;
+            }
+        } else if binary && summary.matched_lines > 0 {
+            writeln!(out, "{}: binary file matches", painted_path)?;
+        }
+        Ok(Searched {
+            has_blocks,
+            stats: json::Stats::from_summary(&summary, started.elapsed()),
+        })
+    }
 }
@@ -0,0 +1,248 @@
+//! 리터럴 바이트 검색
+//!
+//! 메타 문자가 없는 패턴은 정규식 VM 대신 이 모듈로 찾습니다.
+//!
+//! - [`memchr`] / [`memrchr`] / [`count_byte`]: 한 번에 `usize` 하나(8 바이트)씩 비교하는
+//!   SWAR(SIMD within a register) 방식의 바이트 검색. 외부 크레이트 없이도 컴파일러가
+//!   자동 벡터화하기 좋은 형태입니다.
+//! - [`Finder`]: 바늘(needle)에서 가장 드물게 나올 바이트 두 개를 골라, 두 바이트가 모두
+//!   맞는 위치를 워드 단위로 한꺼번에 걸러낸 뒤 나머지를 비교합니다.
+//!   후보가 너무 자주 나와서 이득이 없으면 Horspool 로 전환합니다.
+
+const WORD: usize = std::mem::size_of::<usize>();
+const LO: usize = usize::MAX / 255; // 0x0101...01
+const HI: usize = LO << 7; // 0x8080...80
+
+/// 워드 안에 0 바이트가 있으면 참 (Mycroft 의 트릭)
+#[inline]
+fn has_zero(word: usize) -> bool {
+    word.wrapping_sub(LO) & !word & HI != 0
+}
+
+#[inline]
+fn read_word(bytes: &[u8], at: usize) -> usize {
+    let mut word = [0; WORD];
+    word.copy_from_slice(&bytes[at..at + WORD]);
+    usize::from_ne_bytes(word)
+}
+
+/// `haystack` 에서 `byte` 가 처음 나타나는 위치
+pub fn memchr(byte: u8, haystack: &[u8]) -> Option<usize> {
+    let repeated = LO * byte as usize;
+    let mut i = 0;
+    while i + WORD <= haystack.len() {
+        if has_zero(read_word(haystack, i) ^ repeated) {
+            break;
+        }
+        i += WORD;
+    }
+    haystack[i..]
+        .iter()
+        .position(|&b| b == byte)
+        .map(|pos| i + pos)
+}
+
+/// `haystack` 에서 `byte` 가 마지막으로 나타나는 위치
+pub fn memrchr(byte: u8, haystack: &[u8]) -> Option<usize> {
+    let repeated = LO * byte as usize;
+    let mut end = haystack.len();
+    while end >= WORD {
+        if has_zero(read_word(haystack, end - WORD) ^ repeated) {
+            break;
+        }
+        end -= WORD;
+    }
+    haystack[..end].iter().rposition(|&b| b == byte)
+}
+
+/// `haystack` 안의 `byte` 개수. 라인 번호를 셀 때 사용합니다.
+pub fn count_byte(byte: u8, haystack: &[u8]) -> usize {
+    // 고정 길이 청크로 나누면 컴파일러가 비교와 합산을 벡터화합니다.
+    let mut chunks = haystack.chunks_exact(64);
+    let mut count = 0;
+    for chunk in &mut chunks {
+        count += chunk.iter().filter(|&&b| b == byte).count();
+    }
+    count + chunks.remainder().iter().filter(|&&b| b == byte).count()
+}
+
+/// 바이트가 일반적인 텍스트/소스 코드에 얼마나 흔한지 대략적인 순위 (클수록 흔함)
+fn frequency_rank(byte: u8) -> u8 {
+    match byte {
+        b' ' | b'e' | b't' | b'a' | b'o' | b'i' | b'n' | b's' | b'r' => 255,
+        b'h' | b'l' | b'd' | b'c' | b'u' | b'm' | b'\n' | b'_' => 230,
+        b'a'..=b'z' => 200,
+        b'0'..=b'9' | b'.' | b',' | b'(' | b')' | b'"' | b';' | b':' | b'=' | b'/' => 180,
+        b'A'..=b'Z' => 150,
+        0x21..=0x7E => 120,
+        0x80..=0xFF => 100, // 멀티바이트 UTF-8 의 일부
+        _ => 50,
+    }
+}
+
+/// 버퍼에서 바늘을 찾는 검색기. [`matching_lines`] 가 사용합니다.
+pub trait FindAt {
+    /// `start` 이후에서 찾은 매치의 시작 위치
+    fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize>;
+}
+
+/// 후보 하나당 평균 이만큼도 전진하지 못하면 Horspool 로 전환합니다.
+const MIN_SKIP_PER_CANDIDATE: usize = 16;
+/// 전환 여부를 판단하기 전에 살펴볼 최소 후보 수
+const CANDIDATES_BEFORE_SWITCH: usize = 64;
+
+/// 미리 준비된 리터럴 검색기
+#[derive(Debug, Clone)]
+pub struct Finder {
+    needle: Vec<u8>,
+    /// 후보 탐색에 쓰는 가장 드문 바이트 두 개의 위치
+    rare: (usize, usize),
+    /// Horspool 이동 거리 표
+    shift: Box<[usize; 256]>,
+}
+
+impl Finder {
+    pub fn new(needle: &[u8]) -> Finder {
+        let rarest = |skip: Option<usize>| {
+            (0..needle.len())
+                .filter(|&i| Some(i) != skip)
+                .min_by_key(|&i| frequency_rank(needle[i]))
+        };
+        let first = rarest(None).unwrap_or(0);
+        let rare = (first, rarest(Some(first)).unwrap_or(first));
+        let mut shift = Box::new([needle.len().max(1); 256]);
+        for (i, &b) in needle
+            .iter()
+            .enumerate()
+            .take(needle.len().saturating_sub(1))
+        {
+            shift[b as usize] = needle.len() - 1 - i;
+        }
+        Finder {
+            needle: needle.to_vec(),
+            rare,
+            shift,
+        }
+    }
+
+    pub fn needle(&self) -> &[u8] {
+        &self.needle
+    }
+
+    /// `haystack` 에서 바늘이 처음 나타나는 위치
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        self.find_at(haystack, 0)
+    }
+
+    /// `start` 이후에서 바늘이 처음 나타나는 위치
+    pub fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        let n = self.needle.len();
+        if start > haystack.len() {
+            return None;
+        }
+        if n == 0 {
+            return Some(start);
+        }
+        if n == 1 {
+            return memchr(self.needle[0], &haystack[start..]).map(|i| start + i);
+        }
+
+        let (i1, i2) = self.rare;
+        let (b1, b2) = (self.needle[i1], self.needle[i2]);
+        let (r1, r2) = (LO * b1 as usize, LO * b2 as usize);
+        let last = haystack.len().checked_sub(n)?;
+        let mut pos = start;
+        let mut candidates = 0;
+        while pos <= last {
+            // 후보 시작 위치 `pos..pos + WORD` 를 한 번에 거릅니다.
+            // 두 워드를 XOR 한 뒤 OR 하면 두 바이트가 모두 맞는 자리만 0 이 됩니다.
+            let width = if pos + WORD <= last + 1 {
+                let w1 = read_word(haystack, pos + i1) ^ r1;
+                let w2 = read_word(haystack, pos + i2) ^ r2;
+                if !has_zero(w1 | w2) {
+                    pos += WORD;
+                    continue;
+                }
+                WORD
+            } else {
+                last + 1 - pos
+            };
+            for at in pos..pos + width {
+                if haystack[at + i1] != b1 || haystack[at + i2] != b2 {
+                    continue;
+                }
+                if haystack[at..at + n] == self.needle[..] {
+                    return Some(at);
+                }
+                candidates += 1;
+            }
+            pos += width;
+            if candidates >= CANDIDATES_BEFORE_SWITCH
+                && pos - start < candidates * MIN_SKIP_PER_CANDIDATE
+            {
+                return self.horspool(haystack, pos);
+            }
+        }
+        None
+    }
+
+    /// 후보가 너무 흔한 입력을 위한 Boyer-Moore-Horspool 검색
+    fn horspool(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        let n = self.needle.len();
+        let last = self.needle[n - 1];
+        let mut pos = start;
+        while pos + n <= haystack.len() {
+            let tail = haystack[pos + n - 1];
+            if tail == last && haystack[pos..pos + n - 1] == self.needle[..n - 1] {
+                return Some(pos);
+            }
+            pos += self.shift[tail as usize];
+        }
+        None
+    }
+}
+
+impl FindAt for Finder {
+    fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        Finder::find_at(self, haystack, start)
+    }
+}
+
+/// `haystack` 전체에서 바늘을 찾고, 찾을 때마다 그 위치를 포함하는 라인의 범위를 돌려줍니다.
+/// 라인 범위에 `\n` 은 포함되지 않으며, 한 라인은 한 번만 나옵니다.
+///
+/// 라인을 먼저 나누지 않으므로 매치가 드문 큰 입력에서는 대부분의 바이트를
+/// 검색기만 훑고 지나갑니다. 바늘에 `\n` 이 있으면 결과가 라인 범위를 넘을 수 있습니다.
+pub fn matching_lines<'f, 'h, F: FindAt + ?Sized>(
+    finder: &'f F,
+    haystack: &'h [u8],
+) -> MatchingLines<'f, 'h, F> {
+    MatchingLines {
+        finder,
+        haystack,
+        pos: 0,
+    }
+}
+
+/// [`matching_lines`] 가 반환하는 반복자
+pub struct MatchingLines<'f, 'h, F: ?Sized = Finder> {
+    finder: &'f F,
+    haystack: &'h [u8],
+    pos: usize,
+}
+
+impl<F: FindAt + ?Sized> Iterator for MatchingLines<'_, '_, F> {
+    type Item = std::ops::Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.haystack.len() {
+            return None;
+        }
+        let hit = self.finder.find_at(self.haystack, self.pos)?;
+        let start =
+            memrchr(b'\n', &self.haystack[self.pos..hit]).map_or(self.pos, |i| self.pos + i + 1);
+        let end = memchr(b'\n', &self.haystack[hit..]).map_or(self.haystack.len(), |i| hit + i);
+        self.pos = end + 1;
+        Some(start..end)
+    }
+}
@@ -1,17 +1,33 @@
 use std::env;
+use std::io::{self, Write};
 use std::process;
 
-use minigrep::Config;
+use minigrep::{Config, ConfigError, SkippedInputs};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     let config = Config::build(&args).unwrap_or_else(|err| {
+        if err == ConfigError::Help {
+            // `minigrep --help | head -1` 처럼 출력 쪽이 먼저 닫혀도 검색 결과처럼 조용히 끝냅니다.
+            let mut out = io::stdout().lock();
+            match writeln!(out, "{err}").and_then(|()| out.flush()) {
+                Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+                    eprintln!("Application error: {e}");
+                    process::exit(1);
+                }
+                _ => process::exit(0),
+            }
+        }
         eprintln!("Problem parsing arguments: {err}");
-        process::exit(1);
+        process::exit(2);
     });
 
     if let Err(e) = minigrep::run(config) {
+        // 검색하지 못한 입력은 이미 하나씩 알렸으므로 `grep` 처럼 종료 코드로만 알립니다.
+        if e.is::<SkippedInputs>() {
+            process::exit(2);
+        }
         eprintln!("Application error: {e}");
         process::exit(1);
     }
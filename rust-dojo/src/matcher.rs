@@ -0,0 +1,511 @@
+//! 여러 패턴의 조합 (`-e`, `-f`, `--and`, `--or`, `--not`)
+//!
+//! `git grep` 과 같은 문법으로 패턴들을 조합합니다. 우선순위는 `--not` > `--and` > `--or` 이고,
+//! 연산자 없이 나란히 둔 패턴은 `--or` 로 묶입니다. 모든 조건은 라인 단위로 판단합니다.
+//!
+//! ```text
+//! -e TODO -e FIXME                   TODO 나 FIXME 가 있는 라인
+//! -e unsafe --and -e unwrap          두 패턴이 모두 있는 라인
+//! -f banned.txt --and --not -e ok    banned.txt 의 패턴 중 하나가 있고 ok 는 없는 라인
+//! ```
+//!
+//! 메타 문자가 없는 패턴들은 [`AhoCorasick`] 오토마톤 하나로 모아 라인을 한 번만 훑고,
+//! 나머지 패턴만 정규식 엔진으로 따로 확인합니다. `-i` 면 패턴과 라인을 모두 접어서 찾고,
+//! `-w` 면 오토마톤이 찾은 위치마다 앞뒤가 단어 경계인지 확인합니다.
+
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Range;
+
+use crate::aho_corasick::{AhoCorasick, PatternMatch};
+use crate::literal::FindAt;
+use crate::pattern;
+use crate::{unicode, Pattern, PatternOptions};
+
+/// 라인을 고르는 검색 조건. [`crate::stream::search_reader`] 가 사용합니다.
+pub trait Matcher {
+    /// 라인(줄 끝 문자 제외)이 조건에 맞는지 확인합니다.
+    fn is_match_line(&self, line: &[u8]) -> bool;
+
+    /// 라인 안의 매치 구간들. 시작 위치 순서이며 서로 겹치지 않습니다.
+    fn find_spans(&self, line: &[u8]) -> Vec<Range<usize>>;
+
+    /// 라인으로 나누지 않고 버퍼 전체에서 바로 찾아도 되는 검색기.
+    /// 찾은 위치가 있는 라인은 반드시 조건에 맞고, 조건에 맞는 라인에는 반드시 찾을 위치가 있어야 합니다.
+    fn buffer_finder(&self) -> Option<&dyn FindAt> {
+        None
+    }
+}
+
+/// 라인을 나누지 않고 찾아도 되는 바늘인지 확인합니다.
+fn fits_in_line(needle: &[u8]) -> bool {
+    !needle.is_empty() && !needle.contains(&b'\n') && !needle.contains(&b'\r')
+}
+
+impl Matcher for Pattern {
+    fn is_match_line(&self, line: &[u8]) -> bool {
+        self.find_at(line, 0).is_some()
+    }
+
+    fn find_spans(&self, line: &[u8]) -> Vec<Range<usize>> {
+        self.find_iter(line).collect()
+    }
+
+    fn buffer_finder(&self) -> Option<&dyn FindAt> {
+        let finder = self.literal_finder()?;
+        fits_in_line(finder.needle()).then_some(finder as &dyn FindAt)
+    }
+}
+
+// =============================================================================
+// 패턴 식
+// =============================================================================
+
+/// 패턴 식을 이루는 커맨드라인 항목
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprTerm {
+    /// `-e PATTERN`
+    Pattern(String),
+    /// `-f FILE`: 파일의 각 줄이 패턴이고, 그중 하나라도 맞으면 참입니다.
+    File(String),
+    /// `--and`
+    And,
+    /// `--or`
+    Or,
+    /// `--not`
+    Not,
+}
+
+impl ExprTerm {
+    fn operator_name(&self) -> &'static str {
+        match self {
+            ExprTerm::And => "--and",
+            ExprTerm::Or => "--or",
+            ExprTerm::Not => "--not",
+            ExprTerm::Pattern(_) => "--regexp",
+            ExprTerm::File(_) => "--file",
+        }
+    }
+}
+
+/// 패턴 식 파싱 에러
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    /// 연산자 앞이나 뒤에 패턴이 없습니다.
+    MissingOperand(&'static str),
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::MissingOperand(op) => write!(f, "missing pattern for '{op}'"),
+        }
+    }
+}
+
+impl Error for ExprError {}
+
+/// 패턴 식의 구문 트리
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// 패턴 하나. [`parse_expr`] 의 결과에서는 [`ExprTerm`] 목록의 위치입니다.
+    Term(usize),
+    Not(Box<Expr>),
+    /// 모두 참 (`--and`)
+    All(Vec<Expr>),
+    /// 하나라도 참 (`--or`). 비어 있으면 거짓입니다.
+    Any(Vec<Expr>),
+}
+
+impl Expr {
+    /// `term` 이 참인지 알려주는 함수로 식을 계산합니다. 필요한 항만 물어봅니다.
+    pub fn eval<F: FnMut(usize) -> bool>(&self, term: &mut F) -> bool {
+        match self {
+            Expr::Term(index) => term(*index),
+            Expr::Not(inner) => !inner.eval(term),
+            Expr::All(exprs) => exprs.iter().all(|e| e.eval(term)),
+            Expr::Any(exprs) => exprs.iter().any(|e| e.eval(term)),
+        }
+    }
+
+    /// 각 항을 다른 식으로 바꿉니다.
+    fn map_terms<F: FnMut(usize) -> Expr>(self, f: &mut F) -> Expr {
+        match self {
+            Expr::Term(index) => f(index),
+            Expr::Not(inner) => Expr::Not(Box::new(inner.map_terms(f))),
+            Expr::All(exprs) => Expr::All(exprs.into_iter().map(|e| e.map_terms(f)).collect()),
+            Expr::Any(exprs) => Expr::Any(exprs.into_iter().map(|e| e.map_terms(f)).collect()),
+        }
+    }
+
+    /// 항마다 `--not` 아래에 있는지(홀수 번) 표시합니다.
+    fn visit_terms(&self, negated: bool, visit: &mut impl FnMut(usize, bool)) {
+        match self {
+            Expr::Term(index) => visit(*index, negated),
+            Expr::Not(inner) => inner.visit_terms(!negated, visit),
+            Expr::All(exprs) | Expr::Any(exprs) => {
+                exprs.iter().for_each(|e| e.visit_terms(negated, visit))
+            }
+        }
+    }
+
+    fn is_term_list(&self) -> bool {
+        match self {
+            Expr::Term(_) => true,
+            Expr::Any(exprs) => exprs.iter().all(|e| matches!(e, Expr::Term(_))),
+            _ => false,
+        }
+    }
+}
+
+/// 커맨드라인 항목들을 식으로 파싱합니다. `-f` 파일은 아직 읽지 않습니다.
+pub fn parse_expr(terms: &[ExprTerm]) -> Result<Expr, ExprError> {
+    let mut parser = ExprParser { terms, pos: 0 };
+    parser.parse_or()
+}
+
+struct ExprParser<'a> {
+    terms: &'a [ExprTerm],
+    pos: usize,
+}
+
+impl ExprParser<'_> {
+    fn parse_or(&mut self) -> Result<Expr, ExprError> {
+        let mut any = vec![self.parse_and(None)?];
+        while let Some(term) = self.terms.get(self.pos) {
+            let operator = match term {
+                ExprTerm::Or => {
+                    self.pos += 1;
+                    Some("--or")
+                }
+                // 연산자 없이 이어진 패턴은 `--or` 로 묶습니다.
+                _ => None,
+            };
+            any.push(self.parse_and(operator)?);
+        }
+        Ok(flatten(any, Expr::Any))
+    }
+
+    fn parse_and(&mut self, operator: Option<&'static str>) -> Result<Expr, ExprError> {
+        let mut all = vec![self.parse_unary(operator)?];
+        while self.terms.get(self.pos) == Some(&ExprTerm::And) {
+            self.pos += 1;
+            all.push(self.parse_unary(Some("--and"))?);
+        }
+        Ok(flatten(all, Expr::All))
+    }
+
+    /// `operator` 는 피연산자를 기다리는 연산자입니다.
+    fn parse_unary(&mut self, operator: Option<&'static str>) -> Result<Expr, ExprError> {
+        let index = self.pos;
+        let term = self.terms.get(index);
+        self.pos += 1;
+        match term {
+            Some(ExprTerm::Not) => Ok(Expr::Not(Box::new(self.parse_unary(Some("--not"))?))),
+            Some(ExprTerm::Pattern(_) | ExprTerm::File(_)) => Ok(Expr::Term(index)),
+            // 이항 연산자가 피연산자 자리에 오면 그 연산자의 왼쪽이 비어 있는 것입니다.
+            Some(other) => Err(ExprError::MissingOperand(other.operator_name())),
+            None => Err(ExprError::MissingOperand(operator.unwrap_or("--regexp"))),
+        }
+    }
+}
+
+fn flatten(mut exprs: Vec<Expr>, wrap: fn(Vec<Expr>) -> Expr) -> Expr {
+    match exprs.len() {
+        1 => exprs.pop().unwrap(),
+        _ => wrap(exprs),
+    }
+}
+
+// =============================================================================
+// 패턴 집합
+// =============================================================================
+
+#[derive(Debug, Clone)]
+struct Leaf {
+    pattern: Pattern,
+    /// 리터럴 오토마톤 안에서의 번호
+    literal: Option<usize>,
+    /// `--not` 아래에 있는 패턴은 매치 구간으로 표시하지 않습니다.
+    negated: bool,
+}
+
+/// 패턴 식 전체를 검사하는 [`Matcher`]
+#[derive(Debug, Clone)]
+pub struct PatternSet {
+    leaves: Vec<Leaf>,
+    /// 항은 `leaves` 의 위치입니다.
+    expr: Expr,
+    /// 두 개 이상의 리터럴 패턴을 모은 오토마톤
+    literals: Option<AhoCorasick>,
+    /// 오토마톤이 대소문자를 접은 패턴으로 만들어졌는지 (`-i`)
+    folded: bool,
+    /// 오토마톤 번호 → `leaves` 위치
+    literal_leaves: Vec<usize>,
+    /// 식이 리터럴 패턴들의 `--or` 뿐이라 오토마톤만으로 라인을 고를 수 있는지
+    literal_only: bool,
+}
+
+impl PatternSet {
+    /// 패턴 하나짜리 집합
+    pub fn single(pattern: Pattern) -> PatternSet {
+        PatternSet::new(vec![pattern], Expr::Term(0))
+    }
+
+    /// 패턴 중 하나라도 맞으면 참인 집합 (`-e A -e B`)
+    pub fn any(patterns: Vec<Pattern>) -> PatternSet {
+        let expr = Expr::Any((0..patterns.len()).map(Expr::Term).collect());
+        PatternSet::new(patterns, expr)
+    }
+
+    /// `expr` 의 항은 `patterns` 의 위치입니다.
+    pub fn new(patterns: Vec<Pattern>, expr: Expr) -> PatternSet {
+        let mut negated = vec![false; patterns.len()];
+        expr.visit_terms(false, &mut |index, under_not| negated[index] = under_not);
+
+        // 오토마톤 하나는 한 가지 방식으로만 비교하므로 대소문자 무시 여부가 같은 패턴만 모읍니다.
+        let folded = patterns.iter().any(Pattern::is_case_insensitive);
+        let literal_leaves: Vec<usize> = (0..patterns.len())
+            .filter(|&i| {
+                patterns[i].literal_text().is_some() && patterns[i].is_case_insensitive() == folded
+            })
+            .collect();
+        // 리터럴이 하나뿐이면 그 패턴의 검색기가 더 빠릅니다.
+        let literals = (literal_leaves.len() >= 2).then(|| {
+            let needles: Vec<Vec<u8>> = literal_leaves
+                .iter()
+                .map(|&i| {
+                    let text = patterns[i].literal_text().unwrap();
+                    match folded {
+                        true => text.chars().map(unicode::simple_fold).collect::<String>(),
+                        false => text.to_string(),
+                    }
+                    .into_bytes()
+                })
+                .collect();
+            AhoCorasick::new(&needles)
+        });
+        let literal_only =
+            literals.is_some() && literal_leaves.len() == patterns.len() && expr.is_term_list();
+
+        let leaves = patterns
+            .into_iter()
+            .zip(negated)
+            .enumerate()
+            .map(|(i, (pattern, negated))| Leaf {
+                pattern,
+                literal: literals
+                    .as_ref()
+                    .and_then(|_| literal_leaves.iter().position(|&leaf| leaf == i)),
+                negated,
+            })
+            .collect();
+        PatternSet {
+            leaves,
+            expr,
+            literals,
+            folded,
+            literal_leaves,
+            literal_only,
+        }
+    }
+
+    /// 커맨드라인 항목들로 집합을 만듭니다. `-f` 파일은 여기서 읽습니다.
+    /// 파일의 빈 줄은 모든 라인에 맞는 패턴이 되지 않도록 건너뜁니다.
+    pub fn build(
+        terms: &[ExprTerm],
+        options: &PatternOptions,
+    ) -> Result<PatternSet, Box<dyn Error>> {
+        let expr = parse_expr(terms)?;
+        let mut patterns = Vec::new();
+        let mut failure: Option<Box<dyn Error>> = None;
+        let expr = expr.map_terms(&mut |index| {
+            let sources = match &terms[index] {
+                ExprTerm::Pattern(pattern) => vec![pattern.clone()],
+                ExprTerm::File(path) => match read_pattern_file(path) {
+                    Ok(lines) => lines,
+                    Err(e) => {
+                        failure.get_or_insert(e.into());
+                        Vec::new()
+                    }
+                },
+                _ => unreachable!("parse_expr only produces pattern terms"),
+            };
+            let mut any = Vec::new();
+            for source in sources {
+                match Pattern::with_options(&source, options) {
+                    Ok(pattern) => {
+                        any.push(Expr::Term(patterns.len()));
+                        patterns.push(pattern);
+                    }
+                    Err(e) => {
+                        failure.get_or_insert(e.into());
+                    }
+                }
+            }
+            match &terms[index] {
+                ExprTerm::Pattern(_) if any.len() == 1 => any.pop().unwrap(),
+                _ => Expr::Any(any),
+            }
+        });
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(PatternSet::new(patterns, expr)),
+        }
+    }
+
+    /// 집합 안의 패턴 수
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// 식이 패턴 하나뿐이면 그 패턴
+    pub fn as_single(&self) -> Option<&Pattern> {
+        match (&self.expr, self.leaves.as_slice()) {
+            (Expr::Term(0), [leaf]) => Some(&leaf.pattern),
+            _ => None,
+        }
+    }
+
+    /// 오토마톤으로 찾는 리터럴 패턴 수. 나머지 패턴은 정규식 엔진으로 라인마다 확인합니다.
+    pub fn literal_patterns(&self) -> usize {
+        self.literals.as_ref().map_or(0, AhoCorasick::patterns_len)
+    }
+
+    /// 선택되는 라인이라면 반드시 그중 하나를 포함하는 리터럴들.
+    /// 패턴 하나가 리터럴이거나 식이 리터럴들의 `--or` 뿐일 때만 알 수 있습니다 (트라이그램 색인용).
+    /// 대소문자를 무시하면 바이트가 그대로 들어 있지 않을 수 있으므로 알 수 없습니다.
+    pub fn required_literals(&self) -> Option<Vec<&[u8]>> {
+        if let Some(pattern) = self.as_single() {
+            return pattern.literal_finder().map(|finder| vec![finder.needle()]);
+        }
+        (self.literal_only && !self.folded).then(|| {
+            self.leaves
+                .iter()
+                .map(|leaf| leaf.pattern.literal_text().unwrap().as_bytes())
+                .collect()
+        })
+    }
+
+    /// 오토마톤으로 찾은 리터럴 매치들. 원래 라인 기준 구간이고, `-w` 패턴은 앞뒤가 단어 경계인
+    /// 매치만 남깁니다.
+    fn literal_matches(&self, line: &[u8]) -> Vec<PatternMatch> {
+        let Some(literals) = &self.literals else {
+            return Vec::new();
+        };
+        let bounded = |found: &PatternMatch| {
+            let leaf = &self.leaves[self.literal_leaves[found.pattern]];
+            !leaf.pattern.is_word() || pattern::is_word_bounded(line, &found.range)
+        };
+        if !self.folded {
+            return literals
+                .find_overlapping_iter(line)
+                .filter(bounded)
+                .collect();
+        }
+        let (folded, offsets) = pattern::fold_line(line);
+        literals
+            .find_overlapping_iter(&folded)
+            .map(|found| PatternMatch {
+                pattern: found.pattern,
+                range: offsets[found.range.start]..offsets[found.range.end],
+            })
+            .filter(bounded)
+            .collect()
+    }
+
+    /// 오토마톤에 들어간 리터럴 패턴마다 라인에 있는지 표시합니다.
+    fn literals_present(&self, line: &[u8]) -> Vec<bool> {
+        let mut present = vec![false; self.literal_leaves.len()];
+        for found in self.literal_matches(line) {
+            present[found.pattern] = true;
+        }
+        present
+    }
+
+    /// 오토마톤이 찾은 위치가 곧 매치라서 바이트를 그대로 찾아도 되는지
+    fn plain_literals(&self) -> bool {
+        !self.folded && self.leaves.iter().all(|leaf| !leaf.pattern.is_word())
+    }
+}
+
+fn read_pattern_file(path: &str) -> io::Result<Vec<String>> {
+    let bytes = fs::read(path).map_err(|e| io::Error::new(e.kind(), format!("{path}: {e}")))?;
+    Ok(String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+impl Matcher for PatternSet {
+    fn is_match_line(&self, line: &[u8]) -> bool {
+        if let Some(pattern) = self.as_single() {
+            return pattern.is_match_line(line);
+        }
+        if let (true, Some(literals)) = (self.literal_only, &self.literals) {
+            return match self.plain_literals() {
+                true => literals.find_at(line, 0).is_some(),
+                false => !self.literal_matches(line).is_empty(),
+            };
+        }
+        // 리터럴 패턴이 처음 필요해질 때 오토마톤으로 한 번에 확인합니다.
+        let mut present = None;
+        self.expr.eval(&mut |index| {
+            let leaf = &self.leaves[index];
+            match leaf.literal {
+                Some(id) => present.get_or_insert_with(|| self.literals_present(line))[id],
+                None => leaf.pattern.is_match_line(line),
+            }
+        })
+    }
+
+    fn find_spans(&self, line: &[u8]) -> Vec<Range<usize>> {
+        if let Some(pattern) = self.as_single() {
+            return pattern.find_spans(line);
+        }
+        let mut spans: Vec<Range<usize>> = self
+            .literal_matches(line)
+            .into_iter()
+            .filter(|found| !self.leaves[self.literal_leaves[found.pattern]].negated)
+            .map(|found| found.range)
+            .collect();
+        for leaf in &self.leaves {
+            if leaf.literal.is_none() && !leaf.negated {
+                spans.extend(leaf.pattern.find_iter(line));
+            }
+        }
+        // 겹치는 구간은 먼저 시작한 쪽(같으면 더 긴 쪽)만 남깁니다.
+        spans.sort_by_key(|span| (span.start, Reverse(span.end)));
+        let mut end = 0;
+        spans.retain(|span| {
+            let keep = !span.is_empty() && span.start >= end;
+            if keep {
+                end = span.end;
+            }
+            keep
+        });
+        spans
+    }
+
+    fn buffer_finder(&self) -> Option<&dyn FindAt> {
+        if let Some(pattern) = self.as_single() {
+            return pattern.buffer_finder();
+        }
+        let literals = self
+            .literals
+            .as_ref()
+            .filter(|_| self.literal_only && self.plain_literals())?;
+        self.leaves
+            .iter()
+            .all(|leaf| fits_in_line(leaf.pattern.literal_text().unwrap().as_bytes()))
+            .then_some(literals as &dyn FindAt)
+    }
+}
@@ -0,0 +1,147 @@
+//! 여러 줄에 걸친 매칭 (`-U`)
+//!
+//! 라인 단위 검색은 줄바꿈을 넘어 매칭할 수 없으므로 입력 전체를 메모리에 읽은 뒤
+//! 패턴을 한 번에 실행합니다. `-l` 처럼 찾을 줄 수가 정해져 있으면 읽는 도중에도
+//! 찾아봅니다. 매치가 걸친 줄 전체를 [`LineMatch`] 하나로 만들고, 같은 줄에 걸친
+//! 매치들은 하나로 합칩니다.
+
+use std::io::{self, BufRead};
+use std::ops::Range;
+
+use crate::stream::{StreamItem, StreamSummary};
+use crate::{LineMatch, Pattern};
+
+/// `haystack` 에서 여러 줄에 걸칠 수 있는 매치를 찾습니다.
+/// 결과의 `line_number` 와 `end_line_number` 는 매치가 걸친 첫 줄과 마지막 줄입니다.
+pub fn find_matches(pattern: &Pattern, haystack: &[u8]) -> Vec<LineMatch> {
+    matches_until(pattern, haystack, haystack.len())
+}
+
+/// [`find_matches`] 와 같지만 `end` 를 넘어 끝나는 매치가 나오면 그 앞까지만 찾습니다.
+fn matches_until(pattern: &Pattern, haystack: &[u8], end: usize) -> Vec<LineMatch> {
+    let mut results = Vec::new();
+    // 지금 모으는 결과: 줄들의 바이트 범위, 첫 줄 번호, 매치 구간들
+    let mut current: Option<(Range<usize>, usize, Vec<Range<usize>>)> = None;
+    let mut line_number = 1;
+    let mut counted = 0;
+    for found in pattern.find_iter(haystack) {
+        if found.end > end {
+            break;
+        }
+        if found.is_empty() {
+            continue;
+        }
+        let start = haystack[..found.start]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        // 줄바꿈으로 끝나는 매치는 그 줄바꿈이 있는 줄에서 끝납니다.
+        let end = haystack[found.end - 1..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(haystack.len(), |i| found.end - 1 + i);
+        match &mut current {
+            Some((lines, _, spans)) if start <= lines.end => {
+                lines.end = lines.end.max(end);
+                spans.push(found);
+            }
+            _ => {
+                results.extend(current.take().map(|c| to_line_match(haystack, c)));
+                line_number += haystack[counted..start]
+                    .iter()
+                    .filter(|&&b| b == b'\n')
+                    .count();
+                counted = start;
+                current = Some((start..end, line_number, vec![found]));
+            }
+        }
+    }
+    results.extend(current.map(|c| to_line_match(haystack, c)));
+    results
+}
+
+fn to_line_match(
+    haystack: &[u8],
+    (lines, line_number, spans): (Range<usize>, usize, Vec<Range<usize>>),
+) -> LineMatch {
+    let text = &haystack[lines.clone()];
+    // 걸친 줄마다 `stream::trim_line_end` 처럼 줄 끝의 `\r` 을 빼고, 뺀 만큼 매치 구간을 당깁니다.
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut removed = Vec::new();
+    for (i, &b) in text.iter().enumerate() {
+        if b == b'\r' && text.get(i + 1).is_none_or(|&next| next == b'\n') {
+            removed.push(i);
+        } else {
+            bytes.push(b);
+        }
+    }
+    let shift = |offset: usize| offset - removed.partition_point(|&i| i < offset);
+    let spans = spans.into_iter().map(|span| {
+        let end = (span.end - lines.start).min(text.len());
+        shift(span.start - lines.start)..shift(end)
+    });
+    LineMatch::with_spans(line_number, &bytes, spans)
+}
+
+/// `reader` 를 끝까지 읽어 여러 줄 매치마다 `emit` 을 호출합니다.
+/// 요약의 `matched_lines` 는 매치가 걸친 줄 수의 합입니다.
+///
+/// `max_count` 가 있으면 그만큼의 줄을 찾은 뒤 멈춥니다. 읽는 도중에도 뒤에 무엇이 오든
+/// 바뀌지 않는 매치로 그만큼 찾았으면 나머지 입력은 읽지 않으므로, 끝나지 않는 입력에서도 끝납니다.
+pub fn search_reader<R, F>(
+    pattern: &Pattern,
+    mut reader: R,
+    max_count: Option<u64>,
+    mut emit: F,
+) -> io::Result<StreamSummary>
+where
+    R: BufRead,
+    F: FnMut(StreamItem) -> io::Result<()>,
+{
+    let mut haystack = Vec::new();
+    let mut searched = 0;
+    let found = loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            break find_matches(pattern, &haystack);
+        }
+        haystack.extend_from_slice(available);
+        let len = available.len();
+        reader.consume(len);
+        let Some(max) = max_count else {
+            continue;
+        };
+        // 읽은 양이 지난번의 두 배가 될 때마다 찾아봐서 다시 찾는 비용을 입력 길이에 비례하게 둡니다.
+        if haystack.len() < searched * 2 {
+            continue;
+        }
+        searched = haystack.len();
+        // 마지막 줄바꿈 앞에서 끝나는 매치는 뒤에 더 읽을 내용과 상관없이 매치이고,
+        // 매치가 걸친 줄도 모두 읽은 상태입니다.
+        let Some(settled) = haystack.iter().rposition(|&b| b == b'\n') else {
+            continue;
+        };
+        let found = matches_until(pattern, &haystack, settled);
+        if found.iter().map(line_count).sum::<u64>() >= max {
+            break found;
+        }
+    };
+    let mut summary = StreamSummary {
+        bytes_read: haystack.len() as u64,
+        ..StreamSummary::default()
+    };
+    for found in found {
+        if max_count.is_some_and(|max| summary.matched_lines >= max) {
+            break;
+        }
+        summary.matched_lines += line_count(&found);
+        summary.matches += found.matches.len() as u64;
+        emit(StreamItem::Line(found))?;
+    }
+    Ok(summary)
+}
+
+/// 매치가 걸친 줄 수
+fn line_count(found: &LineMatch) -> u64 {
+    (found.end_line_number - found.line_number + 1) as u64
+}
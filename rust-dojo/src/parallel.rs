@@ -0,0 +1,69 @@
+//! 병렬 검색
+//!
+//! 작업(파일)을 스레드 풀에 나눠 처리하면서도 결과는 입력 순서대로 넘겨줍니다.
+//! 워커는 공유 카운터에서 다음 작업 번호를 가져가고, 메인 스레드는
+//! 아직 차례가 되지 않은 결과를 잠시 보관했다가 순서가 맞을 때 내보냅니다.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// `--threads` 를 지정하지 않았을 때 사용할 스레드 수
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// `items` 의 각 항목에 `work` 를 `threads` 개의 스레드로 적용하고,
+/// 결과를 입력 순서 그대로 `sink` 에 전달합니다.
+///
+/// `sink` 가 에러를 반환하면 남은 작업을 멈추고 그 에러를 반환합니다.
+pub fn ordered_map<T, R, F, S>(items: &[T], threads: usize, work: F, mut sink: S) -> io::Result<()>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    S: FnMut(R) -> io::Result<()>,
+{
+    let threads = threads.clamp(1, items.len().max(1));
+    if threads == 1 {
+        return items.iter().try_for_each(|item| sink(work(item)));
+    }
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..threads {
+            let tx = tx.clone();
+            let (next, stop, work) = (&next, &stop, &work);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    if tx.send((index, work(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut wanted = 0;
+        for (index, result) in rx {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&wanted) {
+                wanted += 1;
+                if let Err(e) = sink(result) {
+                    stop.store(true, Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,1284 @@
+//! 자체 구현 정규 표현식 엔진
+//!
+//! 외부 크레이트 없이 `--regex` 모드를 지원하기 위한 작은 정규식 엔진입니다.
+//! 패턴을 구문 트리로 파싱한 뒤 명령어 프로그램으로 컴파일하고,
+//! Pike VM 으로 실행하므로 입력 길이에 대해 선형 시간에 동작합니다.
+//!
+//! 지원 문법:
+//! - 리터럴, `.`, 이스케이프 (`\.`, `\n`, `\t`, ...)
+//! - 문자 클래스 `[a-z]`, `[^0-9]`, `\d \w \s \D \W \S` (`\w` 와 `\b` 는 유니코드 기준)
+//! - 앵커 `^ $`, 단어 경계 `\b \B`
+//! - 대안 `|`, 그룹 `( )`, `(?: )`, `(?P<name> )`
+//! - 반복 `* + ? {n} {n,} {n,m}` 과 게으른(lazy) 버전 `*? +? ??`
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+use crate::literal::Finder;
+use crate::unicode;
+
+/// 반복 횟수 상한. `a{100000}` 같은 패턴이 프로그램을 폭발시키지 않도록 막습니다.
+const MAX_REPEAT: u32 = 1000;
+
+/// 컴파일된 프로그램의 명령어 수 상한. 반복을 중첩해 프로그램이 곱절로 커지는
+/// 패턴(`((a?){1000}){200}`)을 막습니다.
+const MAX_PROGRAM: usize = 100_000;
+
+/// 그룹과 반복의 중첩 깊이 상한. 파서와 컴파일러가 재귀하므로 스택을 넘지 않게 합니다.
+const MAX_NESTING: usize = 200;
+
+// =============================================================================
+// 에러 타입
+// =============================================================================
+
+/// 패턴 파싱 중 발생하는 에러
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternError {
+    /// 닫히지 않은 그룹 `(`
+    UnclosedGroup(usize),
+    /// 짝이 없는 `)`
+    UnopenedGroup(usize),
+    /// 닫히지 않은 문자 클래스 `[`
+    UnclosedClass(usize),
+    /// 잘못된 문자 범위 (`[z-a]`)
+    InvalidRange(usize),
+    /// 반복할 대상이 없는 반복 연산자 (`*a`)
+    NothingToRepeat(usize),
+    /// 잘못된 반복 횟수 (`{3,1}` 또는 상한 초과)
+    InvalidRepeat(usize),
+    /// 지원하지 않거나 끝나버린 이스케이프
+    InvalidEscape(usize),
+    /// 지원하지 않는 그룹 문법 (`(?=...)` 등)
+    UnsupportedGroup(usize),
+    /// 그룹이나 반복이 너무 깊게 중첩되었습니다.
+    NestingTooDeep(usize),
+    /// 컴파일된 프로그램이 [`MAX_PROGRAM`] 을 넘습니다.
+    TooLarge,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::UnclosedGroup(pos) => write!(f, "unclosed group at offset {pos}"),
+            PatternError::UnopenedGroup(pos) => write!(f, "unopened group at offset {pos}"),
+            PatternError::UnclosedClass(pos) => {
+                write!(f, "unclosed character class at offset {pos}")
+            }
+            PatternError::InvalidRange(pos) => {
+                write!(f, "invalid character range at offset {pos}")
+            }
+            PatternError::NothingToRepeat(pos) => write!(f, "nothing to repeat at offset {pos}"),
+            PatternError::InvalidRepeat(pos) => {
+                write!(f, "invalid repetition count at offset {pos}")
+            }
+            PatternError::InvalidEscape(pos) => write!(f, "invalid escape at offset {pos}"),
+            PatternError::UnsupportedGroup(pos) => {
+                write!(f, "unsupported group syntax at offset {pos}")
+            }
+            PatternError::NestingTooDeep(pos) => write!(f, "nesting too deep at offset {pos}"),
+            PatternError::TooLarge => write!(
+                f,
+                "pattern compiles to more than {MAX_PROGRAM} instructions"
+            ),
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+// =============================================================================
+// 공개 API
+// =============================================================================
+
+/// 패턴을 만들 때 사용하는 옵션
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PatternOptions {
+    /// true 면 정규식으로, false 면 리터럴 문자열로 해석합니다.
+    pub regex: bool,
+    /// 대소문자를 무시하고 매칭합니다.
+    pub case_insensitive: bool,
+    /// 단어 단위로만 매칭합니다 (`grep -w`).
+    /// 매치 앞뒤가 줄의 끝이거나 단어 문자가 아니어야 합니다.
+    pub word: bool,
+    /// 여러 줄에 걸친 텍스트를 검색합니다 (`-U`).
+    /// `^` 와 `$` 가 텍스트 전체가 아니라 각 줄의 시작과 끝에 매칭되고, `\A` 와 `\z` 는 그대로
+    /// 텍스트의 시작과 끝입니다. `.` 은 여전히 줄바꿈에 매칭되지 않으므로 `\n` 이나 `\s` 를 씁니다.
+    pub multiline: bool,
+}
+
+/// 검색에 사용되는 컴파일된 패턴
+///
+/// 리터럴 검색과 정규식 검색을 하나의 타입으로 감싸서
+/// `run` 이 원본 `query` 문자열 대신 사용할 수 있게 합니다.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    program: Program,
+    /// 메타 문자가 없는 패턴이면 VM 대신 바이트 검색을 사용합니다.
+    literal: Option<Finder>,
+    /// 메타 문자가 없는 패턴의 텍스트 (`-i`, `-w` 와 무관)
+    text: Option<String>,
+    /// `-w` 로 만든 패턴인지
+    word: bool,
+}
+
+/// 한 번의 매칭에서 얻은 캡처 그룹들
+#[derive(Debug, Clone, PartialEq)]
+pub struct Captures {
+    groups: Vec<Option<Range<usize>>>,
+    names: Vec<Option<String>>,
+}
+
+impl Captures {
+    /// `index` 번째 그룹의 바이트 범위 (0 번은 전체 매치)
+    pub fn get(&self, index: usize) -> Option<Range<usize>> {
+        self.groups.get(index).cloned().flatten()
+    }
+
+    /// 이름 있는 그룹의 바이트 범위
+    pub fn name(&self, name: &str) -> Option<Range<usize>> {
+        let index = self.names.iter().position(|n| n.as_deref() == Some(name))?;
+        self.get(index)
+    }
+
+    /// 그룹 개수 (전체 매치 포함)
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+impl Pattern {
+    /// 정규식 패턴을 컴파일합니다.
+    pub fn new(pattern: &str) -> Result<Pattern, PatternError> {
+        Pattern::with_options(
+            pattern,
+            &PatternOptions {
+                regex: true,
+                ..PatternOptions::default()
+            },
+        )
+    }
+
+    /// 메타 문자를 해석하지 않는 리터럴 패턴을 만듭니다.
+    pub fn literal(text: &str) -> Pattern {
+        Pattern::with_options(text, &PatternOptions::default())
+            .expect("literal patterns always compile")
+    }
+
+    /// 옵션을 지정해 패턴을 만듭니다.
+    pub fn with_options(pattern: &str, options: &PatternOptions) -> Result<Pattern, PatternError> {
+        let (node, names) = if options.regex {
+            let mut parser = Parser::new(pattern);
+            parser.line_anchors = options.multiline;
+            parser.parse()?
+        } else {
+            (
+                Node::Concat(pattern.chars().map(Node::Literal).collect()),
+                vec![None],
+            )
+        };
+        let text = literal_text(&node);
+        let node = if options.word {
+            Node::Concat(vec![
+                Node::Assert(Assertion::NotWordBefore),
+                Node::Group(Box::new(node), None),
+                Node::Assert(Assertion::NotWordAfter),
+            ])
+        } else {
+            node
+        };
+        // 리터럴은 길이에 비례하는 프로그램이 되므로 정규식만 크기를 제한합니다.
+        if options.regex && program_size(&node) > MAX_PROGRAM {
+            return Err(PatternError::TooLarge);
+        }
+        let literal = match (options.case_insensitive, options.word) {
+            (false, false) => text.as_ref().map(|text| Finder::new(text.as_bytes())),
+            _ => None,
+        };
+        let program = Compiler::compile(&node, names, options.case_insensitive);
+        Ok(Pattern {
+            source: pattern.to_string(),
+            program,
+            literal,
+            text,
+            word: options.word,
+        })
+    }
+
+    /// 패턴 원문
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// `haystack` 어딘가에 매칭되는지 확인합니다.
+    pub fn is_match(&self, haystack: &str) -> bool {
+        self.find(haystack).is_some()
+    }
+
+    /// 가장 왼쪽 매치의 바이트 범위를 반환합니다.
+    pub fn find(&self, haystack: &str) -> Option<Range<usize>> {
+        self.find_at(haystack.as_bytes(), 0)
+    }
+
+    /// `start` 바이트 위치부터 검색합니다. 입력은 UTF-8 이 아니어도 됩니다.
+    pub fn find_at(&self, haystack: &[u8], start: usize) -> Option<Range<usize>> {
+        if let Some(finder) = &self.literal {
+            let len = finder.needle().len();
+            return finder.find_at(haystack, start).map(|at| at..at + len);
+        }
+        let mut slots = vec![None; 2];
+        if self.program.exec(haystack, start, &mut slots) {
+            Some(slots[0]?..slots[1]?)
+        } else {
+            None
+        }
+    }
+
+    /// 겹치지 않는 모든 매치를 순서대로 반환합니다.
+    pub fn find_iter<'p, 'h>(&'p self, haystack: &'h [u8]) -> FindIter<'p, 'h> {
+        FindIter {
+            pattern: self,
+            haystack,
+            pos: 0,
+            last_end: None,
+        }
+    }
+
+    /// 가장 왼쪽 매치의 캡처 그룹을 반환합니다.
+    pub fn captures(&self, haystack: &str) -> Option<Captures> {
+        self.captures_at(haystack.as_bytes(), 0)
+    }
+
+    /// `start` 바이트 위치부터 캡처 그룹을 찾습니다.
+    pub fn captures_at(&self, haystack: &[u8], start: usize) -> Option<Captures> {
+        let mut slots = vec![None; self.program.slot_count];
+        if !self.program.exec(haystack, start, &mut slots) {
+            return None;
+        }
+        let groups = slots
+            .chunks(2)
+            .map(|pair| match (pair[0], pair[1]) {
+                (Some(s), Some(e)) => Some(s..e),
+                _ => None,
+            })
+            .collect();
+        Some(Captures {
+            groups,
+            names: self.program.names.clone(),
+        })
+    }
+
+    /// 패턴이 단순 리터럴이면 그 검색기. 대소문자 무시나 `-w` 패턴은 `None` 입니다.
+    pub fn literal_finder(&self) -> Option<&Finder> {
+        self.literal.as_ref()
+    }
+
+    /// 메타 문자가 없는 패턴의 텍스트. [`Pattern::literal_finder`] 와 달리
+    /// 대소문자 무시나 `-w` 패턴도 텍스트를 돌려주므로 호출하는 쪽이 두 조건을 따로 확인합니다.
+    pub fn literal_text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// 대소문자를 무시하는 패턴인지
+    pub fn is_case_insensitive(&self) -> bool {
+        self.program.case_insensitive
+    }
+
+    /// `-w` 로 만든 패턴인지
+    pub fn is_word(&self) -> bool {
+        self.word
+    }
+
+    /// 캡처 그룹 개수 (전체 매치 포함)
+    pub fn captures_len(&self) -> usize {
+        self.program.slot_count / 2
+    }
+
+    /// 이름 있는 그룹의 번호
+    pub fn group_index(&self, name: &str) -> Option<usize> {
+        self.program
+            .names
+            .iter()
+            .position(|n| n.as_deref() == Some(name))
+    }
+}
+
+/// [`Pattern::find_iter`] 가 반환하는 반복자
+pub struct FindIter<'p, 'h> {
+    pattern: &'p Pattern,
+    haystack: &'h [u8],
+    pos: usize,
+    last_end: Option<usize>,
+}
+
+impl Iterator for FindIter<'_, '_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        loop {
+            if self.pos > self.haystack.len() {
+                return None;
+            }
+            let found = self.pattern.find_at(self.haystack, self.pos)?;
+            if found.is_empty() && Some(found.end) == self.last_end {
+                // 직전 매치 바로 뒤의 빈 매치는 건너뛰고 한 글자 전진합니다.
+                self.pos = found.end + utf8_len_at(self.haystack, found.end).max(1);
+                continue;
+            }
+            self.pos = if found.is_empty() {
+                found.end + utf8_len_at(self.haystack, found.end).max(1)
+            } else {
+                found.end
+            };
+            self.last_end = Some(found.end);
+            return Some(found);
+        }
+    }
+}
+
+// =============================================================================
+// 구문 트리와 파서
+// =============================================================================
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Literal(char),
+    Any,
+    Class(Class),
+    Assert(Assertion),
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Assertion {
+    StartText,
+    EndText,
+    /// 텍스트 시작이나 줄바꿈 뒤 (`multiline` 의 `^`)
+    StartLine,
+    /// 텍스트 끝이나 줄바꿈 앞 (`multiline` 의 `$`)
+    EndLine,
+    WordBoundary,
+    NotWordBoundary,
+    /// 앞 글자가 단어 문자가 아님 (`-w` 전용)
+    NotWordBefore,
+    /// 뒤 글자가 단어 문자가 아님 (`-w` 전용)
+    NotWordAfter,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Class {
+    ranges: Vec<(char, char)>,
+    /// 범위 외에 유니코드 단어 문자(`\w`)도 포함합니다.
+    word: bool,
+    negated: bool,
+}
+
+impl Class {
+    fn new(ranges: Vec<(char, char)>, negated: bool) -> Class {
+        Class {
+            ranges,
+            word: false,
+            negated,
+        }
+    }
+
+    fn word(negated: bool) -> Class {
+        Class {
+            ranges: Vec::new(),
+            word: true,
+            negated,
+        }
+    }
+
+    fn digit() -> Vec<(char, char)> {
+        vec![('0', '9')]
+    }
+
+    fn space() -> Vec<(char, char)> {
+        vec![('\t', '\r'), (' ', ' ')]
+    }
+
+    fn contains(&self, c: char) -> bool {
+        self.includes(c) != self.negated
+    }
+
+    /// 대소문자를 무시하고 확인합니다. `c` 와 같은 문자 중 하나라도 범위에 들면 포함입니다.
+    fn contains_any_case(&self, c: char) -> bool {
+        unicode::case_variants(c).any(|v| self.includes(v)) != self.negated
+    }
+
+    fn includes(&self, c: char) -> bool {
+        (self.word && unicode::is_word_char(c))
+            || self.ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi)
+    }
+}
+
+struct Parser<'a> {
+    chars: Vec<(usize, char)>,
+    pos: usize,
+    source: &'a str,
+    names: Vec<Option<String>>,
+    /// `^`, `$` 를 줄 단위 앵커로 해석합니다.
+    line_anchors: bool,
+    /// 지금 위치를 감싼 그룹 수
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Parser<'a> {
+        Parser {
+            chars: source.char_indices().collect(),
+            pos: 0,
+            source,
+            names: vec![None],
+            line_anchors: false,
+            depth: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<(Node, Vec<Option<String>>), PatternError> {
+        let node = self.parse_alternation()?;
+        if self.pos < self.chars.len() {
+            return Err(PatternError::UnopenedGroup(self.offset()));
+        }
+        Ok((node, self.names))
+    }
+
+    fn offset(&self) -> usize {
+        self.chars
+            .get(self.pos)
+            .map(|&(i, _)| i)
+            .unwrap_or(self.source.len())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).map(|&(_, c)| c)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, PatternError> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.eat('|') {
+            branches.push(self.parse_concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.pop().unwrap()
+        } else {
+            Node::Alternate(branches)
+        })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, PatternError> {
+        let mut items = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            let atom = self.parse_repeat(atom)?;
+            items.push(atom);
+        }
+        Ok(match items.len() {
+            0 => Node::Empty,
+            1 => items.pop().unwrap(),
+            _ => Node::Concat(items),
+        })
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, PatternError> {
+        let start = self.offset();
+        let c = self.bump().expect("parse_atom called at end of input");
+        match c {
+            '.' => Ok(Node::Any),
+            '^' if self.line_anchors => Ok(Node::Assert(Assertion::StartLine)),
+            '$' if self.line_anchors => Ok(Node::Assert(Assertion::EndLine)),
+            '^' => Ok(Node::Assert(Assertion::StartText)),
+            '$' => Ok(Node::Assert(Assertion::EndText)),
+            '[' => self.parse_class(start),
+            '(' => self.parse_group(start),
+            '\\' => self.parse_escape(start),
+            '*' | '+' | '?' => Err(PatternError::NothingToRepeat(start)),
+            '{' if self.looks_like_repeat() => Err(PatternError::NothingToRepeat(start)),
+            c => Ok(Node::Literal(c)),
+        }
+    }
+
+    fn parse_group(&mut self, start: usize) -> Result<Node, PatternError> {
+        if self.depth >= MAX_NESTING {
+            return Err(PatternError::NestingTooDeep(start));
+        }
+        let capture = if self.eat('?') {
+            if self.eat(':') {
+                None
+            } else if self.eat('P') || self.peek() == Some('<') {
+                if !self.eat('<') {
+                    return Err(PatternError::UnsupportedGroup(start));
+                }
+                let mut name = String::new();
+                loop {
+                    match self.bump() {
+                        Some('>') => break,
+                        Some(c) if c.is_alphanumeric() || c == '_' => name.push(c),
+                        _ => return Err(PatternError::UnsupportedGroup(start)),
+                    }
+                }
+                self.names.push(Some(name));
+                Some(self.names.len() - 1)
+            } else {
+                return Err(PatternError::UnsupportedGroup(start));
+            }
+        } else {
+            self.names.push(None);
+            Some(self.names.len() - 1)
+        };
+        self.depth += 1;
+        let inner = self.parse_alternation()?;
+        self.depth -= 1;
+        if !self.eat(')') {
+            return Err(PatternError::UnclosedGroup(start));
+        }
+        Ok(Node::Group(Box::new(inner), capture))
+    }
+
+    fn parse_escape(&mut self, start: usize) -> Result<Node, PatternError> {
+        let c = self.bump().ok_or(PatternError::InvalidEscape(start))?;
+        Ok(match c {
+            'd' => Node::Class(Class::new(Class::digit(), false)),
+            'D' => Node::Class(Class::new(Class::digit(), true)),
+            's' => Node::Class(Class::new(Class::space(), false)),
+            'S' => Node::Class(Class::new(Class::space(), true)),
+            'w' => Node::Class(Class::word(false)),
+            'W' => Node::Class(Class::word(true)),
+            'b' => Node::Assert(Assertion::WordBoundary),
+            'B' => Node::Assert(Assertion::NotWordBoundary),
+            'A' => Node::Assert(Assertion::StartText),
+            'z' => Node::Assert(Assertion::EndText),
+            _ => Node::Literal(self.escaped_char(c, start)?),
+        })
+    }
+
+    /// 클래스 안팎에서 공통으로 쓰이는 단일 문자 이스케이프
+    fn escaped_char(&self, c: char, start: usize) -> Result<char, PatternError> {
+        match c {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            'f' => Ok('\x0c'),
+            'v' => Ok('\x0b'),
+            '0' => Ok('\0'),
+            c if c.is_ascii_alphanumeric() => Err(PatternError::InvalidEscape(start)),
+            c => Ok(c),
+        }
+    }
+
+    fn parse_class(&mut self, start: usize) -> Result<Node, PatternError> {
+        let negated = self.eat('^');
+        let mut ranges = Vec::new();
+        let mut word = false;
+        let mut first = true;
+        loop {
+            let item_start = self.offset();
+            let c = self.bump().ok_or(PatternError::UnclosedClass(start))?;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            let lo = if c == '\\' {
+                let e = self.bump().ok_or(PatternError::UnclosedClass(start))?;
+                match e {
+                    'd' => {
+                        ranges.extend(Class::digit());
+                        continue;
+                    }
+                    's' => {
+                        ranges.extend(Class::space());
+                        continue;
+                    }
+                    'w' => {
+                        word = true;
+                        continue;
+                    }
+                    e => self.escaped_char(e, item_start)?,
+                }
+            } else {
+                c
+            };
+            // `-` 뒤에 `]` 가 오면 리터럴 `-` 로 취급합니다.
+            let is_range = self.peek() == Some('-')
+                && self.chars.get(self.pos + 1).map(|&(_, c)| c) != Some(']')
+                && self.chars.get(self.pos + 1).is_some();
+            if is_range {
+                self.pos += 1;
+                let hi_start = self.offset();
+                let hi = match self.bump().ok_or(PatternError::UnclosedClass(start))? {
+                    '\\' => {
+                        let e = self.bump().ok_or(PatternError::UnclosedClass(start))?;
+                        self.escaped_char(e, hi_start)?
+                    }
+                    c => c,
+                };
+                if hi < lo {
+                    return Err(PatternError::InvalidRange(item_start));
+                }
+                ranges.push((lo, hi));
+            } else {
+                ranges.push((lo, lo));
+            }
+        }
+        Ok(Node::Class(Class {
+            ranges,
+            word,
+            negated,
+        }))
+    }
+
+    fn looks_like_repeat(&self) -> bool {
+        // `{` 다음이 숫자로 시작하고 `}` 로 닫힐 때만 반복으로 봅니다.
+        let rest: String = self.chars[self.pos..].iter().map(|&(_, c)| c).collect();
+        let close = match rest.find('}') {
+            Some(i) => i,
+            None => return false,
+        };
+        let body = &rest[..close];
+        !body.is_empty()
+            && body.chars().next().is_some_and(|c| c.is_ascii_digit())
+            && body.chars().all(|c| c.is_ascii_digit() || c == ',')
+            && body.matches(',').count() <= 1
+    }
+
+    fn parse_repeat(&mut self, mut atom: Node) -> Result<Node, PatternError> {
+        // `a****` 처럼 이어진 반복도 한 단계씩 중첩됩니다.
+        let mut depth = self.depth;
+        loop {
+            let start = self.offset();
+            let (min, max) = match self.peek() {
+                Some('*') => {
+                    self.pos += 1;
+                    (0, None)
+                }
+                Some('+') => {
+                    self.pos += 1;
+                    (1, None)
+                }
+                Some('?') => {
+                    self.pos += 1;
+                    (0, Some(1))
+                }
+                Some('{') => {
+                    self.pos += 1;
+                    if !self.looks_like_repeat() {
+                        self.pos -= 1;
+                        return Ok(atom);
+                    }
+                    self.parse_counted(start)?
+                }
+                _ => return Ok(atom),
+            };
+            if matches!(atom, Node::Assert(_) | Node::Empty) {
+                return Err(PatternError::NothingToRepeat(start));
+            }
+            depth += 1;
+            if depth > MAX_NESTING {
+                return Err(PatternError::NestingTooDeep(start));
+            }
+            let greedy = !self.eat('?');
+            atom = Node::Repeat {
+                node: Box::new(atom),
+                min,
+                max,
+                greedy,
+            };
+        }
+    }
+
+    /// `{` 바로 다음부터 `}` 까지 읽어 반복 횟수를 파싱합니다.
+    fn parse_counted(&mut self, start: usize) -> Result<(u32, Option<u32>), PatternError> {
+        let mut body = String::new();
+        while let Some(c) = self.bump() {
+            if c == '}' {
+                break;
+            }
+            body.push(c);
+        }
+        let parse = |s: &str| {
+            s.parse::<u32>()
+                .map_err(|_| PatternError::InvalidRepeat(start))
+        };
+        let (min, max) = match body.split_once(',') {
+            None => {
+                let n = parse(&body)?;
+                (n, Some(n))
+            }
+            Some((lo, "")) => (parse(lo)?, None),
+            Some((lo, hi)) => (parse(lo)?, Some(parse(hi)?)),
+        };
+        if max.is_some_and(|m| m < min) || min > MAX_REPEAT || max.unwrap_or(0) > MAX_REPEAT {
+            return Err(PatternError::InvalidRepeat(start));
+        }
+        Ok((min, max))
+    }
+}
+
+/// 트리를 컴파일했을 때의 명령어 수. 중첩된 반복이 넘치지 않도록 포화 연산으로 셉니다.
+fn program_size(node: &Node) -> usize {
+    match node {
+        Node::Empty => 0,
+        Node::Literal(_) | Node::Any | Node::Class(_) | Node::Assert(_) => 1,
+        Node::Group(inner, capture) => {
+            program_size(inner).saturating_add(if capture.is_some() { 2 } else { 0 })
+        }
+        Node::Concat(items) => items
+            .iter()
+            .map(program_size)
+            .fold(0, usize::saturating_add),
+        Node::Alternate(branches) => branches
+            .iter()
+            .map(program_size)
+            .fold(2 * (branches.len() - 1), usize::saturating_add),
+        Node::Repeat { node, min, max, .. } => {
+            let body = program_size(node);
+            let (min, max) = (*min as usize, max.map(|max| max as usize));
+            match max {
+                // 필수 반복 min 번, 그리고 split 과 jmp 로 감싼 본문 하나
+                None => body.saturating_mul(min + 1).saturating_add(2),
+                // 본문 max 번, 그리고 선택 반복마다 split 하나
+                Some(max) => body.saturating_mul(max).saturating_add(max - min),
+            }
+        }
+    }
+}
+
+/// 트리가 순수한 리터럴 연결이면 그 문자열을 반환합니다.
+fn literal_text(node: &Node) -> Option<String> {
+    match node {
+        Node::Empty => Some(String::new()),
+        Node::Literal(c) => Some(c.to_string()),
+        Node::Concat(items) => items.iter().map(literal_text).collect(),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// 컴파일러: 구문 트리 -> Pike VM 명령어
+// =============================================================================
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Assert(Assertion),
+    /// 두 갈래로 나뉩니다. 첫 번째가 우선순위가 높습니다.
+    Split(usize, usize),
+    Jmp(usize),
+    Save(usize),
+    Match,
+}
+
+#[derive(Debug, Clone)]
+struct Program {
+    insts: Vec<Inst>,
+    slot_count: usize,
+    names: Vec<Option<String>>,
+    case_insensitive: bool,
+}
+
+struct Compiler {
+    insts: Vec<Inst>,
+}
+
+impl Compiler {
+    fn compile(node: &Node, names: Vec<Option<String>>, case_insensitive: bool) -> Program {
+        let mut c = Compiler { insts: Vec::new() };
+        c.push(Inst::Save(0));
+        c.emit(node);
+        c.push(Inst::Save(1));
+        c.push(Inst::Match);
+        Program {
+            insts: c.insts,
+            slot_count: names.len() * 2,
+            names,
+            case_insensitive,
+        }
+    }
+
+    fn push(&mut self, inst: Inst) -> usize {
+        self.insts.push(inst);
+        self.insts.len() - 1
+    }
+
+    fn patch_split(&mut self, at: usize, first: usize, second: usize) {
+        self.insts[at] = Inst::Split(first, second);
+    }
+
+    fn emit(&mut self, node: &Node) {
+        match node {
+            Node::Empty => {}
+            Node::Literal(c) => {
+                self.push(Inst::Char(*c));
+            }
+            Node::Any => {
+                self.push(Inst::Any);
+            }
+            Node::Class(class) => {
+                self.push(Inst::Class(class.clone()));
+            }
+            Node::Assert(a) => {
+                self.push(Inst::Assert(*a));
+            }
+            Node::Group(inner, capture) => match capture {
+                Some(index) => {
+                    self.push(Inst::Save(index * 2));
+                    self.emit(inner);
+                    self.push(Inst::Save(index * 2 + 1));
+                }
+                None => self.emit(inner),
+            },
+            Node::Concat(items) => {
+                for item in items {
+                    self.emit(item);
+                }
+            }
+            Node::Alternate(branches) => {
+                let mut jumps = Vec::new();
+                for (i, branch) in branches.iter().enumerate() {
+                    if i + 1 < branches.len() {
+                        let split = self.push(Inst::Split(0, 0));
+                        self.emit(branch);
+                        jumps.push(self.push(Inst::Jmp(0)));
+                        let next = self.insts.len();
+                        self.patch_split(split, split + 1, next);
+                    } else {
+                        self.emit(branch);
+                    }
+                }
+                let end = self.insts.len();
+                for j in jumps {
+                    self.insts[j] = Inst::Jmp(end);
+                }
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } => {
+                for _ in 0..*min {
+                    self.emit(node);
+                }
+                match max {
+                    None => {
+                        // L: split body, out ; body ; jmp L
+                        let split = self.push(Inst::Split(0, 0));
+                        self.emit(node);
+                        self.push(Inst::Jmp(split));
+                        let out = self.insts.len();
+                        self.split_order(split, split + 1, out, *greedy);
+                    }
+                    Some(max) => {
+                        let mut splits = Vec::new();
+                        for _ in *min..*max {
+                            splits.push(self.push(Inst::Split(0, 0)));
+                            self.emit(node);
+                        }
+                        let out = self.insts.len();
+                        for split in splits {
+                            self.split_order(split, split + 1, out, *greedy);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn split_order(&mut self, at: usize, body: usize, out: usize, greedy: bool) {
+        if greedy {
+            self.patch_split(at, body, out);
+        } else {
+            self.patch_split(at, out, body);
+        }
+    }
+}
+
+// =============================================================================
+// Pike VM
+// =============================================================================
+
+/// 중복 스레드를 막기 위한 희소 집합
+struct SparseSet {
+    dense: Vec<usize>,
+    sparse: Vec<usize>,
+}
+
+impl SparseSet {
+    fn new(capacity: usize) -> SparseSet {
+        SparseSet {
+            dense: Vec::with_capacity(capacity),
+            sparse: vec![0; capacity],
+        }
+    }
+
+    fn contains(&self, value: usize) -> bool {
+        let i = self.sparse[value];
+        i < self.dense.len() && self.dense[i] == value
+    }
+
+    fn insert(&mut self, value: usize) {
+        self.sparse[value] = self.dense.len();
+        self.dense.push(value);
+    }
+
+    fn clear(&mut self) {
+        self.dense.clear();
+    }
+}
+
+/// 한 위치에서 살아 있는 스레드들
+///
+/// 각 pc 는 한 번만 등장하므로 캡처 슬롯을 pc 별로 평평한 배열에 저장해
+/// 스레드마다 벡터를 할당하지 않습니다.
+struct Threads {
+    set: SparseSet,
+    slots_per_thread: usize,
+    caps: Vec<Option<usize>>,
+}
+
+impl Threads {
+    fn new(size: usize, slots_per_thread: usize) -> Threads {
+        Threads {
+            set: SparseSet::new(size),
+            slots_per_thread,
+            caps: vec![None; size * slots_per_thread],
+        }
+    }
+
+    fn caps(&self, pc: usize) -> &[Option<usize>] {
+        &self.caps[pc * self.slots_per_thread..(pc + 1) * self.slots_per_thread]
+    }
+
+    fn caps_mut(&mut self, pc: usize) -> &mut [Option<usize>] {
+        &mut self.caps[pc * self.slots_per_thread..(pc + 1) * self.slots_per_thread]
+    }
+}
+
+/// `add_thread` 가 재귀 대신 쓰는 스택의 항목
+enum Frame {
+    /// 이 pc 부터 epsilon 전이를 따라갑니다.
+    Explore(usize),
+    /// 캡처 슬롯을 이전 값으로 되돌립니다.
+    Restore(usize, Option<usize>),
+}
+
+/// `exec` 마다 스레드 목록을 새로 할당하지 않도록 스레드(OS)별로 재사용하는 작업 공간
+struct Cache {
+    clist: Threads,
+    nlist: Threads,
+    scratch: Vec<Option<usize>>,
+    stack: Vec<Frame>,
+}
+
+impl Cache {
+    fn reset(&mut self, size: usize, slots_per_thread: usize) {
+        if self.clist.set.sparse.len() != size || self.scratch.len() != slots_per_thread {
+            self.clist = Threads::new(size, slots_per_thread);
+            self.nlist = Threads::new(size, slots_per_thread);
+            self.scratch = vec![None; slots_per_thread];
+        } else {
+            self.clist.set.clear();
+            self.nlist.set.clear();
+        }
+    }
+}
+
+thread_local! {
+    static CACHE: RefCell<Cache> = RefCell::new(Cache {
+        clist: Threads::new(0, 0),
+        nlist: Threads::new(0, 0),
+        scratch: Vec::new(),
+        stack: Vec::new(),
+    });
+}
+
+/// 현재 위치의 앞뒤 문자. 앵커와 단어 경계 판정에 사용됩니다.
+#[derive(Clone, Copy)]
+struct Context {
+    prev: Option<char>,
+    next: Option<char>,
+    at_start: bool,
+    at_end: bool,
+}
+
+impl Program {
+    /// `start` 부터 가장 왼쪽 매치를 찾아 `slots` 에 기록합니다.
+    /// `slots` 의 길이만큼만 캡처를 추적하므로 `find` 는 2 개만 넘기면 됩니다.
+    fn exec(&self, haystack: &[u8], start: usize, slots: &mut [Option<usize>]) -> bool {
+        CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            cache.reset(self.insts.len(), slots.len());
+            self.exec_with(haystack, start, slots, &mut cache)
+        })
+    }
+
+    fn exec_with(
+        &self,
+        haystack: &[u8],
+        start: usize,
+        slots: &mut [Option<usize>],
+        cache: &mut Cache,
+    ) -> bool {
+        let Cache {
+            clist,
+            nlist,
+            scratch,
+            stack,
+        } = cache;
+        let mut matched = false;
+        let mut pos = start;
+        let mut prev = decode_last(&haystack[..start.min(haystack.len())]);
+        let (mut next, mut width) = decode(haystack, pos);
+
+        loop {
+            if !matched {
+                let ctx = Context {
+                    prev,
+                    next,
+                    at_start: pos == 0,
+                    at_end: pos >= haystack.len(),
+                };
+                scratch.fill(None);
+                self.add_thread(clist, 0, pos, scratch, ctx, stack);
+            }
+            if clist.set.dense.is_empty() && matched {
+                break;
+            }
+            let after_pos = pos + width;
+            let (after, after_width) = decode(haystack, after_pos);
+            let next_ctx = Context {
+                prev: next,
+                next: after,
+                at_start: false,
+                at_end: after_pos >= haystack.len(),
+            };
+            for i in 0..clist.set.dense.len() {
+                let pc = clist.set.dense[i];
+                match &self.insts[pc] {
+                    Inst::Match => {
+                        slots.copy_from_slice(clist.caps(pc));
+                        matched = true;
+                        // 우선순위가 낮은 나머지 스레드는 버립니다 (leftmost-first).
+                        break;
+                    }
+                    inst @ (Inst::Char(_) | Inst::Any | Inst::Class(_))
+                        if next.is_some_and(|c| self.step(inst, c)) =>
+                    {
+                        scratch.copy_from_slice(clist.caps(pc));
+                        self.add_thread(nlist, pc + 1, after_pos, scratch, next_ctx, stack);
+                    }
+                    // 매칭 실패, 또는 add_thread 에서 이미 따라간 epsilon 명령어
+                    _ => {}
+                }
+            }
+            if pos >= haystack.len() {
+                break;
+            }
+            std::mem::swap(clist, nlist);
+            nlist.set.clear();
+            prev = next;
+            pos = after_pos;
+            (next, width) = (after, after_width);
+        }
+        matched
+    }
+
+    /// 한 글자를 소비하는 명령어가 `c` 를 받아들이는지 확인합니다.
+    fn step(&self, inst: &Inst, c: char) -> bool {
+        match inst {
+            Inst::Char(expected) => {
+                *expected == c
+                    || (self.case_insensitive
+                        && unicode::simple_fold(*expected) == unicode::simple_fold(c))
+            }
+            Inst::Any => c != '\n',
+            Inst::Class(class) if self.case_insensitive => class.contains_any_case(c),
+            Inst::Class(class) => class.contains(c),
+            _ => false,
+        }
+    }
+
+    /// epsilon 전이를 따라가며 스레드를 추가합니다.
+    /// `caps` 는 호출 전후로 같은 값을 유지합니다 (Save 는 되돌려 놓습니다).
+    ///
+    /// 프로그램이 크면 재귀가 스택을 넘길 수 있으므로 `stack` 에 남은 갈래와 되돌릴 캡처를
+    /// 쌓아 두고 반복합니다. Split 은 두 번째 갈래를 쌓고 첫 번째를 먼저 따라가므로
+    /// 스레드의 우선순위는 재귀로 따라갈 때와 같습니다.
+    fn add_thread(
+        &self,
+        threads: &mut Threads,
+        pc: usize,
+        pos: usize,
+        caps: &mut [Option<usize>],
+        ctx: Context,
+        stack: &mut Vec<Frame>,
+    ) {
+        stack.push(Frame::Explore(pc));
+        while let Some(frame) = stack.pop() {
+            let mut pc = match frame {
+                Frame::Explore(pc) => pc,
+                Frame::Restore(slot, old) => {
+                    caps[slot] = old;
+                    continue;
+                }
+            };
+            while !threads.set.contains(pc) {
+                threads.set.insert(pc);
+                match &self.insts[pc] {
+                    Inst::Jmp(target) => pc = *target,
+                    Inst::Split(first, second) => {
+                        stack.push(Frame::Explore(*second));
+                        pc = *first;
+                    }
+                    Inst::Save(slot) if *slot < caps.len() => {
+                        stack.push(Frame::Restore(*slot, caps[*slot]));
+                        caps[*slot] = Some(pos);
+                        pc += 1;
+                    }
+                    Inst::Save(_) => pc += 1,
+                    Inst::Assert(assertion) if check_assertion(*assertion, ctx) => pc += 1,
+                    Inst::Assert(_) => break,
+                    _ => {
+                        threads.caps_mut(pc).copy_from_slice(caps);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_assertion(assertion: Assertion, ctx: Context) -> bool {
+    match assertion {
+        Assertion::StartText => ctx.at_start,
+        Assertion::EndText => ctx.at_end,
+        Assertion::StartLine => ctx.at_start || ctx.prev == Some('\n'),
+        Assertion::EndLine => ctx.at_end || ctx.next == Some('\n'),
+        Assertion::WordBoundary => is_word(ctx.prev) != is_word(ctx.next),
+        Assertion::NotWordBoundary => is_word(ctx.prev) == is_word(ctx.next),
+        Assertion::NotWordBefore => !is_word(ctx.prev),
+        Assertion::NotWordAfter => !is_word(ctx.next),
+    }
+}
+
+fn is_word(c: Option<char>) -> bool {
+    c.is_some_and(unicode::is_word_char)
+}
+
+/// `range` 의 앞뒤 글자가 단어 문자가 아닌지 확인합니다. `-w` 패턴의 매치 조건과 같습니다.
+pub(crate) fn is_word_bounded(haystack: &[u8], range: &Range<usize>) -> bool {
+    !is_word(decode_last(&haystack[..range.start])) && !is_word(decode(haystack, range.end).0)
+}
+
+/// 라인의 글자마다 [`unicode::simple_fold`] 를 적용한 UTF-8 바이트와,
+/// 접은 바이트 위치마다 원래 라인의 바이트 위치(끝에는 라인 길이)를 돌려줍니다.
+/// 잘못된 바이트는 VM 과 같이 바이트마다 U+FFFD 로 바꿉니다.
+pub(crate) fn fold_line(line: &[u8]) -> (Vec<u8>, Vec<usize>) {
+    let mut folded = Vec::with_capacity(line.len());
+    let mut offsets = Vec::with_capacity(line.len() + 1);
+    let mut push = |c: char, at: usize| {
+        let mut buf = [0; 4];
+        let bytes = unicode::simple_fold(c).encode_utf8(&mut buf).as_bytes();
+        folded.extend_from_slice(bytes);
+        offsets.extend(std::iter::repeat_n(at, bytes.len()));
+    };
+    let mut at = 0;
+    for chunk in line.utf8_chunks() {
+        for (i, c) in chunk.valid().char_indices() {
+            push(c, at + i);
+        }
+        at += chunk.valid().len();
+        for _ in chunk.invalid() {
+            push(char::REPLACEMENT_CHARACTER, at);
+            at += 1;
+        }
+    }
+    offsets.push(line.len());
+    (folded, offsets)
+}
+
+// =============================================================================
+// UTF-8 디코딩 헬퍼
+// =============================================================================
+
+/// `pos` 위치의 문자와 바이트 길이. 잘못된 바이트는 U+FFFD 1 바이트로 취급합니다.
+fn decode(haystack: &[u8], pos: usize) -> (Option<char>, usize) {
+    match haystack.get(pos) {
+        None => return (None, 0),
+        Some(&b) if b.is_ascii() => return (Some(b as char), 1),
+        Some(_) => {}
+    }
+    let len = utf8_len_at(haystack, pos);
+    if len == 0 {
+        return (Some(char::REPLACEMENT_CHARACTER), 1);
+    }
+    let c = std::str::from_utf8(&haystack[pos..pos + len])
+        .ok()
+        .and_then(|s| s.chars().next())
+        .unwrap_or(char::REPLACEMENT_CHARACTER);
+    (Some(c), len)
+}
+
+/// `pos` 에서 시작하는 유효한 UTF-8 문자의 길이. 유효하지 않으면 0.
+fn utf8_len_at(haystack: &[u8], pos: usize) -> usize {
+    let b = match haystack.get(pos) {
+        Some(&b) => b,
+        None => return 0,
+    };
+    let len = match b {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return 0,
+    };
+    if pos + len > haystack.len() || std::str::from_utf8(&haystack[pos..pos + len]).is_err() {
+        return 0;
+    }
+    len
+}
+
+fn decode_last(haystack: &[u8]) -> Option<char> {
+    let end = haystack.len();
+    (1..=4.min(end))
+        .map(|len| end - len)
+        .find(|&start| utf8_len_at(haystack, start) == end - start)
+        .map(|start| decode(haystack, start).0)
+        .unwrap_or_else(|| (end > 0).then_some(char::REPLACEMENT_CHARACTER))
+}
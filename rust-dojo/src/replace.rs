@@ -0,0 +1,305 @@
+//! 검색 후 치환 (`--replace`)
+//!
+//! 검색과 같은 [`Pattern`] 으로 치환하므로 `minigrep` 으로 찾은 위치와
+//! 실제로 바뀌는 위치가 항상 같습니다.
+//!
+//! 템플릿 문법:
+//! - `$1`, `${1}`: 번호 있는 캡처 그룹 (`$0` 은 매치 전체)
+//! - `$name`, `${name}`: 이름 있는 캡처 그룹
+//! - `$$`: 리터럴 `$`
+//!
+//! 기본적으로 바뀔 내용을 unified diff 로 보여주기만 하고, `--write` 를 주면
+//! 같은 디렉토리의 임시 파일에 쓴 뒤 rename 으로 원본을 원자적으로 교체합니다.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+use crate::pattern::Captures;
+use crate::Pattern;
+
+/// unified diff 에서 변경 줄 앞뒤로 보여줄 줄 수
+pub const DIFF_CONTEXT: usize = 3;
+
+/// 템플릿 파싱 에러
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    /// 패턴에 없는 그룹을 참조했습니다.
+    UnknownGroup(String),
+    /// `${` 가 닫히지 않았습니다. 값은 `$` 의 바이트 위치입니다.
+    UnclosedBrace(usize),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnknownGroup(group) => {
+                write!(f, "replacement refers to unknown group '{group}'")
+            }
+            TemplateError::UnclosedBrace(pos) => {
+                write!(f, "unclosed '${{' in replacement at {pos}")
+            }
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(Vec<u8>),
+    Group(usize),
+}
+
+/// 컴파일된 치환 템플릿
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// `pattern` 의 그룹을 기준으로 템플릿을 파싱합니다.
+    /// 없는 그룹을 참조하면 조용히 빈 문자열로 바꾸지 않고 에러를 반환합니다.
+    pub fn new(template: &str, pattern: &Pattern) -> Result<Template, TemplateError> {
+        let mut parts = Vec::new();
+        let mut literal = Vec::new();
+        let mut rest = template;
+        let resolve = |group: &str| -> Result<usize, TemplateError> {
+            let index = match group.parse::<usize>() {
+                Ok(index) => Some(index).filter(|&i| i < pattern.captures_len()),
+                Err(_) => pattern.group_index(group),
+            };
+            index.ok_or_else(|| TemplateError::UnknownGroup(group.to_string()))
+        };
+
+        while let Some(dollar) = rest.find('$') {
+            literal.extend_from_slice(&rest.as_bytes()[..dollar]);
+            let offset = template.len() - rest.len() + dollar;
+            let after = &rest[dollar + 1..];
+            if let Some(after) = after.strip_prefix('$') {
+                literal.push(b'$');
+                rest = after;
+                continue;
+            }
+            let (group, consumed) = if let Some(braced) = after.strip_prefix('{') {
+                let close = braced
+                    .find('}')
+                    .ok_or(TemplateError::UnclosedBrace(offset))?;
+                (&braced[..close], close + 2)
+            } else if after.starts_with(|c: char| c.is_ascii_digit()) {
+                let len = after
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(after.len());
+                (&after[..len], len)
+            } else {
+                let len = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..len], len)
+            };
+            if group.is_empty() {
+                // 그룹 이름이 오지 않는 `$` 는 그대로 둡니다.
+                literal.push(b'$');
+                rest = after;
+                continue;
+            }
+            if !literal.is_empty() {
+                parts.push(Part::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(Part::Group(resolve(group)?));
+            rest = &after[consumed..];
+        }
+        literal.extend_from_slice(rest.as_bytes());
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Template { parts })
+    }
+
+    /// 매치 하나에 대한 치환 결과를 `out` 에 덧붙입니다.
+    /// 매칭되지 않은 선택적 그룹은 빈 문자열이 됩니다.
+    pub fn expand(&self, haystack: &[u8], caps: &Captures, out: &mut Vec<u8>) {
+        for part in &self.parts {
+            match part {
+                Part::Literal(bytes) => out.extend_from_slice(bytes),
+                Part::Group(index) => {
+                    if let Some(range) = caps.get(*index) {
+                        out.extend_from_slice(&haystack[range]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 라인 하나의 모든 매치를 치환합니다. 매치가 없으면 `None` 입니다.
+pub fn replace_line(pattern: &Pattern, template: &Template, line: &[u8]) -> Option<Vec<u8>> {
+    let mut replaced = Vec::new();
+    let mut copied = 0;
+    let mut matched = false;
+    for found in pattern.find_iter(line) {
+        let caps = pattern.captures_at(line, found.start)?;
+        replaced.extend_from_slice(&line[copied..found.start]);
+        template.expand(line, &caps, &mut replaced);
+        copied = found.end;
+        matched = true;
+    }
+    if !matched {
+        return None;
+    }
+    replaced.extend_from_slice(&line[copied..]);
+    Some(replaced)
+}
+
+/// 파일 하나에 대한 치환 결과. 줄 끝 문자는 원본 그대로 유지합니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rewrite<'a> {
+    /// 원본 라인 (줄 끝 문자 포함)
+    lines: Vec<&'a [u8]>,
+    /// `(라인 인덱스, 바뀐 라인)`. 바뀐 라인도 원본의 줄 끝 문자를 포함합니다.
+    changes: Vec<(usize, Vec<u8>)>,
+}
+
+impl<'a> Rewrite<'a> {
+    /// `contents` 의 각 라인에 치환을 적용합니다.
+    pub fn new(pattern: &Pattern, template: &Template, contents: &'a [u8]) -> Rewrite<'a> {
+        let lines: Vec<&[u8]> = contents.split_inclusive(|&b| b == b'\n').collect();
+        let mut changes = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            let (body, ending) = split_line_end(line);
+            if let Some(mut replaced) = replace_line(pattern, template, body) {
+                replaced.extend_from_slice(ending);
+                if replaced != *line {
+                    changes.push((index, replaced));
+                }
+            }
+        }
+        Rewrite { lines, changes }
+    }
+
+    /// 바뀐 라인 수
+    pub fn changed_lines(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// 치환을 적용한 전체 내용
+    pub fn apply(&self) -> Vec<u8> {
+        let mut changes = self.changes.iter().peekable();
+        let mut out = Vec::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            match changes.next_if(|(i, _)| *i == index) {
+                Some((_, replaced)) => out.extend_from_slice(replaced),
+                None => out.extend_from_slice(line),
+            }
+        }
+        out
+    }
+
+    /// `path` 에 대한 unified diff. 바뀐 것이 없으면 빈 벡터입니다.
+    pub fn unified_diff(&self, path: &str) -> Vec<u8> {
+        let mut out = Vec::new();
+        if self.changes.is_empty() {
+            return out;
+        }
+        out.extend_from_slice(format!("--- {path}\n+++ {path}\n").as_bytes());
+
+        // 새 파일 쪽 라인 번호는 앞쪽 치환이 늘리거나 줄인 라인 수만큼 밀립니다.
+        let mut shift: isize = 0;
+        let mut next = 0;
+        while next < self.changes.len() {
+            let start = self.changes[next].0.saturating_sub(DIFF_CONTEXT);
+            let mut end = next;
+            while end + 1 < self.changes.len()
+                && self.changes[end + 1].0 <= self.changes[end].0 + 2 * DIFF_CONTEXT + 1
+            {
+                end += 1;
+            }
+            let stop = (self.changes[end].0 + DIFF_CONTEXT + 1).min(self.lines.len());
+            let hunk = &self.changes[next..=end];
+
+            let old_count = stop - start;
+            let new_count = hunk.iter().fold(old_count, |count, (_, replaced)| {
+                count - 1 + replaced.split_inclusive(|&b| b == b'\n').count()
+            });
+            let new_start = start as isize + 1 + shift;
+            out.extend_from_slice(
+                format!(
+                    "@@ -{},{} +{},{} @@\n",
+                    start + 1,
+                    old_count,
+                    new_start,
+                    new_count
+                )
+                .as_bytes(),
+            );
+
+            let mut changes = hunk.iter().peekable();
+            for index in start..stop {
+                match changes.next_if(|(i, _)| *i == index) {
+                    Some((_, replaced)) => {
+                        push_diff_lines(&mut out, b'-', self.lines[index]);
+                        push_diff_lines(&mut out, b'+', replaced);
+                    }
+                    None => push_diff_lines(&mut out, b' ', self.lines[index]),
+                }
+            }
+            shift += new_count as isize - old_count as isize;
+            next = end + 1;
+        }
+        out
+    }
+}
+
+/// 라인(들)에 diff 접두사를 붙입니다. 마지막 줄에 개행이 없으면 diff 규약대로 표시합니다.
+fn push_diff_lines(out: &mut Vec<u8>, prefix: u8, text: &[u8]) {
+    for line in text.split_inclusive(|&b| b == b'\n') {
+        out.push(prefix);
+        out.extend_from_slice(line);
+        if !line.ends_with(b"\n") {
+            out.extend_from_slice(b"\n\\ No newline at end of file\n");
+        }
+    }
+}
+
+/// 라인을 내용과 줄 끝 문자(`\n`, `\r\n`, 또는 없음)로 나눕니다.
+fn split_line_end(line: &[u8]) -> (&[u8], &[u8]) {
+    let len = match line {
+        [.., b'\r', b'\n'] => 2,
+        [.., b'\n'] => 1,
+        _ => 0,
+    };
+    line.split_at(line.len() - len)
+}
+
+/// `contents` 를 `path` 에 원자적으로 씁니다.
+///
+/// 같은 디렉토리에 임시 파일을 만들어 내용을 쓰고 디스크에 반영한 뒤 rename 하므로,
+/// 중간에 실패해도 원본은 예전 내용 그대로 남습니다. 원본의 권한도 임시 파일에 옮깁니다.
+/// `path` 가 심볼릭 링크면 링크는 그대로 두고 링크가 가리키는 실제 파일을 바꿉니다.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let with_path = |e: io::Error| io::Error::new(e.kind(), format!("{}: {}", path.display(), e));
+    // 링크 자리에 rename 하면 링크가 일반 파일로 바뀌므로 실제 파일 옆에서 바꿉니다.
+    let target = fs::canonicalize(path).map_err(with_path)?;
+    let dir = target.parent().unwrap_or(Path::new("."));
+    let name = target
+        .file_name()
+        .unwrap_or(target.as_os_str())
+        .to_string_lossy();
+    let temp = dir.join(format!(".{name}.minigrep-{}.tmp", process::id()));
+
+    let result = (|| {
+        let permissions = fs::metadata(&target)?.permissions();
+        let mut file = fs::File::create_new(&temp)?;
+        file.write_all(contents)?;
+        file.set_permissions(permissions)?;
+        file.sync_all()?;
+        fs::rename(&temp, &target)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result.map_err(with_path)
+}
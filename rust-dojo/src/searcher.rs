@@ -0,0 +1,351 @@
+//! 라이브러리로 쓰기 위한 검색 API
+//!
+//! [`Searcher`] 는 패턴과 옵션을 한 번 정해 두고 여러 입력에 재사용하는 검색기이고,
+//! 결과는 문자열이 아니라 [`Sink`] 의 콜백으로 전달됩니다. 다른 도구에 minigrep 을 넣어
+//! 쓸 때는 `Sink` 를 구현해 결과를 원하는 자료구조에 바로 모으면 됩니다.
+//!
+//! `run` 의 텍스트 출력([`Printer`])과 `--json` 출력([`crate::json::JsonPrinter`])도
+//! 이 API 위에서 동작하는 싱크입니다.
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::stream::{self, StreamItem, StreamOptions, StreamSummary};
+use crate::{
+    args, color, multiline, walk, BlockLine, Encoding, FieldQuery, Input, LineMatch, OutputStyle,
+    Palette, PatternSet, SearchResult, GROUP_SEPARATOR,
+};
+
+/// 검색 결과를 받는 콜백 모음
+///
+/// [`Sink::matched`] 외에는 아무것도 하지 않는 기본 구현이 있습니다.
+/// 콜백이 에러를 반환하면 검색을 멈추고 그 에러를 그대로 돌려줍니다.
+pub trait Sink {
+    /// 결과로 선택된 라인 하나. `-U` 면 여러 줄에 걸친 매치 하나입니다.
+    fn matched(&mut self, line: &LineMatch) -> io::Result<()>;
+
+    /// 매치 주변의 컨텍스트 라인 (`-A/-B/-C`)
+    fn context(&mut self, _line: &LineMatch) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// 서로 떨어진 컨텍스트 블록 사이. 컨텍스트 없이 검색하면 호출되지 않습니다.
+    fn context_break(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// 입력이 바이너리로 보입니다. `true` 를 반환하면 그래도 검색하고,
+    /// `false` 면 그 입력을 건너뜁니다. 기본 구현은 건너뜁니다.
+    fn binary_detected(&mut self) -> io::Result<bool> {
+        Ok(false)
+    }
+
+    /// 입력 하나의 검색이 끝났습니다. 바이너리라 건너뛴 입력에는 호출되지 않습니다.
+    fn finish(&mut self, _summary: &StreamSummary) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 한 번 설정해 두고 여러 입력에 재사용하는 검색기
+#[derive(Debug, Clone)]
+pub struct Searcher {
+    matcher: PatternSet,
+    options: StreamOptions,
+    multiline: bool,
+    encoding: Option<Encoding>,
+    field: Option<FieldQuery>,
+}
+
+impl Searcher {
+    /// 기본 옵션(컨텍스트 없음, 인코딩 자동 감지)으로 `matcher` 를 검색하는 검색기를 만듭니다.
+    pub fn new(matcher: PatternSet) -> Searcher {
+        Searcher {
+            matcher,
+            options: StreamOptions::default(),
+            multiline: false,
+            encoding: None,
+            field: None,
+        }
+    }
+
+    /// `-v` 와 컨텍스트 줄 수를 지정합니다.
+    pub fn with_options(mut self, options: StreamOptions) -> Searcher {
+        self.options = options;
+        self
+    }
+
+    /// 입력 전체에서 여러 줄에 걸친 매치를 찾습니다 (`-U`).
+    /// 패턴 식이 패턴 하나가 아니면 검색할 때 `InvalidInput` 에러를 반환합니다.
+    pub fn with_multiline(mut self, multiline: bool) -> Searcher {
+        self.multiline = multiline;
+        self
+    }
+
+    /// [`Searcher::search_path`] 로 읽는 파일의 인코딩. `None` 이면 앞부분으로 감지합니다.
+    pub fn with_encoding(mut self, encoding: Option<Encoding>) -> Searcher {
+        self.encoding = encoding;
+        self
+    }
+
+    /// 라인 전체 대신 레코드의 필드 하나에만 패턴을 적용합니다 (`--jsonl-field`, `--csv-column`).
+    pub fn with_field(mut self, field: Option<FieldQuery>) -> Searcher {
+        self.field = field;
+        self
+    }
+
+    pub fn matcher(&self) -> &PatternSet {
+        &self.matcher
+    }
+
+    pub fn options(&self) -> &StreamOptions {
+        &self.options
+    }
+
+    /// 이미 UTF-8 인 바이트를 검색합니다. 결과 라인에는 경로가 없습니다.
+    pub fn search_slice<S: Sink + ?Sized>(
+        &self,
+        bytes: &[u8],
+        sink: &mut S,
+    ) -> io::Result<StreamSummary> {
+        self.search_at(bytes, None, sink)
+    }
+
+    /// `reader` 를 끝까지 읽으며 검색합니다. 압축 해제나 인코딩 변환은 하지 않습니다.
+    pub fn search_reader<R: BufRead, S: Sink + ?Sized>(
+        &self,
+        reader: R,
+        sink: &mut S,
+    ) -> io::Result<StreamSummary> {
+        self.search_at(reader, None, sink)
+    }
+
+    /// 파일 하나를 검색합니다. `run` 처럼 gzip 은 풀고 UTF-8 이 아닌 파일은 바꿔 읽으며,
+    /// 결과 라인의 `path` 에 파일 경로를 채웁니다.
+    pub fn search_path<S: Sink + ?Sized>(
+        &self,
+        path: impl AsRef<Path>,
+        sink: &mut S,
+    ) -> io::Result<StreamSummary> {
+        let input = Input::File(path.as_ref().to_path_buf());
+        let mut summary = None;
+        input.for_each_source(self.encoding, |source, opened| {
+            summary = Some(self.search_at(opened.reader, Some(&source.name()), sink)?);
+            Ok(())
+        })?;
+        Ok(summary.unwrap_or_default())
+    }
+
+    /// 검색해 결과를 `sink` 로 보냅니다. `path` 가 있으면 결과 라인에 붙입니다.
+    pub(crate) fn search_at<R: BufRead, S: Sink + ?Sized>(
+        &self,
+        mut reader: R,
+        path: Option<&str>,
+        sink: &mut S,
+    ) -> io::Result<StreamSummary> {
+        if walk::is_binary(reader.fill_buf()?) && !sink.binary_detected()? {
+            return Ok(StreamSummary::default());
+        }
+        let with_path = |line: LineMatch| match path {
+            Some(path) => line.with_path(path),
+            None => line,
+        };
+        let mut blocks = 0;
+        let emit = |item| match item {
+            StreamItem::Line(line) => sink.matched(&with_path(line)),
+            StreamItem::Block(block) => {
+                if blocks > 0 {
+                    sink.context_break()?;
+                }
+                blocks += 1;
+                block.lines.into_iter().try_for_each(|line| match line {
+                    BlockLine::Match(line) => sink.matched(&with_path(line)),
+                    BlockLine::Context(line) => sink.context(&with_path(line)),
+                })
+            }
+        };
+        let summary = match (self.multiline, &self.field) {
+            (true, _) => {
+                let pattern = self.matcher.as_single().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, args::multiline_conflict())
+                })?;
+                multiline::search_reader(pattern, reader, self.options.max_count, emit)?
+            }
+            (false, Some(field)) => {
+                let matcher = field.matcher(&self.matcher);
+                stream::search_reader(&matcher, reader, &self.options, emit)?
+            }
+            (false, None) => stream::search_reader(&self.matcher, reader, &self.options, emit)?,
+        };
+        sink.finish(&summary)?;
+        Ok(summary)
+    }
+}
+
+/// 결과를 메모리에 모으는 싱크. 모은 라인은 [`crate::format_results`] 로 출력할 수 있습니다.
+#[derive(Debug, Clone, Default)]
+pub struct Collector {
+    /// 결과로 선택된 라인
+    pub matches: Vec<LineMatch>,
+    /// 컨텍스트 라인
+    pub context: Vec<LineMatch>,
+    /// 바이너리라 건너뛴 입력 수
+    pub binary_inputs: usize,
+    /// 검색한 입력들의 요약 합계
+    pub summary: StreamSummary,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Collector::default()
+    }
+}
+
+impl Sink for Collector {
+    fn matched(&mut self, line: &LineMatch) -> io::Result<()> {
+        self.matches.push(line.clone());
+        Ok(())
+    }
+
+    fn context(&mut self, line: &LineMatch) -> io::Result<()> {
+        self.context.push(line.clone());
+        Ok(())
+    }
+
+    fn binary_detected(&mut self) -> io::Result<bool> {
+        self.binary_inputs += 1;
+        Ok(false)
+    }
+
+    fn finish(&mut self, summary: &StreamSummary) -> io::Result<()> {
+        self.summary.add(summary);
+        Ok(())
+    }
+}
+
+/// `run` 의 텍스트 출력. 라인마다 [`SearchResult::format_colored`] 로 포맷해 씁니다.
+#[derive(Debug)]
+pub struct Printer<W: Write> {
+    out: W,
+    style: OutputStyle,
+    palette: Palette,
+    /// 결과를 컨텍스트 블록으로 보고 블록 사이에 `--` 를 넣습니다.
+    context: bool,
+    /// 라인을 출력하지 않습니다.
+    quiet: bool,
+    /// 바이너리 입력도 검색합니다.
+    search_binary: bool,
+    /// 바이너리 입력을 만났는지 여부
+    binary: bool,
+    /// 지금 출력 중인 블록이 있는지 여부
+    in_block: bool,
+    /// 앞에서 블록을 출력했는지 여부. 다음 블록 앞에 구분선을 넣습니다.
+    printed_block: bool,
+    /// 이 프린터가 블록을 출력했는지 여부
+    has_blocks: bool,
+}
+
+impl<W: Write> Printer<W> {
+    pub fn new(out: W, style: OutputStyle, palette: Palette) -> Self {
+        Printer {
+            out,
+            style,
+            palette,
+            context: false,
+            quiet: false,
+            search_binary: false,
+            binary: false,
+            in_block: false,
+            printed_block: false,
+            has_blocks: false,
+        }
+    }
+
+    /// 컨텍스트와 함께 검색합니다 (`-A/-B/-C`). 떨어진 블록 사이에 `--` 를 넣습니다.
+    pub fn with_context(mut self, context: bool) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// 앞에서 이미 블록을 출력했으면 첫 블록 앞에도 구분선을 넣습니다.
+    pub fn after_block(mut self, printed_block: bool) -> Self {
+        self.printed_block = printed_block;
+        self
+    }
+
+    /// 라인을 출력하지 않습니다. `-c` 와 `-l` 처럼 요약만 필요할 때 씁니다.
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// 바이너리 입력도 검색합니다 (`--binary`).
+    /// 터미널을 어지럽히지 않도록 바이너리 입력의 라인은 출력하지 않습니다.
+    pub fn search_binary(mut self, search_binary: bool) -> Self {
+        self.search_binary = search_binary;
+        self
+    }
+
+    /// 블록을 하나라도 출력했는지 여부
+    pub fn has_blocks(&self) -> bool {
+        self.has_blocks
+    }
+
+    /// 바이너리 입력을 만났는지 여부
+    pub fn saw_binary(&self) -> bool {
+        self.binary
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// 라인을 출력하기 전에 부릅니다. 새 블록이 시작되면 필요한 구분선을 넣습니다.
+    fn begin_line(&mut self) -> io::Result<bool> {
+        if self.quiet || self.binary {
+            return Ok(false);
+        }
+        if self.context && !self.in_block {
+            if self.printed_block {
+                let separator = color::painted(self.palette.separator, GROUP_SEPARATOR);
+                writeln!(self.out, "{}", separator)?;
+            }
+            self.in_block = true;
+            self.printed_block = true;
+            self.has_blocks = true;
+        }
+        Ok(true)
+    }
+}
+
+impl<W: Write> Sink for Printer<W> {
+    fn matched(&mut self, line: &LineMatch) -> io::Result<()> {
+        if self.begin_line()? {
+            let formatted = line.format_colored(&self.style, &self.palette);
+            writeln!(self.out, "{}", formatted)?;
+        }
+        Ok(())
+    }
+
+    fn context(&mut self, line: &LineMatch) -> io::Result<()> {
+        if self.begin_line()? {
+            let formatted = line.format_context(&self.style, &self.palette);
+            writeln!(self.out, "{}", formatted)?;
+        }
+        Ok(())
+    }
+
+    fn context_break(&mut self) -> io::Result<()> {
+        self.in_block = false;
+        Ok(())
+    }
+
+    fn binary_detected(&mut self) -> io::Result<bool> {
+        self.binary = true;
+        Ok(self.search_binary)
+    }
+
+    fn finish(&mut self, _summary: &StreamSummary) -> io::Result<()> {
+        self.in_block = false;
+        Ok(())
+    }
+}
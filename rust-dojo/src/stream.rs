@@ -0,0 +1,279 @@
+//! 스트리밍 검색
+//!
+//! 파일 전체를 메모리에 올리지 않고 [`BufRead`] 에서 한 줄씩 읽으며 검색합니다.
+//! 라인 버퍼는 재사용되고, `-B` 컨텍스트를 위해 최근 N 줄만 보관하므로
+//! 메모리 사용량은 입력 크기가 아니라 라인 길이와 컨텍스트 크기에 비례합니다.
+//! 파일, 표준 입력, 파이프 모두 같은 방식으로 다룰 수 있습니다.
+//!
+//! 컨텍스트 없이 리터럴을 찾을 때는 라인을 나누지 않고 큰 청크 전체에서 리터럴을 찾은 뒤,
+//! 매치 주변에서만 라인 경계를 찾습니다 ([`Matcher::buffer_finder`]).
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+use crate::literal::{self, FindAt};
+use crate::matcher::Matcher;
+use crate::{BlockLine, ContextBlock, LineMatch};
+
+/// 리터럴 검색에서 한 번에 살펴보는 최소 바이트 수
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// 스트리밍 검색 옵션
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StreamOptions {
+    /// 매칭되지 않는 라인을 결과로 삼습니다 (`-v`).
+    pub invert: bool,
+    /// 매치 앞에 붙일 컨텍스트 줄 수 (`-B`)
+    pub before: usize,
+    /// 매치 뒤에 붙일 컨텍스트 줄 수 (`-A`)
+    pub after: usize,
+    /// 결과로 선택된 라인이 이만큼 모이면 나머지는 읽지 않습니다.
+    /// 매치 여부만 필요한 `-l`, `-L` 은 1 입니다. 마지막 매치의 뒤쪽 컨텍스트는 마저 읽습니다.
+    pub max_count: Option<u64>,
+}
+
+impl StreamOptions {
+    fn has_context(&self) -> bool {
+        self.before > 0 || self.after > 0
+    }
+
+    fn limit_reached(&self, summary: &StreamSummary) -> bool {
+        self.max_count
+            .is_some_and(|max| summary.matched_lines >= max)
+    }
+}
+
+/// 검색 중에 만들어지는 결과 하나
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamItem {
+    /// 컨텍스트 없이 검색할 때의 매칭 라인
+    Line(LineMatch),
+    /// 컨텍스트와 함께 검색할 때 완성된 블록
+    Block(ContextBlock),
+}
+
+/// 검색이 끝난 뒤의 요약
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StreamSummary {
+    /// 읽은 바이트 수
+    pub bytes_read: u64,
+    /// 결과로 선택된 라인 수 (`-v` 면 매칭되지 않은 라인 수)
+    pub matched_lines: u64,
+    /// 선택된 라인 안의 매치 구간 수
+    pub matches: u64,
+}
+
+impl StreamSummary {
+    /// 다른 요약을 더합니다. 여러 파일의 합계를 낼 때 사용합니다.
+    pub fn add(&mut self, other: &StreamSummary) {
+        self.bytes_read += other.bytes_read;
+        self.matched_lines += other.matched_lines;
+        self.matches += other.matches;
+    }
+}
+
+/// `reader` 를 끝까지 읽으며 결과가 만들어질 때마다 `emit` 을 호출합니다.
+///
+/// 컨텍스트가 없으면 매칭 라인마다, 있으면 블록이 닫힐 때마다 호출됩니다.
+/// 읽기 에러와 `emit` 이 반환한 에러는 그대로 전파됩니다.
+/// `matcher` 는 [`crate::Pattern`] 하나일 수도, 여러 패턴의 조합일 수도 있습니다.
+pub fn search_reader<M, R, F>(
+    matcher: &M,
+    mut reader: R,
+    options: &StreamOptions,
+    mut emit: F,
+) -> io::Result<StreamSummary>
+where
+    M: Matcher + ?Sized,
+    R: BufRead,
+    F: FnMut(StreamItem) -> io::Result<()>,
+{
+    if let Some(finder) = matcher.buffer_finder() {
+        if !options.invert && !options.has_context() {
+            return search_literal(matcher, finder, reader, options, emit);
+        }
+    }
+
+    let mut summary = StreamSummary::default();
+    let mut buf = Vec::new();
+    let mut line_number = 0;
+
+    // 컨텍스트 상태: 최근 `before` 줄, 열린 블록, 남은 `after` 줄 수
+    let mut recent: VecDeque<LineMatch> = VecDeque::with_capacity(options.before);
+    let mut block = ContextBlock::new();
+    let mut after_left = 0;
+    let mut last_included = 0;
+
+    loop {
+        let limit_reached = options.limit_reached(&summary);
+        if limit_reached && after_left == 0 {
+            break;
+        }
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf)?;
+        if read == 0 {
+            break;
+        }
+        summary.bytes_read += read as u64;
+        line_number += 1;
+
+        let line = trim_line_end(&buf);
+        let selected = !limit_reached && matcher.is_match_line(line) != options.invert;
+        if selected {
+            summary.matched_lines += 1;
+        }
+
+        if !options.has_context() {
+            if selected {
+                let found = line_match(matcher, line_number, line, options);
+                summary.matches += found.matches.len() as u64;
+                emit(StreamItem::Line(found))?;
+            }
+            continue;
+        }
+
+        if selected {
+            // 새 매치의 컨텍스트가 열린 블록과 떨어져 있으면 블록을 닫습니다.
+            let first_new = recent.front().map_or(line_number, |l| l.line_number);
+            if !block.lines.is_empty() && first_new > last_included + 1 {
+                emit(StreamItem::Block(std::mem::take(&mut block)))?;
+            }
+            block.lines.extend(recent.drain(..).map(BlockLine::Context));
+            let found = line_match(matcher, line_number, line, options);
+            summary.matches += found.matches.len() as u64;
+            block.lines.push(BlockLine::Match(found));
+            after_left = options.after;
+            last_included = line_number;
+        } else if after_left > 0 {
+            block.lines.push(BlockLine::Context(LineMatch::from_bytes(
+                line_number,
+                line,
+                None,
+            )));
+            after_left -= 1;
+            last_included = line_number;
+        } else {
+            // 앞으로 어떤 매치가 와도 이어질 수 없으면 블록을 바로 내보냅니다.
+            if !block.lines.is_empty() && line_number > last_included + options.before {
+                emit(StreamItem::Block(std::mem::take(&mut block)))?;
+            }
+            if options.before > 0 {
+                if recent.len() == options.before {
+                    recent.pop_front();
+                }
+                recent.push_back(LineMatch::from_bytes(line_number, line, None));
+            }
+        }
+    }
+
+    if !block.lines.is_empty() {
+        emit(StreamItem::Block(block))?;
+    }
+    Ok(summary)
+}
+
+/// 청크 단위 리터럴 검색. 결과는 라인 단위 검색과 같습니다.
+fn search_literal<M, R, F>(
+    matcher: &M,
+    finder: &dyn FindAt,
+    mut reader: R,
+    options: &StreamOptions,
+    mut emit: F,
+) -> io::Result<StreamSummary>
+where
+    M: Matcher + ?Sized,
+    R: BufRead,
+    F: FnMut(StreamItem) -> io::Result<()>,
+{
+    let mut summary = StreamSummary::default();
+    let mut buf = Vec::with_capacity(CHUNK_SIZE);
+    // `buf` 앞쪽까지 지나간 라인 수
+    let mut lines_before = 0;
+    let mut target = CHUNK_SIZE;
+    let mut eof = false;
+    // 읽기 에러는 이미 읽은 완전한 라인들을 처리한 뒤에 반환합니다.
+    let mut failure = None;
+
+    loop {
+        while !eof && failure.is_none() && buf.len() < target {
+            let available = match reader.fill_buf() {
+                Ok(available) => available,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    failure = Some(e);
+                    break;
+                }
+            };
+            if available.is_empty() {
+                eof = true;
+                break;
+            }
+            let read = available.len();
+            buf.extend_from_slice(available);
+            reader.consume(read);
+            summary.bytes_read += read as u64;
+        }
+        // 마지막 개행까지만 처리하고, 잘린 라인은 다음 청크로 넘깁니다.
+        let end = match literal::memrchr(b'\n', &buf) {
+            _ if eof => buf.len(),
+            Some(i) => i + 1,
+            None if failure.is_some() => 0,
+            None => {
+                // 청크보다 긴 라인: 개행이 나올 때까지 더 읽습니다.
+                target = buf.len() + CHUNK_SIZE;
+                continue;
+            }
+        };
+        target = CHUNK_SIZE;
+
+        let region = &buf[..end];
+        let mut counted = 0;
+        for line in literal::matching_lines(finder, region) {
+            lines_before += literal::count_byte(b'\n', &region[counted..line.start]);
+            let terminated = line.end < region.len();
+            let mut text = &region[line.clone()];
+            if terminated {
+                text = text.strip_suffix(b"\r").unwrap_or(text);
+            }
+            let found = LineMatch::with_spans(lines_before + 1, text, matcher.find_spans(text));
+            summary.matched_lines += 1;
+            summary.matches += found.matches.len() as u64;
+            emit(StreamItem::Line(found))?;
+            if options.limit_reached(&summary) {
+                return Ok(summary);
+            }
+            lines_before += 1;
+            counted = (line.end + 1).min(region.len());
+        }
+        lines_before += literal::count_byte(b'\n', &region[counted..]);
+
+        buf.drain(..end);
+        if let Some(e) = failure {
+            return Err(e);
+        }
+        if eof {
+            break;
+        }
+    }
+    Ok(summary)
+}
+
+fn line_match<M: Matcher + ?Sized>(
+    matcher: &M,
+    line_number: usize,
+    line: &[u8],
+    options: &StreamOptions,
+) -> LineMatch {
+    match options.invert {
+        true => LineMatch::from_bytes(line_number, line, None),
+        false => LineMatch::with_spans(line_number, line, matcher.find_spans(line)),
+    }
+}
+
+/// `str::lines` 와 같은 규칙으로 줄 끝의 `\n` 또는 `\r\n` 을 제거합니다.
+pub(crate) fn trim_line_end(line: &[u8]) -> &[u8] {
+    match line.strip_suffix(b"\n") {
+        Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+        None => line,
+    }
+}
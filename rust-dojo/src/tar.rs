@@ -0,0 +1,329 @@
+//! tar 아카이브 읽기 (POSIX ustar, GNU 긴 이름, pax 확장 헤더)
+//!
+//! 일반 파일 멤버만 순서대로 돌려줍니다. 디렉토리, 링크, 장치 파일 같은 멤버는
+//! 검색할 내용이 없으므로 건너뜁니다. [`Archive`] 는 스트림에서 멤버를 하나씩 읽으므로
+//! 아카이브 전체를 메모리에 올리지 않습니다.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+const BLOCK: usize = 512;
+
+/// 아카이브 안의 일반 파일 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// 아카이브 안에서의 경로
+    pub path: String,
+    pub data: &'a [u8],
+}
+
+/// tar 파싱 에러
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TarError {
+    /// 헤더나 데이터가 잘려 있습니다.
+    UnexpectedEof,
+    /// 헤더 체크섬이 맞지 않습니다. 오프셋은 헤더의 시작 위치입니다.
+    BadChecksum(usize),
+    /// 숫자 필드가 잘못되었습니다.
+    InvalidNumber(usize),
+}
+
+impl fmt::Display for TarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TarError::UnexpectedEof => write!(f, "unexpected end of tar archive"),
+            TarError::BadChecksum(offset) => {
+                write!(f, "bad tar header checksum at offset {offset}")
+            }
+            TarError::InvalidNumber(offset) => {
+                write!(f, "invalid number in tar header at offset {offset}")
+            }
+        }
+    }
+}
+
+impl Error for TarError {}
+
+impl From<TarError> for io::Error {
+    fn from(e: TarError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// 첫 헤더에 ustar 매직(`ustar\0` 또는 GNU 의 `ustar `)이 있는지 확인합니다.
+pub fn is_tar(bytes: &[u8]) -> bool {
+    bytes.len() >= BLOCK && &bytes[257..262] == b"ustar"
+}
+
+/// 아카이브의 일반 파일 멤버들
+pub fn entries(bytes: &[u8]) -> Result<Vec<Entry<'_>>, TarError> {
+    let mut entries = Vec::new();
+    // GNU `L` 헤더나 pax `path` 로 받은, 다음 멤버에 쓸 긴 경로
+    let mut long_path: Option<String> = None;
+    let mut offset = 0;
+    loop {
+        let header = bytes
+            .get(offset..offset + BLOCK)
+            .ok_or(TarError::UnexpectedEof)?;
+        let Some(header) = Header::parse(header, offset)? else {
+            return Ok(entries);
+        };
+        let size = usize::try_from(header.size).map_err(|_| TarError::InvalidNumber(offset))?;
+        let start = offset + BLOCK;
+        let data = start
+            .checked_add(size)
+            .and_then(|end| bytes.get(start..end))
+            .ok_or(TarError::UnexpectedEof)?;
+
+        match header.kind {
+            Kind::File => {
+                let path = long_path.take().unwrap_or(header.path);
+                entries.push(Entry { path, data });
+            }
+            Kind::LongName => long_path = Some(field(data)),
+            Kind::Pax => {
+                if let Some(path) = pax_path(data) {
+                    long_path = Some(path);
+                }
+            }
+            Kind::Other => {}
+        }
+        offset = start + size.div_ceil(BLOCK) * BLOCK;
+    }
+}
+
+/// 스트림에서 멤버를 하나씩 읽는 tar 리더
+pub struct Archive<R> {
+    inner: R,
+    /// 지금까지 읽은 바이트 수. 에러에 붙는 헤더 오프셋입니다.
+    offset: u64,
+    /// 현재 멤버에서 아직 읽지 않은 데이터의 길이
+    remaining: u64,
+    /// 현재 멤버 데이터 뒤를 블록 경계까지 채운 패딩의 길이
+    padding: u64,
+    done: bool,
+}
+
+/// [`Archive`] 에서 꺼낸 일반 파일 하나. 읽으면 멤버의 내용이 나옵니다.
+pub struct Member<'a, R> {
+    /// 아카이브 안에서의 경로
+    pub path: String,
+    archive: &'a mut Archive<R>,
+}
+
+impl<R: Read> Archive<R> {
+    pub fn new(inner: R) -> Archive<R> {
+        Archive {
+            inner,
+            offset: 0,
+            remaining: 0,
+            padding: 0,
+            done: false,
+        }
+    }
+
+    /// 다음 일반 파일 멤버. 앞 멤버에서 읽지 않은 내용은 건너뜁니다.
+    /// 헤더가 잘못되었으면 [`TarError`] 를 담은 [`io::ErrorKind::InvalidData`] 에러를 반환합니다.
+    pub fn next_member(&mut self) -> io::Result<Option<Member<'_, R>>> {
+        let mut long_path: Option<String> = None;
+        loop {
+            self.skip(self.remaining + self.padding)?;
+            if self.done {
+                return Ok(None);
+            }
+            let offset = usize::try_from(self.offset).unwrap_or(usize::MAX);
+            let mut block = [0u8; BLOCK];
+            self.read_exact(&mut block)?;
+            let Some(header) = Header::parse(&block, offset)? else {
+                self.done = true;
+                return Ok(None);
+            };
+            self.remaining = header.size;
+            let padded = header
+                .size
+                .checked_next_multiple_of(BLOCK as u64)
+                .ok_or(TarError::InvalidNumber(offset))?;
+            self.padding = padded - header.size;
+
+            match header.kind {
+                Kind::File => {
+                    let path = long_path.take().unwrap_or(header.path);
+                    return Ok(Some(Member {
+                        path,
+                        archive: self,
+                    }));
+                }
+                Kind::LongName => long_path = Some(field(&self.read_data()?)),
+                Kind::Pax => {
+                    if let Some(path) = pax_path(&self.read_data()?) {
+                        long_path = Some(path);
+                    }
+                }
+                Kind::Other => {}
+            }
+        }
+    }
+
+    /// 현재 멤버의 데이터를 모두 읽습니다. 긴 경로를 담은 작은 멤버에만 씁니다.
+    fn read_data(&mut self) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        Member {
+            path: String::new(),
+            archive: self,
+        }
+        .read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        match self.inner.read_exact(buf) {
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                Err(TarError::UnexpectedEof.into())
+            }
+            result => {
+                self.offset += buf.len() as u64;
+                result
+            }
+        }
+    }
+
+    fn skip(&mut self, len: u64) -> io::Result<()> {
+        let skipped = io::copy(&mut (&mut self.inner).take(len), &mut io::sink())?;
+        self.offset += skipped;
+        if skipped < len {
+            return Err(TarError::UnexpectedEof.into());
+        }
+        self.remaining = 0;
+        self.padding = 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Member<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let archive = &mut *self.archive;
+        if archive.remaining == 0 || buf.is_empty() {
+            return Ok(0);
+        }
+        let max = usize::try_from(archive.remaining).map_or(buf.len(), |n| n.min(buf.len()));
+        let n = archive.inner.read(&mut buf[..max])?;
+        if n == 0 {
+            return Err(TarError::UnexpectedEof.into());
+        }
+        archive.remaining -= n as u64;
+        archive.offset += n as u64;
+        Ok(n)
+    }
+}
+
+/// 헤더 블록이 나타내는 멤버 종류
+enum Kind {
+    /// 일반 파일 (`0`, 옛 형식의 `\0`, 연속 파일 `7`)
+    File,
+    /// 다음 멤버의 긴 경로를 담은 GNU `L` 멤버
+    LongName,
+    /// 다음 멤버의 속성을 담은 pax `x` 멤버
+    Pax,
+    /// `g`(전역 pax 헤더)를 포함한 나머지 종류. 검색할 내용이 없습니다.
+    Other,
+}
+
+/// 헤더 블록 하나
+struct Header {
+    kind: Kind,
+    size: u64,
+    path: String,
+}
+
+impl Header {
+    /// `offset` 에 있는 헤더 블록을 읽습니다. 아카이브 끝(0 으로 채운 블록)이면 `None` 입니다.
+    fn parse(header: &[u8], offset: usize) -> Result<Option<Header>, TarError> {
+        if header.iter().all(|&b| b == 0) {
+            return Ok(None);
+        }
+        if checksum(header) != number(&header[148..156]).ok_or(TarError::BadChecksum(offset))? {
+            return Err(TarError::BadChecksum(offset));
+        }
+        let size = number(&header[124..136]).ok_or(TarError::InvalidNumber(offset))?;
+        let kind = match header[156] {
+            b'0' | b'\0' | b'7' => Kind::File,
+            b'L' => Kind::LongName,
+            b'x' => Kind::Pax,
+            _ => Kind::Other,
+        };
+        Ok(Some(Header {
+            kind,
+            size,
+            path: header_path(header),
+        }))
+    }
+}
+
+/// 헤더의 이름 필드. ustar 는 긴 경로의 앞부분을 `prefix` 에 나눠 담습니다.
+fn header_path(header: &[u8]) -> String {
+    let name = field(&header[..100]);
+    let prefix = match &header[257..263] {
+        b"ustar\0" => field(&header[345..500]),
+        _ => String::new(),
+    };
+    match prefix.is_empty() {
+        true => name,
+        false => format!("{prefix}/{name}"),
+    }
+}
+
+/// NUL 로 끝나는 문자열 필드
+fn field(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// 8진수 숫자 필드. GNU tar 는 큰 값을 첫 바이트의 최상위 비트를 켠 256진수로 씁니다.
+fn number(bytes: &[u8]) -> Option<u64> {
+    if bytes.first().is_some_and(|&b| b & 0x80 != 0) {
+        return bytes[1..]
+            .iter()
+            .try_fold(u64::from(bytes[0] & 0x7f), |n, &b| {
+                n.checked_mul(256)?.checked_add(u64::from(b))
+            });
+    }
+    let digits: &[u8] = bytes
+        .split(|&b| b == 0 || b == b' ')
+        .find(|part| !part.is_empty())
+        .unwrap_or_default();
+    if digits.is_empty() {
+        return Some(0);
+    }
+    let digits = std::str::from_utf8(digits).ok()?;
+    u64::from_str_radix(digits, 8).ok()
+}
+
+/// 체크섬 필드를 공백으로 보고 계산한 헤더 바이트의 합
+fn checksum(header: &[u8]) -> u64 {
+    header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| match i {
+            148..156 => u64::from(b' '),
+            _ => u64::from(b),
+        })
+        .sum()
+}
+
+/// pax 확장 헤더(`길이 키=값\n` 레코드들)의 `path`
+fn pax_path(data: &[u8]) -> Option<String> {
+    let mut rest = data;
+    let mut path = None;
+    while !rest.is_empty() {
+        let space = rest.iter().position(|&b| b == b' ')?;
+        let len: usize = std::str::from_utf8(&rest[..space]).ok()?.parse().ok()?;
+        let record = rest.get(space + 1..len)?;
+        let record = record.strip_suffix(b"\n").unwrap_or(record);
+        if let Some(value) = record.strip_prefix(b"path=") {
+            path = Some(String::from_utf8_lossy(value).into_owned());
+        }
+        rest = &rest[len..];
+    }
+    path
+}
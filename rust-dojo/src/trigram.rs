@@ -0,0 +1,377 @@
+//! 디스크 트라이그램 색인 (`minigrep index build`, `--index`)
+//!
+//! 파일마다 나오는 연속된 3 바이트(트라이그램)를 모아 `트라이그램 → 파일 목록` 포스팅을
+//! 디렉토리의 [`INDEX_FILE`] 에 저장합니다. 리터럴 질의의 트라이그램이 모두 들어 있는
+//! 파일만 후보로 남기고, 후보는 평소처럼 검색해 확인합니다.
+//!
+//! 색인은 파일 크기와 수정 시각을 함께 기록합니다. 다시 만들 때는 둘 다 그대로인 파일의
+//! 트라이그램을 재사용하고, 검색할 때는 달라진 파일(또는 색인에 없는 파일)을 후보에서
+//! 빼지 않고 그대로 검색하므로 색인이 오래돼도 결과가 빠지지 않습니다.
+//!
+//! 파일 형식은 매직 문자열 뒤에 LEB128 가변 길이 정수로 파일 목록과, 트라이그램 순으로
+//! 정렬된 포스팅(트라이그램과 파일 번호 모두 앞 값과의 차이)을 적은 것입니다.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::UNIX_EPOCH;
+
+use crate::encoding::{self, Encoding};
+use crate::{gzip, walk};
+
+/// 색인 디렉토리 안에 만드는 색인 파일 이름
+pub const INDEX_FILE: &str = ".minigrep-index";
+
+const MAGIC: &[u8] = b"minigrep trigram index v1\n";
+
+/// 색인에 기록된 파일 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    /// 색인 디렉토리 기준 상대 경로
+    pub path: PathBuf,
+    /// 파일 크기 (바이트)
+    pub size: u64,
+    /// 수정 시각 (유닉스 시각, 나노초)
+    pub modified: u64,
+}
+
+impl FileEntry {
+    /// `metadata` 가 색인할 때와 같은 파일을 가리키는지 확인합니다.
+    pub fn is_fresh(&self, metadata: &fs::Metadata) -> bool {
+        self.size == metadata.len() && Some(self.modified) == modified_nanos(metadata)
+    }
+}
+
+/// [`TrigramIndex::build`] 의 결과 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuildStats {
+    /// 색인한 전체 파일 수
+    pub files: usize,
+    /// 새로 읽은(추가되거나 바뀐) 파일 수
+    pub updated: usize,
+    /// 이전 색인에는 있었지만 사라진 파일 수
+    pub removed: usize,
+}
+
+/// 트라이그램 포스팅 색인
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrigramIndex {
+    files: Vec<FileEntry>,
+    /// 트라이그램 → 파일 번호 (오름차순)
+    postings: BTreeMap<u32, Vec<u32>>,
+}
+
+impl TrigramIndex {
+    /// `root` 아래의 `files` 로 색인을 만듭니다. `previous` 에 크기와 수정 시각이 같은
+    /// 파일이 있으면 파일을 다시 읽지 않고 그 트라이그램을 씁니다.
+    /// 바이너리 파일은 검색 대상이 아니므로 트라이그램 없이 기록합니다.
+    pub fn build(
+        root: &Path,
+        files: &[PathBuf],
+        previous: Option<&TrigramIndex>,
+    ) -> io::Result<(TrigramIndex, BuildStats)> {
+        let mut reusable: HashMap<&Path, (&FileEntry, Vec<u32>)> = HashMap::new();
+        if let Some(previous) = previous {
+            let mut per_file = previous.trigrams_per_file();
+            for (entry, trigrams) in previous.files.iter().zip(per_file.iter_mut()) {
+                reusable.insert(&entry.path, (entry, std::mem::take(trigrams)));
+            }
+        }
+
+        let mut stats = BuildStats::default();
+        let mut entries = Vec::with_capacity(files.len());
+        let mut per_file = Vec::with_capacity(files.len());
+        for path in files {
+            let metadata = fs::metadata(path).map_err(|e| with_path(e, path))?;
+            let relative = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+            let entry = FileEntry {
+                size: metadata.len(),
+                modified: modified_nanos(&metadata).unwrap_or_default(),
+                path: relative,
+            };
+            let trigrams = match reusable.remove(entry.path.as_path()) {
+                Some((old, trigrams)) if old.is_fresh(&metadata) => trigrams,
+                _ => {
+                    stats.updated += 1;
+                    let mut contents = fs::read(path).map_err(|e| with_path(e, path))?;
+                    // 검색할 때처럼 gzip 파일은 푼 내용을 색인합니다.
+                    if gzip::is_gzip(&contents) {
+                        contents = gzip::gunzip(&contents).map_err(|e| {
+                            with_path(io::Error::new(io::ErrorKind::InvalidData, e), path)
+                        })?;
+                    }
+                    // UTF-16 이나 CP949 파일은 검색할 때처럼 UTF-8 로 바꾼 내용을 색인합니다.
+                    match encoding::detect(&contents) {
+                        Encoding::Utf8 => {}
+                        found => contents = encoding::decode(&contents, found).into_bytes(),
+                    }
+                    match walk::is_binary(&contents) {
+                        true => Vec::new(),
+                        false => trigrams(&contents),
+                    }
+                }
+            };
+            entries.push(entry);
+            per_file.push(trigrams);
+        }
+        // 지금 있는 파일은 모두 꺼냈으므로 남은 항목은 사라진 파일입니다.
+        stats.removed = reusable.len();
+        stats.files = entries.len();
+
+        let mut postings: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for (id, trigrams) in per_file.iter().enumerate() {
+            for &trigram in trigrams {
+                postings.entry(trigram).or_default().push(id as u32);
+            }
+        }
+        let index = TrigramIndex {
+            files: entries,
+            postings,
+        };
+        Ok((index, stats))
+    }
+
+    /// 색인에 든 파일들
+    pub fn files(&self) -> &[FileEntry] {
+        &self.files
+    }
+
+    /// 서로 다른 트라이그램 수
+    pub fn trigram_count(&self) -> usize {
+        self.postings.len()
+    }
+
+    /// `literals` 중 하나라도 들어 있을 수 있는 파일이면 `true` 인 목록 (파일 번호 순).
+    /// 3 바이트보다 짧은 리터럴이 있으면 거를 수 없으므로 `None` 입니다.
+    pub fn candidates(&self, literals: &[&[u8]]) -> Option<Vec<bool>> {
+        if literals.iter().any(|literal| literal.len() < 3) {
+            return None;
+        }
+        let mut candidates = vec![false; self.files.len()];
+        for literal in literals {
+            for id in self.containing_all(&trigrams(literal)) {
+                candidates[id as usize] = true;
+            }
+        }
+        Some(candidates)
+    }
+
+    /// 모든 트라이그램이 나오는 파일 번호. 가장 짧은 포스팅부터 교집합을 구합니다.
+    fn containing_all(&self, trigrams: &[u32]) -> Vec<u32> {
+        let mut lists = Vec::with_capacity(trigrams.len());
+        for trigram in trigrams {
+            match self.postings.get(trigram) {
+                Some(list) => lists.push(list.as_slice()),
+                None => return Vec::new(),
+            }
+        }
+        lists.sort_by_key(|list| list.len());
+        let Some((first, rest)) = lists.split_first() else {
+            return Vec::new();
+        };
+        first
+            .iter()
+            .copied()
+            .filter(|id| rest.iter().all(|list| list.binary_search(id).is_ok()))
+            .collect()
+    }
+
+    /// 포스팅을 뒤집어 파일마다 트라이그램 목록(오름차순)을 만듭니다.
+    fn trigrams_per_file(&self) -> Vec<Vec<u32>> {
+        let mut per_file = vec![Vec::new(); self.files.len()];
+        for (&trigram, ids) in &self.postings {
+            for &id in ids {
+                per_file[id as usize].push(trigram);
+            }
+        }
+        per_file
+    }
+
+    /// 색인을 바이트로 직렬화합니다.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        write_varint(&mut out, self.files.len() as u64);
+        for entry in &self.files {
+            let path = path_to_bytes(&entry.path);
+            write_varint(&mut out, path.len() as u64);
+            out.extend_from_slice(&path);
+            write_varint(&mut out, entry.size);
+            write_varint(&mut out, entry.modified);
+        }
+        write_varint(&mut out, self.postings.len() as u64);
+        let mut previous = 0;
+        for (&trigram, ids) in &self.postings {
+            write_varint(&mut out, u64::from(trigram - previous));
+            previous = trigram;
+            write_varint(&mut out, ids.len() as u64);
+            let mut last = 0;
+            for &id in ids {
+                write_varint(&mut out, u64::from(id - last));
+                last = id;
+            }
+        }
+        out
+    }
+
+    /// [`TrigramIndex::to_bytes`] 의 결과를 읽습니다.
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<TrigramIndex> {
+        let rest = bytes
+            .strip_prefix(MAGIC)
+            .ok_or_else(|| invalid("not a minigrep index"))?;
+        let mut reader = Reader { bytes: rest };
+        let file_count = reader.count()?;
+        let mut files = Vec::new();
+        for _ in 0..file_count {
+            let len = reader.count()?;
+            let path = bytes_to_path(reader.take(len)?);
+            files.push(FileEntry {
+                path,
+                size: reader.varint()?,
+                modified: reader.varint()?,
+            });
+        }
+        let trigram_count = reader.count()?;
+        let mut postings = BTreeMap::new();
+        let mut trigram = 0u32;
+        for _ in 0..trigram_count {
+            trigram = trigram
+                .checked_add(reader.u32()?)
+                .ok_or_else(|| invalid("trigram out of range"))?;
+            let len = reader.count()?;
+            let mut ids = Vec::with_capacity(len.min(files.len()));
+            let mut id = 0u32;
+            for _ in 0..len {
+                id = id
+                    .checked_add(reader.u32()?)
+                    .filter(|&id| (id as usize) < files.len())
+                    .ok_or_else(|| invalid("file number out of range"))?;
+                ids.push(id);
+            }
+            postings.insert(trigram, ids);
+        }
+        if !reader.bytes.is_empty() {
+            return Err(invalid("trailing bytes"));
+        }
+        Ok(TrigramIndex { files, postings })
+    }
+
+    /// 디렉토리 `root` 의 색인 파일을 읽습니다.
+    pub fn load(root: &Path) -> io::Result<TrigramIndex> {
+        let path = root.join(INDEX_FILE);
+        let bytes = fs::read(&path).map_err(|e| with_path(e, &path))?;
+        TrigramIndex::from_bytes(&bytes).map_err(|e| with_path(e, &path))
+    }
+
+    /// 디렉토리 `root` 에 색인 파일을 씁니다. 임시 파일에 쓴 뒤 rename 하므로
+    /// 동시에 검색하는 프로세스는 이전 색인이나 새 색인 중 하나를 온전히 읽습니다.
+    pub fn save(&self, root: &Path) -> io::Result<()> {
+        let path = root.join(INDEX_FILE);
+        let temp = root.join(format!("{INDEX_FILE}.{}.tmp", process::id()));
+        let result = fs::write(&temp, self.to_bytes()).and_then(|_| fs::rename(&temp, &path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        result.map_err(|e| with_path(e, &path))
+    }
+}
+
+/// 바이트열의 서로 다른 트라이그램 (오름차순)
+pub fn trigrams(bytes: &[u8]) -> Vec<u32> {
+    let mut trigrams: Vec<u32> = bytes
+        .windows(3)
+        .map(|w| u32::from(w[0]) << 16 | u32::from(w[1]) << 8 | u32::from(w[2]))
+        .collect();
+    trigrams.sort_unstable();
+    trigrams.dedup();
+    trigrams
+}
+
+fn modified_nanos(metadata: &fs::Metadata) -> Option<u64> {
+    let since_epoch = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    u64::try_from(since_epoch.as_nanos()).ok()
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// 색인 파일을 앞에서부터 읽는 커서
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn varint(&mut self) -> io::Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self
+                .bytes
+                .split_first()
+                .ok_or_else(|| invalid("unexpected end of index"))?;
+            self.bytes = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("varint too long"))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        u32::try_from(self.varint()?).map_err(|_| invalid("value out of range"))
+    }
+
+    /// 길이나 개수. 남은 바이트보다 클 수 없으므로 손상된 파일로 큰 할당을 하지 않습니다.
+    fn count(&mut self) -> io::Result<usize> {
+        match usize::try_from(self.varint()?) {
+            Ok(n) if n <= self.bytes.len() => Ok(n),
+            _ => Err(invalid("length out of range")),
+        }
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if len > self.bytes.len() {
+            return Err(invalid("unexpected end of index"));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+}
+
+#[cfg(unix)]
+fn path_to_bytes(path: &Path) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(not(unix))]
+fn path_to_bytes(path: &Path) -> Vec<u8> {
+    path.to_string_lossy().into_owned().into_bytes()
+}
+
+#[cfg(unix)]
+fn bytes_to_path(bytes: &[u8]) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+fn bytes_to_path(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// io 에러 메시지에 문제가 된 경로를 붙입니다.
+fn with_path(err: io::Error, path: &Path) -> io::Error {
+    io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+}
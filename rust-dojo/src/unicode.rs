@@ -0,0 +1,171 @@
+//! 유니코드 대소문자 접기와 단어 문자 판별
+//!
+//! `-i` 와 `-w` 가 사용하는 유니코드 규칙입니다.
+//!
+//! - 대소문자 접기는 Unicode `CaseFolding.txt` 의 단순(simple, C+S) 접기를 따릅니다.
+//!   한 글자는 항상 한 글자로 접히므로 `ẞ`/`ß`, `ſ`/`s`, `ς`/`σ`, `K`(켈빈)/`k` 는 같게
+//!   취급하지만, 여러 글자로 바뀌는 완전(full) 접기인 `ß`/`SS` 는 같게 보지 않습니다.
+//!   튀르키예어 전용 규칙도 적용하지 않으므로 `I` 는 `i` 로 접히고, 점 없는 `ı` 와
+//!   점 있는 `İ` 는 각자 그대로 남습니다.
+//! - 단어 문자는 UTS #18 의 `\w` 정의(문자, 숫자, 결합 문자, 연결 구두점, ZWJ/ZWNJ)를 따릅니다.
+//!
+//! 모든 함수는 문자 하나 단위로 동작하며 힙 할당을 하지 않습니다.
+
+/// `char::to_lowercase` 만으로는 얻을 수 없는 단순 접기 쌍 `(원래 문자, 접힌 문자)`.
+/// 소문자 변환 결과가 같더라도 역방향 조회([`case_variants`])에 필요한 문자는 함께 둡니다.
+/// 첫 번째 값 기준으로 정렬되어 있습니다.
+const FOLD_EXCEPTIONS: &[(char, char)] = &[
+    ('\u{00B5}', '\u{03BC}'), // µ MICRO SIGN → μ
+    ('\u{017F}', 's'),        // ſ LONG S
+    ('\u{0345}', '\u{03B9}'), // COMBINING GREEK YPOGEGRAMMENI → ι
+    ('\u{03C2}', '\u{03C3}'), // ς FINAL SIGMA → σ
+    ('\u{03D0}', '\u{03B2}'), // ϐ → β
+    ('\u{03D1}', '\u{03B8}'), // ϑ → θ
+    ('\u{03D5}', '\u{03C6}'), // ϕ → φ
+    ('\u{03D6}', '\u{03C0}'), // ϖ → π
+    ('\u{03F0}', '\u{03BA}'), // ϰ → κ
+    ('\u{03F1}', '\u{03C1}'), // ϱ → ρ
+    ('\u{03F4}', '\u{03B8}'), // ϴ → θ
+    ('\u{03F5}', '\u{03B5}'), // ϵ → ε
+    ('\u{1C80}', '\u{0432}'), // ᲀ → в
+    ('\u{1C81}', '\u{0434}'), // ᲁ → д
+    ('\u{1C82}', '\u{043E}'), // ᲂ → о
+    ('\u{1C83}', '\u{0441}'), // ᲃ → с
+    ('\u{1C84}', '\u{0442}'), // ᲄ → т
+    ('\u{1C85}', '\u{0442}'), // ᲅ → т
+    ('\u{1C86}', '\u{044A}'), // ᲆ → ъ
+    ('\u{1C87}', '\u{0463}'), // ᲇ → ѣ
+    ('\u{1C88}', '\u{A64B}'), // ᲈ → ꙋ
+    ('\u{1E9B}', '\u{1E61}'), // ẛ → ṡ
+    ('\u{1E9E}', '\u{00DF}'), // ẞ CAPITAL SHARP S → ß
+    ('\u{1FBE}', '\u{03B9}'), // ι GREEK PROSGEGRAMMENI → ι
+    ('\u{2126}', '\u{03C9}'), // Ω OHM SIGN → ω
+    ('\u{212A}', 'k'),        // K KELVIN SIGN
+    ('\u{212B}', '\u{00E5}'), // Å ANGSTROM SIGN → å
+];
+
+/// 결합 문자(Mn, Mc, Me) 가운데 `char::is_alphanumeric` 에 포함되지 않는 주요 범위.
+/// 인도계 문자의 모음 기호처럼 Alphabetic 속성을 가진 결합 문자는 이미 포함되므로 제외했습니다.
+const MARKS: &[(char, char)] = &[
+    ('\u{0300}', '\u{036F}'), // Combining Diacritical Marks
+    ('\u{0483}', '\u{0489}'), // Cyrillic
+    ('\u{0591}', '\u{05BD}'), // Hebrew
+    ('\u{05BF}', '\u{05BF}'),
+    ('\u{05C1}', '\u{05C2}'),
+    ('\u{05C4}', '\u{05C5}'),
+    ('\u{05C7}', '\u{05C7}'),
+    ('\u{0610}', '\u{061A}'), // Arabic
+    ('\u{064B}', '\u{065F}'),
+    ('\u{0670}', '\u{0670}'),
+    ('\u{06D6}', '\u{06DC}'),
+    ('\u{06DF}', '\u{06E4}'),
+    ('\u{06E7}', '\u{06E8}'),
+    ('\u{06EA}', '\u{06ED}'),
+    ('\u{093C}', '\u{093C}'), // Devanagari nukta, virama, stress
+    ('\u{094D}', '\u{094D}'),
+    ('\u{0951}', '\u{0954}'),
+    ('\u{09BC}', '\u{09BC}'), // Bengali ~ Malayalam nukta/virama
+    ('\u{09CD}', '\u{09CD}'),
+    ('\u{0A3C}', '\u{0A3C}'),
+    ('\u{0A4D}', '\u{0A4D}'),
+    ('\u{0ABC}', '\u{0ABC}'),
+    ('\u{0ACD}', '\u{0ACD}'),
+    ('\u{0B3C}', '\u{0B3C}'),
+    ('\u{0B4D}', '\u{0B4D}'),
+    ('\u{0BCD}', '\u{0BCD}'),
+    ('\u{0C4D}', '\u{0C4D}'),
+    ('\u{0CBC}', '\u{0CBC}'),
+    ('\u{0CCD}', '\u{0CCD}'),
+    ('\u{0D4D}', '\u{0D4D}'),
+    ('\u{0E47}', '\u{0E4E}'),   // Thai
+    ('\u{0EC8}', '\u{0ECE}'),   // Lao
+    ('\u{1AB0}', '\u{1AFF}'),   // Combining Diacritical Marks Extended
+    ('\u{1DC0}', '\u{1DFF}'),   // Combining Diacritical Marks Supplement
+    ('\u{20D0}', '\u{20FF}'),   // Combining Diacritical Marks for Symbols
+    ('\u{302A}', '\u{302F}'),   // 한자 성조 기호, 한글 방점 (〮 〯)
+    ('\u{3099}', '\u{309A}'),   // 가나 탁점/반탁점
+    ('\u{FE00}', '\u{FE0F}'),   // Variation Selectors
+    ('\u{FE20}', '\u{FE2F}'),   // Combining Half Marks
+    ('\u{E0100}', '\u{E01EF}'), // Variation Selectors Supplement
+];
+
+/// 연결 구두점(Pc)과 결합 제어 문자(ZWNJ, ZWJ)
+const CONNECTORS: &[(char, char)] = &[
+    ('_', '_'),
+    ('\u{200C}', '\u{200D}'),
+    ('\u{203F}', '\u{2040}'),
+    ('\u{2054}', '\u{2054}'),
+    ('\u{FE33}', '\u{FE34}'),
+    ('\u{FE4D}', '\u{FE4F}'),
+    ('\u{FF3F}', '\u{FF3F}'),
+];
+
+/// 문자를 단순 대소문자 접기 규칙으로 접습니다.
+/// 접은 결과가 같은 두 문자는 대소문자를 무시했을 때 같은 문자입니다.
+pub fn simple_fold(c: char) -> char {
+    if c.is_ascii() {
+        return c.to_ascii_lowercase();
+    }
+    if let Ok(i) = FOLD_EXCEPTIONS.binary_search_by_key(&c, |&(from, _)| from) {
+        return FOLD_EXCEPTIONS[i].1;
+    }
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        // `İ` 처럼 여러 글자로 바뀌는 문자는 단순 접기 대상이 아닙니다.
+        _ => c,
+    }
+}
+
+/// 대소문자를 무시했을 때 `c` 와 같은 문자들 (`c` 자신 포함, 중복이 있을 수 있음).
+/// 문자 클래스처럼 "같은 문자 중 하나라도 포함되는가" 를 확인할 때 사용합니다.
+pub fn case_variants(c: char) -> impl Iterator<Item = char> {
+    let folded = simple_fold(c);
+    let mut upper = folded.to_uppercase();
+    let upper = match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => folded,
+    };
+    [c, folded, upper].into_iter().chain(
+        FOLD_EXCEPTIONS
+            .iter()
+            .filter(move |&&(_, to)| to == folded)
+            .map(|&(from, _)| from),
+    )
+}
+
+/// 대소문자를 무시하고 `haystack` 에 `folded_needle` 이 들어 있는지 확인합니다.
+/// `folded_needle` 은 [`simple_fold`] 로 미리 접어 둔 문자들이어야 합니다.
+pub fn contains_folded(haystack: &str, folded_needle: &[char]) -> bool {
+    if folded_needle.is_empty() {
+        return true;
+    }
+    haystack.char_indices().any(|(start, _)| {
+        let mut rest = haystack[start..].chars();
+        folded_needle
+            .iter()
+            .all(|&expected| rest.next().is_some_and(|c| simple_fold(c) == expected))
+    })
+}
+
+/// 유니코드 단어 문자인지 확인합니다 (`\w`, `\b`, `-w` 가 사용).
+pub fn is_word_char(c: char) -> bool {
+    if c.is_ascii() {
+        return c.is_ascii_alphanumeric() || c == '_';
+    }
+    c.is_alphanumeric() || in_ranges(MARKS, c) || in_ranges(CONNECTORS, c)
+}
+
+fn in_ranges(ranges: &[(char, char)], c: char) -> bool {
+    ranges
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                std::cmp::Ordering::Less
+            } else if lo > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
@@ -0,0 +1,243 @@
+//! 디렉토리 순회
+//!
+//! 커맨드라인에 주어진 파일과 디렉토리를 재귀적으로 펼쳐서
+//! 검색할 파일 목록을 만듭니다. 결과는 항상 경로 순으로 정렬됩니다.
+//!
+//! [`collect_files_with`] 는 [`WalkOptions`] 에 따라 숨김 파일, `.gitignore` / `.ignore`,
+//! `--glob`, `--type` 규칙을 적용합니다. 명령행에 직접 준 파일은 규칙과 상관없이 포함합니다.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::ignore::{self, FileTypes, Gitignore, Match, Overrides};
+
+/// 디렉토리마다 읽는 무시 파일. 같은 디렉토리에서는 뒤쪽(`.ignore`)이 우선합니다.
+pub const IGNORE_FILES: &[&str] = &[".gitignore", ".ignore"];
+
+/// 바이너리 판별을 위해 앞부분에서 살펴볼 바이트 수
+pub const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+/// 디렉토리 순회 규칙. 기본값은 숨김 파일과 무시 파일에 적힌 경로를 건너뜁니다.
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    /// 숨김 파일과 디렉토리(`.` 으로 시작)도 포함합니다 (`--hidden`).
+    pub hidden: bool,
+    /// `.gitignore`, `.ignore` 를 읽지 않습니다 (`--no-ignore`).
+    pub no_ignore: bool,
+    /// `--glob` 규칙
+    pub overrides: Overrides,
+    /// `--type` / `--type-not` 규칙
+    pub types: FileTypes,
+}
+
+impl WalkOptions {
+    /// 아무것도 건너뛰지 않는 규칙
+    pub fn all() -> WalkOptions {
+        WalkOptions {
+            hidden: true,
+            no_ignore: true,
+            ..WalkOptions::default()
+        }
+    }
+}
+
+/// 주어진 경로들을 펼쳐서 검색 대상 파일 목록을 반환합니다.
+///
+/// - 파일은 그대로 포함합니다.
+/// - 디렉토리는 재귀적으로 순회하며, 각 디렉토리 안의 항목은 이름 순으로 방문합니다.
+/// - 디렉토리 안의 심볼릭 링크는 따라가지 않습니다 (`grep -r` 과 동일).
+/// - 숨김 파일이나 무시 파일도 건너뛰지 않습니다. 건너뛰려면 [`collect_files_with`] 를 사용합니다.
+pub fn collect_files<P: AsRef<Path>>(paths: &[P]) -> io::Result<Vec<PathBuf>> {
+    collect_files_with(paths, &WalkOptions::all())
+}
+
+/// [`collect_files`] 와 같지만 `options` 의 규칙에 맞지 않는 경로는 건너뜁니다.
+pub fn collect_files_with<P: AsRef<Path>>(
+    paths: &[P],
+    options: &WalkOptions,
+) -> io::Result<Vec<PathBuf>> {
+    collect_files_reporting(paths, options, Err)
+}
+
+/// [`collect_files_with`] 와 같지만 없거나 읽을 수 없는 경로의 에러를 `on_error` 에 넘깁니다.
+/// `on_error` 가 `Ok` 를 돌려주면 `grep -r` 처럼 그 경로만 건너뛰고 나머지를 계속 펼칩니다.
+pub fn collect_files_reporting<P, F>(
+    paths: &[P],
+    options: &WalkOptions,
+    mut on_error: F,
+) -> io::Result<Vec<PathBuf>>
+where
+    P: AsRef<Path>,
+    F: FnMut(io::Error) -> io::Result<()>,
+{
+    let mut files = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                on_error(with_path(e, path))?;
+                continue;
+            }
+        };
+        if metadata.is_dir() {
+            match Walker::new(path, options) {
+                Ok(mut walker) => walker.walk_dir(path, &mut files, &mut on_error)?,
+                Err(e) => on_error(e)?,
+            }
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+/// 검색 루트 하나를 순회하는 동안의 상태
+struct Walker<'a> {
+    options: &'a WalkOptions,
+    root: &'a Path,
+    /// 무시 파일의 위치를 비교하기 위한 루트의 절대 경로
+    absolute_root: PathBuf,
+    /// 루트에서 현재 디렉토리까지 읽은 무시 파일들 (얕은 것부터)
+    ignores: Vec<Gitignore>,
+}
+
+impl<'a> Walker<'a> {
+    fn new(root: &'a Path, options: &'a WalkOptions) -> io::Result<Walker<'a>> {
+        let absolute_root = fs::canonicalize(root).map_err(|e| with_path(e, root))?;
+        let mut walker = Walker {
+            options,
+            root,
+            absolute_root,
+            ignores: Vec::new(),
+        };
+        if !options.no_ignore {
+            walker.load_parent_ignores()?;
+        }
+        Ok(walker)
+    }
+
+    /// 루트가 저장소 안쪽 디렉토리일 때를 위해 저장소 최상위(`.git` 이 있는 곳)까지의
+    /// 상위 디렉토리 무시 파일도 읽습니다.
+    fn load_parent_ignores(&mut self) -> io::Result<()> {
+        let mut parents = Vec::new();
+        for dir in self.absolute_root.ancestors().skip(1) {
+            parents.push(dir.to_path_buf());
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        if !self.absolute_root.join(".git").exists()
+            && parents.last().is_some_and(|top| top.join(".git").exists())
+        {
+            for dir in parents.iter().rev() {
+                self.push_ignores(dir)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn push_ignores(&mut self, dir: &Path) -> io::Result<()> {
+        for name in IGNORE_FILES {
+            if let Some(gitignore) =
+                Gitignore::from_dir(dir, name).map_err(|e| with_path(e, dir))?
+            {
+                self.ignores.push(gitignore);
+            }
+        }
+        Ok(())
+    }
+
+    /// `dir` 아래의 파일을 `files` 에 모읍니다. 읽을 수 없는 항목의 에러는 `on_error` 에 넘기고,
+    /// `on_error` 가 `Ok` 를 돌려주면 그 항목만 건너뜁니다.
+    fn walk_dir(
+        &mut self,
+        dir: &Path,
+        files: &mut Vec<PathBuf>,
+        on_error: &mut dyn FnMut(io::Error) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let entries = fs::read_dir(dir).and_then(|entries| entries.collect::<io::Result<Vec<_>>>());
+        let mut entries = match entries {
+            Ok(entries) => entries,
+            Err(e) => return on_error(with_path(e, dir)),
+        };
+        entries.sort_by_key(|entry| entry.file_name());
+
+        let relative_dir = dir.strip_prefix(self.root).unwrap_or(Path::new(""));
+        // 무시 파일을 읽다 실패해도 이미 읽은 것까지 이 디렉토리를 벗어날 때 걷어냅니다.
+        let depth = self.ignores.len();
+        if !self.options.no_ignore {
+            if let Err(e) = self.push_ignores(&self.absolute_root.join(relative_dir)) {
+                on_error(e)?;
+            }
+        }
+
+        for entry in entries {
+            let path = entry.path();
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(e) => {
+                    on_error(with_path(e, &path))?;
+                    continue;
+                }
+            };
+            let is_dir = file_type.is_dir();
+            if !is_dir && !file_type.is_file() {
+                continue;
+            }
+            if self.skip(&path, is_dir) {
+                continue;
+            }
+            if is_dir {
+                self.walk_dir(&path, files, on_error)?;
+            } else {
+                files.push(path);
+            }
+        }
+        self.ignores.truncate(depth);
+        Ok(())
+    }
+
+    /// 루트 아래의 `path` 를 건너뛸지 결정합니다.
+    fn skip(&self, path: &Path, is_dir: bool) -> bool {
+        let relative = path.strip_prefix(self.root).unwrap_or(path);
+        let overridden = self
+            .options
+            .overrides
+            .matched(&ignore::slash_path(relative), is_dir);
+        if overridden != Match::None {
+            return overridden.is_ignore();
+        }
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if !self.options.hidden && name.starts_with('.') {
+            return true;
+        }
+
+        let absolute = self.absolute_root.join(relative);
+        // 깊은 무시 파일이 얕은 것보다 우선합니다.
+        let ignored = self
+            .ignores
+            .iter()
+            .rev()
+            .map(|gitignore| gitignore.matched(&absolute, is_dir))
+            .find(|m| *m != Match::None)
+            .unwrap_or(Match::None);
+        if ignored.is_ignore() {
+            return true;
+        }
+
+        !is_dir && self.options.types.matched(&name).is_ignore()
+    }
+}
+
+/// 앞부분에 NUL 바이트가 있으면 바이너리 파일로 간주합니다.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_SNIFF_LEN).any(|&b| b == 0)
+}
+
+/// io 에러 메시지에 문제가 된 경로를 붙입니다.
+fn with_path(err: io::Error, path: &Path) -> io::Error {
+    io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+}
@@ -0,0 +1,292 @@
+//! 압축 파일(`.gz`)과 tar 아카이브 검색 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test archive_tests
+//! ```
+
+use minigrep::gzip::{self, GzDecoder, InflateError};
+use minigrep::tar;
+use std::fs;
+use std::io::{self, BufReader, Read};
+use std::process::{Command, Output};
+
+fn minigrep(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// `access.log.gz` 를 만들 때 쓴 원문. 앞 절반은 동적 허프만, 뒤 절반은 비압축 블록 멤버입니다.
+fn access_log() -> String {
+    (0..1200)
+        .map(|i| {
+            let status = if i % 13 == 0 { 500 } else { 200 };
+            format!("{i:05} GET /item/{} {status}\n", i * 7919 % 1000)
+        })
+        .collect()
+}
+
+#[test]
+fn test_crc32_check_value() {
+    assert_eq!(gzip::crc32(b"123456789"), 0xcbf4_3926);
+    assert_eq!(gzip::crc32(b""), 0);
+}
+
+#[test]
+fn test_inflate_fixed_huffman_block() {
+    // zlib 으로 만든 raw DEFLATE: 고정 허프만 부호와 겹치는 거리 복사를 씁니다.
+    let compressed = [203, 72, 205, 201, 201, 87, 200, 64, 39, 21, 1];
+    assert_eq!(
+        gzip::inflate(&compressed).unwrap(),
+        b"hello hello hello hello!"
+    );
+    assert_eq!(
+        gzip::inflate(&compressed[..5]),
+        Err(InflateError::UnexpectedEof)
+    );
+    // 블록 종류 3 은 예약되어 있습니다.
+    assert_eq!(gzip::inflate(&[0b111]), Err(InflateError::InvalidBlockType));
+}
+
+#[test]
+fn test_gunzip_dynamic_and_stored_members() {
+    let compressed = fs::read("tests/fixtures/access.log.gz").unwrap();
+    let text = gzip::gunzip(&compressed).unwrap();
+    assert_eq!(String::from_utf8(text).unwrap(), access_log());
+
+    let poem = fs::read("tests/fixtures/poem.txt").unwrap();
+    let compressed = fs::read("tests/fixtures/poem.txt.gz").unwrap();
+    assert_eq!(gzip::gunzip(&compressed).unwrap(), poem);
+}
+
+#[test]
+fn test_gunzip_detects_corruption() {
+    let mut compressed = fs::read("tests/fixtures/poem.txt.gz").unwrap();
+    let len = compressed.len();
+    // 마지막 멤버의 CRC-32 를 망가뜨립니다.
+    compressed[len - 8] ^= 0xff;
+    assert_eq!(
+        gzip::gunzip(&compressed),
+        Err(InflateError::ChecksumMismatch)
+    );
+    compressed[len - 8] ^= 0xff;
+    compressed[len - 4] ^= 0xff;
+    assert_eq!(gzip::gunzip(&compressed), Err(InflateError::SizeMismatch));
+    assert_eq!(
+        gzip::gunzip(&compressed[..len - 3]),
+        Err(InflateError::UnexpectedEof)
+    );
+    assert_eq!(
+        gzip::gunzip(b"\x1f\x8b\x07\0\0\0\0\0\0\0"),
+        Err(InflateError::InvalidHeader)
+    );
+}
+
+#[test]
+fn test_gz_decoder_streams_in_small_reads() {
+    // 입력을 몇 바이트씩만 주고 출력도 조금씩 가져가도 한 번에 푼 결과와 같아야 합니다.
+    for fixture in ["tests/fixtures/access.log.gz", "tests/fixtures/poem.txt.gz"] {
+        let compressed = fs::read(fixture).unwrap();
+        let mut decoder = GzDecoder::new(BufReader::with_capacity(3, compressed.as_slice()));
+        let mut streamed = Vec::new();
+        let mut buf = [0u8; 7];
+        loop {
+            let n = decoder.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            streamed.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(streamed, gzip::gunzip(&compressed).unwrap());
+    }
+
+    // 깨진 데이터는 `InvalidData` 에러로 알립니다.
+    let mut compressed = fs::read("tests/fixtures/poem.txt.gz").unwrap();
+    let len = compressed.len();
+    compressed[len - 8] ^= 0xff;
+    let e = GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut Vec::new())
+        .unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(e.to_string(), InflateError::ChecksumMismatch.to_string());
+}
+
+#[test]
+fn test_tar_archive_reads_members_lazily() {
+    let archive = gzip::gunzip(&fs::read("tests/fixtures/logs.tar.gz").unwrap()).unwrap();
+    let expected = tar::entries(&archive).unwrap();
+
+    let mut reader = tar::Archive::new(archive.as_slice());
+    let mut found = Vec::new();
+    while let Some(mut member) = reader.next_member().unwrap() {
+        // 첫 멤버는 읽지 않고 넘어가도 다음 멤버를 찾아야 합니다.
+        let mut data = Vec::new();
+        if !found.is_empty() {
+            member.read_to_end(&mut data).unwrap();
+        }
+        found.push((member.path, data));
+    }
+    let paths: Vec<&str> = found.iter().map(|(path, _)| path.as_str()).collect();
+    let expected_paths: Vec<&str> = expected.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, expected_paths);
+    for ((_, data), entry) in found.iter().zip(&expected).skip(1) {
+        assert_eq!(data, entry.data);
+    }
+
+    // 첫 멤버를 꺼낼 때는 그 멤버의 헤더까지만 읽습니다.
+    let mut remaining = archive.as_slice();
+    let first = tar::Archive::new(&mut remaining)
+        .next_member()
+        .unwrap()
+        .map(|member| member.path);
+    assert_eq!(first.as_deref(), Some("app/server.log"));
+    assert!(archive.len() - remaining.len() < 4096);
+
+    // 잘린 아카이브는 `TarError` 를 담은 `InvalidData` 에러입니다.
+    let mut truncated = tar::Archive::new(&archive[..1000]);
+    let e = loop {
+        match truncated.next_member() {
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("truncated archive ended cleanly"),
+            Err(e) => break e,
+        }
+    };
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(e.to_string(), tar::TarError::UnexpectedEof.to_string());
+}
+
+#[test]
+fn test_tar_entries() {
+    let archive = gzip::gunzip(&fs::read("tests/fixtures/logs.tar.gz").unwrap()).unwrap();
+    assert!(tar::is_tar(&archive));
+    let entries = tar::entries(&archive).unwrap();
+    let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+    // 디렉토리는 건너뛰고, 100 바이트가 넘는 경로는 pax 헤더에서 가져옵니다.
+    assert_eq!(
+        paths,
+        vec![
+            "app/server.log".to_string(),
+            "app/rotated.log.gz".to_string(),
+            format!("app/{}deep.log", "nested/".repeat(20)),
+        ]
+    );
+    assert_eq!(
+        entries[0].data,
+        b"INFO started\nERROR disk full\nINFO stopped\n"
+    );
+
+    let mut corrupted = archive.clone();
+    corrupted[0] ^= 1;
+    assert!(matches!(
+        tar::entries(&corrupted),
+        Err(tar::TarError::BadChecksum(0))
+    ));
+    assert_eq!(
+        tar::entries(&archive[..1000]),
+        Err(tar::TarError::UnexpectedEof)
+    );
+}
+
+#[test]
+fn test_cli_searches_gzip_files() {
+    let found = stdout(minigrep(&["-n", " 500", "tests/fixtures/access.log.gz"]));
+    let expected: Vec<String> = access_log()
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.contains(" 500"))
+        .map(|(i, line)| format!("{}:{line}", i + 1))
+        .collect();
+    assert_eq!(found.lines().collect::<Vec<_>>(), expected);
+
+    // 표준 입력으로 들어온 gzip 도 풉니다.
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-c", "nobody", "-"])
+        .stdin(fs::File::open("tests/fixtures/poem.txt.gz").unwrap())
+        .output()
+        .unwrap();
+    assert_eq!(stdout(output), "2\n");
+}
+
+#[test]
+fn test_cli_searches_tar_members() {
+    let found = stdout(minigrep(&["-n", "ERROR", "tests/fixtures/logs.tar.gz"]));
+    let archive = "tests/fixtures/logs.tar.gz";
+    assert_eq!(
+        found.lines().collect::<Vec<_>>(),
+        vec![
+            format!("{archive}!app/server.log:2:ERROR disk full"),
+            format!("{archive}!app/rotated.log.gz:1:ERROR old failure"),
+            format!(
+                "{archive}!app/{}deep.log:1:ERROR deep in the tree",
+                "nested/".repeat(20)
+            ),
+        ]
+    );
+    let listed = stdout(minigrep(&["-l", "disk", "tests/fixtures/logs.tar.gz"]));
+    assert_eq!(listed, format!("{archive}!app/server.log\n"));
+}
+
+#[test]
+fn test_cli_skips_truncated_gzip_and_keeps_searching() {
+    let dir = std::env::temp_dir().join(format!("minigrep-truncated-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.txt"), "nobody here\n").unwrap();
+    fs::copy("tests/fixtures/poem.txt.gz", dir.join("b.gz")).unwrap();
+    let compressed = fs::read("tests/fixtures/access.log.gz").unwrap();
+    fs::write(dir.join("c.gz"), &compressed[..compressed.len() / 2]).unwrap();
+    fs::write(dir.join("d.txt"), "nobody there\n").unwrap();
+
+    for threads in ["1", "4"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+            .args(["--threads", threads, "-c", "nobody"])
+            .arg(&dir)
+            .output()
+            .unwrap();
+        // 깨진 파일은 알리고 건너뛰며, 끝까지 검색한 뒤 종료 코드 2 로 끝납니다.
+        assert_eq!(output.status.code(), Some(2), "{output:?}");
+        let path = |name: &str| dir.join(name).display().to_string();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            format!(
+                "{}:1\n{}:2\n{}:1\n",
+                path("a.txt"),
+                path("b.gz"),
+                path("d.txt")
+            )
+        );
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            format!(
+                "minigrep: {}: {}\n",
+                path("c.gz"),
+                InflateError::UnexpectedEof
+            )
+        );
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cli_refuses_to_write_compressed_inputs() {
+    let path = std::env::temp_dir().join(format!("minigrep-write-{}.gz", std::process::id()));
+    fs::copy("tests/fixtures/poem.txt.gz", &path).unwrap();
+    let before = fs::read(&path).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-r", "anybody", "--write", "nobody"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("cannot write"));
+    assert_eq!(fs::read(&path).unwrap(), before);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,130 @@
+//! GNU 스타일 인자 파서 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test args_tests
+//! ```
+
+use minigrep::*;
+use std::process::{Command, Stdio};
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+#[test]
+fn test_long_and_short_flags() {
+    let config = build(&["-i", "--line-number", "-v", "error", "a.log"]).unwrap();
+    assert!(!config.case_sensitive);
+    assert!(config.line_number);
+    assert!(config.invert_match);
+    assert_eq!(config.query, "error");
+    assert_eq!(config.paths, vec!["a.log"]);
+}
+
+#[test]
+fn test_combined_short_flags() {
+    let config = build(&["-inwcl", "error", "a.log"]).unwrap();
+    assert!(!config.case_sensitive);
+    assert!(config.line_number);
+    assert!(config.word_regexp);
+    assert!(config.count);
+    assert!(config.files_with_matches);
+}
+
+#[test]
+fn test_context_values() {
+    let config = build(&["-A", "2", "-B3", "error", "a.log"]).unwrap();
+    assert_eq!((config.before_context, config.after_context), (3, 2));
+
+    let config = build(&["-nC1", "error", "a.log"]).unwrap();
+    assert!(config.line_number);
+    assert_eq!((config.before_context, config.after_context), (1, 1));
+
+    let config = build(&["--context=4", "error", "a.log"]).unwrap();
+    assert_eq!((config.before_context, config.after_context), (4, 4));
+}
+
+#[test]
+fn test_double_dash_ends_options() {
+    let config = build(&["-n", "--", "-v", "-"]).unwrap();
+    assert!(config.line_number);
+    assert!(!config.invert_match);
+    assert_eq!(config.query, "-v");
+    assert_eq!(config.paths, vec!["-"]);
+}
+
+#[test]
+fn test_options_after_positionals() {
+    let config = build(&["error", "a.log", "-i", "b.log"]).unwrap();
+    assert!(!config.case_sensitive);
+    assert_eq!(config.paths, vec!["a.log", "b.log"]);
+}
+
+#[test]
+fn test_typed_errors() {
+    assert_eq!(
+        build(&["-x", "error", "a.log"]).unwrap_err(),
+        ConfigError::UnknownOption(String::from("-x"))
+    );
+    assert_eq!(
+        build(&["error", "a.log", "-A"]).unwrap_err(),
+        ConfigError::MissingValue(String::from("-A"))
+    );
+    assert_eq!(
+        build(&["--count=yes", "error", "a.log"]).unwrap_err(),
+        ConfigError::UnexpectedValue(String::from("--count"))
+    );
+    assert_eq!(
+        build(&["-C", "many", "error", "a.log"]).unwrap_err(),
+        ConfigError::InvalidValue {
+            option: String::from("--context"),
+            value: String::from("many"),
+        }
+    );
+    assert_eq!(build(&[]).unwrap_err(), ConfigError::NotEnoughArguments);
+    // PATH 가 없으면 표준 입력을 읽습니다.
+    assert_eq!(build(&["error"]).unwrap().paths, vec!["-"]);
+}
+
+#[test]
+fn test_help() {
+    let err = build(&["--help"]).unwrap_err();
+    assert_eq!(err, ConfigError::Help);
+    assert!(err.to_string().starts_with("Usage: minigrep"));
+    assert_eq!(
+        build(&["-h", "error", "a.log"]).unwrap_err(),
+        ConfigError::Help
+    );
+}
+
+#[test]
+fn test_cli_help_with_closed_stdout() {
+    // `minigrep --help | head -1` 처럼 읽는 쪽이 먼저 닫혀도 패닉하지 않고 성공으로 끝납니다.
+    let mut child = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .arg("--help")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    assert!(output.stderr.is_empty(), "{output:?}");
+}
+
+#[test]
+fn test_line_match_styled_format() {
+    let m = LineMatch::new(7, "error here").with_path("a.log");
+    let style = OutputStyle {
+        with_path: true,
+        line_number: true,
+        ..OutputStyle::default()
+    };
+    assert_eq!(m.format_styled(&style), "a.log:7:error here");
+    assert_eq!(m.format_styled(&OutputStyle::default()), "error here");
+}
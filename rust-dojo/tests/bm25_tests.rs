@@ -0,0 +1,224 @@
+//! BM25 랭킹 검색(`minigrep rank`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test bm25_tests
+//! ```
+
+use minigrep::bm25::{self, Index};
+use minigrep::*;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("minigrep-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// `bm25-dojo` 의 공식을 그대로 옮긴 점수 (IDF 만 Lucene 방식)
+fn expected_score(index: &Index, term: &str, doc: usize, params: &Bm25Params) -> f64 {
+    let n = index.len() as f64;
+    let df = index.document_frequency(term) as f64;
+    let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+    let tf = index.term_frequency(term, doc) as f64;
+    let length = index.doc_length(doc) as f64 / index.avgdl();
+    idf * tf * (params.k1 + 1.0) / (tf + params.k1 * (1.0 - params.b + params.b * length))
+}
+
+#[test]
+fn test_tokenize_lowercases_unicode_words() {
+    assert_eq!(
+        bm25::tokenize("The quick-brown FOX, 서울특별시 2024!"),
+        vec!["the", "quick", "brown", "fox", "서울특별시", "2024"]
+    );
+    assert!(bm25::tokenize(" -- ").is_empty());
+}
+
+#[test]
+fn test_index_statistics() {
+    let index = Index::from_documents(["the quick brown fox", "the lazy dog", "quick brown"]);
+    assert_eq!(index.len(), 3);
+    assert_eq!(
+        (0..3).map(|doc| index.doc_length(doc)).collect::<Vec<_>>(),
+        vec![4, 3, 2]
+    );
+    assert!((index.avgdl() - 3.0).abs() < 1e-9);
+    assert_eq!(index.document_frequency("quick"), 2);
+    assert_eq!(index.term_frequency("quick", 2), 1);
+    assert_eq!(index.term_frequency("quick", 1), 0);
+    assert_eq!(index.idf("missing"), 0.0);
+    // 드문 단어일수록 IDF 가 큽니다.
+    assert!(index.idf("lazy") > index.idf("the"));
+}
+
+#[test]
+fn test_score_matches_formula() {
+    let index = Index::from_documents([
+        "rust rust rust memory safety",
+        "python scripting",
+        "rust ownership and borrowing in rust programs with lifetimes",
+    ]);
+    for params in [
+        Bm25Params::default(),
+        Bm25Params { k1: 0.5, b: 0.0 },
+        Bm25Params { k1: 2.0, b: 1.0 },
+    ] {
+        for doc in 0..index.len() {
+            let expected = expected_score(&index, "rust", doc, &params)
+                + expected_score(&index, "memory", doc, &params);
+            // 질의에 같은 단어가 반복돼도 한 번만 셉니다.
+            let score = index.score("Rust memory rust", doc, &params);
+            assert!((score - expected).abs() < 1e-9, "{params:?} doc {doc}");
+        }
+    }
+}
+
+#[test]
+fn test_search_ranks_by_score() {
+    let index = Index::from_documents([
+        "a long document that mentions cats once among many other unrelated words here",
+        "dogs only",
+        "cats cats cats",
+        "cats and dogs",
+    ]);
+    let ranked: Vec<usize> = index
+        .search("cats", &Bm25Params::default())
+        .iter()
+        .map(|scored| scored.doc)
+        .collect();
+    // 질의 단어가 없는 문서는 결과에 없습니다.
+    assert_eq!(ranked, vec![2, 3, 0]);
+
+    // b = 0 이면 길이 보정이 없으므로 빈도가 같은 문서끼리는 점수가 같고, 색인 순서를 따릅니다.
+    let flat = Bm25Params { k1: 1.5, b: 0.0 };
+    let ranked = index.search("cats", &flat);
+    assert_eq!(ranked[1].doc, 0);
+    assert!((ranked[1].score - ranked[2].score).abs() < 1e-12);
+}
+
+#[test]
+fn test_paragraphs_split_on_blank_lines() {
+    let text = "first line\nsecond line\n\n  \nthird\r\n\nfourth\n";
+    let paragraphs = bm25::paragraphs(text);
+    let found: Vec<(std::ops::RangeInclusive<usize>, &str)> = paragraphs
+        .iter()
+        .map(|p| (p.lines.clone(), &text[p.range.clone()]))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (1..=2, "first line\nsecond line"),
+            (5..=5, "third"),
+            (7..=7, "fourth"),
+        ]
+    );
+}
+
+#[test]
+fn test_rank_corpus_paragraphs() {
+    let corpus = vec![
+        (
+            String::from("a.md"),
+            String::from("intro text\n\nBM25 ranks documents.\nBM25 uses idf.\n"),
+        ),
+        (String::from("b.md"), String::from("nothing relevant\n")),
+    ];
+    let options = RankOptions {
+        unit: RankUnit::Paragraph,
+        ..RankOptions::default()
+    };
+    let results = rank_corpus("bm25", &corpus, &options);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].path, "a.md");
+    assert_eq!(results[0].lines, Some(3..=4));
+    assert_eq!(results[0].matches, vec![0..4, 22..26]);
+    let formatted = results[0].format();
+    assert!(formatted.starts_with("a.md:3-4:"));
+    assert!(formatted.ends_with("\nBM25 ranks documents.\nBM25 uses idf."));
+}
+
+#[test]
+fn test_rank_argument_parsing() {
+    let config = build(&[
+        "rank",
+        "--k1",
+        "1.2",
+        "--b=0.5",
+        "--paragraphs",
+        "query",
+        "docs/",
+    ])
+    .unwrap();
+    assert_eq!(config.query, "query");
+    assert_eq!(config.paths, vec!["docs/"]);
+    assert_eq!(
+        config.rank,
+        Some(RankOptions {
+            params: Bm25Params { k1: 1.2, b: 0.5 },
+            unit: RankUnit::Paragraph,
+            top: 10,
+        })
+    );
+    // 첫 인자가 아닌 `rank` 는 검색어입니다.
+    let config = build(&["-n", "rank", "a.txt"]).unwrap();
+    assert_eq!((config.query.as_str(), config.rank), ("rank", None));
+
+    assert!(matches!(
+        build(&["rank", "--b", "1.5", "q", "a.txt"]),
+        Err(ConfigError::InvalidValue { .. })
+    ));
+    assert!(matches!(
+        build(&["--k1", "1.2", "q", "a.txt"]),
+        Err(ConfigError::Requires { .. })
+    ));
+    assert!(matches!(
+        build(&["rank", "-E", "q", "a.txt"]),
+        Err(ConfigError::Conflicts { .. })
+    ));
+}
+
+#[test]
+fn test_cli_rank_files() {
+    let dir = temp_dir("rank");
+    fs::write(dir.join("ownership.md"), "ownership ownership borrowing\n").unwrap();
+    fs::write(dir.join("closures.md"), "closures capture by borrowing\n").unwrap();
+    fs::write(dir.join("unrelated.md"), "nothing to see\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["rank", "ownership borrowing"])
+        .arg(&dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let paths: Vec<&str> = stdout
+        .lines()
+        .map(|line| line.rsplit_once(':').unwrap().0)
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            dir.join("ownership.md").to_str().unwrap(),
+            dir.join("closures.md").to_str().unwrap(),
+        ]
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["rank", "--top", "1", "ownership borrowing"])
+        .arg(&dir)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,129 @@
+//! 색상 출력(`--color`, `NO_COLOR`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test color_tests
+//! ```
+
+use minigrep::*;
+use std::ffi::OsStr;
+use std::process::Command;
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+#[test]
+fn test_color_choice_resolution() {
+    let empty = OsStr::new("");
+    let set = OsStr::new("1");
+    assert!(ColorChoice::Auto.enabled(true, None));
+    assert!(!ColorChoice::Auto.enabled(false, None));
+    assert!(!ColorChoice::Auto.enabled(true, Some(set)));
+    // 빈 `NO_COLOR` 는 설정되지 않은 것으로 봅니다.
+    assert!(ColorChoice::Auto.enabled(true, Some(empty)));
+    // 명시적인 옵션은 환경 변수보다 우선합니다.
+    assert!(ColorChoice::Always.enabled(false, Some(set)));
+    assert!(!ColorChoice::Never.enabled(true, None));
+}
+
+#[test]
+fn test_color_option_parsing() {
+    assert_eq!(build(&["error", "a.log"]).unwrap().color, ColorChoice::Auto);
+    assert_eq!(
+        build(&["--color=always", "error", "a.log"]).unwrap().color,
+        ColorChoice::Always
+    );
+    // 값이 없는 `--color` 는 다음 인자를 값으로 가져가지 않습니다.
+    let config = build(&["--color", "error", "a.log"]).unwrap();
+    assert_eq!(config.color, ColorChoice::Auto);
+    assert_eq!(config.query, "error");
+    assert_eq!(
+        build(&["--color=sometimes", "error", "a.log"]),
+        Err(ConfigError::InvalidValue {
+            option: String::from("--color"),
+            value: String::from("sometimes"),
+        })
+    );
+}
+
+#[test]
+fn test_highlighted_line() {
+    let pattern = Pattern::literal("err");
+    let line = LineMatch::with_matches(7, "err: é err", &pattern).with_path("a.log");
+    let style = OutputStyle {
+        with_path: true,
+        line_number: true,
+        ..OutputStyle::default()
+    };
+    assert_eq!(
+        line.format_colored(&style, &Palette::ANSI),
+        "\x1b[35ma.log\x1b[0m\x1b[36m:\x1b[0m\x1b[32m7\x1b[0m\x1b[36m:\x1b[0m\
+         \x1b[01;31merr\x1b[0m: é \x1b[01;31merr\x1b[0m"
+    );
+    // 색이 없으면 기존 출력과 같습니다.
+    assert_eq!(
+        line.format_colored(&style, &Palette::PLAIN),
+        line.format_styled(&style)
+    );
+}
+
+#[test]
+fn test_highlight_uses_character_offsets_for_invalid_utf8() {
+    let pattern = Pattern::literal("error");
+    let line = LineMatch::from_bytes(1, b"caf\xe9 error", Some(&pattern));
+    assert_eq!(
+        line.format_colored(&OutputStyle::default(), &Palette::ANSI),
+        "caf\u{FFFD} \x1b[01;31merror\x1b[0m"
+    );
+}
+
+#[test]
+fn test_context_blocks_color_separators() {
+    let pattern = Pattern::literal("b");
+    let blocks = search_context(&pattern, "b\nx\ny\nz\nb\n", false, 0, 1);
+    let style = OutputStyle {
+        line_number: true,
+        ..OutputStyle::default()
+    };
+    let green = |n: &str| format!("\x1b[32m{n}\x1b[0m");
+    let cyan = |s: &str| format!("\x1b[36m{s}\x1b[0m");
+    let red = |s: &str| format!("\x1b[01;31m{s}\x1b[0m");
+    assert_eq!(
+        format_results_colored(&blocks, &style, &Palette::ANSI),
+        [
+            format!("{}{}{}", green("1"), cyan(":"), red("b")),
+            format!("{}{}x", green("2"), cyan("-")),
+            cyan("--"),
+            format!("{}{}{}", green("5"), cyan(":"), red("b")),
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn test_cli_color_modes() {
+    let grep = |args: &[&str], no_color: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_minigrep"));
+        command.args(args).arg("tests/fixtures/poem.txt");
+        if no_color {
+            command.env("NO_COLOR", "1");
+        } else {
+            command.env_remove("NO_COLOR");
+        }
+        let output = command.output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    // 테스트의 표준 출력은 파이프이므로 auto 는 색을 쓰지 않습니다.
+    assert!(!grep(&["nobody"], false).contains('\x1b'));
+    assert!(grep(&["--color=always", "nobody"], false).contains("\x1b[01;31mnobody\x1b[0m"));
+    assert!(grep(&["--color=always", "nobody"], true).contains('\x1b'));
+    assert!(!grep(&["--color=never", "nobody"], false).contains('\x1b'));
+    // JSON 레코드에는 색을 넣지 않습니다.
+    assert!(!grep(&["--color=always", "--json", "nobody"], false).contains('\x1b'));
+}
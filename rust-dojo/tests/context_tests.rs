@@ -0,0 +1,91 @@
+//! 컨텍스트 라인(-A / -B / -C) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test context_tests
+//! ```
+
+use minigrep::*;
+
+const LOG: &str = "\
+boot
+ERROR disk
+  at mount
+  at init
+ok
+ok
+ok
+ERROR net
+  at connect";
+
+fn numbers(block: &ContextBlock) -> Vec<usize> {
+    block.lines.iter().map(|l| l.line().line_number).collect()
+}
+
+#[test]
+fn test_after_context() {
+    let pattern = Pattern::literal("ERROR");
+    let blocks = search_context(&pattern, LOG, false, 0, 2);
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(numbers(&blocks[0]), vec![2, 3, 4]);
+    assert_eq!(numbers(&blocks[1]), vec![8, 9]);
+    assert!(blocks[0].lines[0].is_match());
+    assert!(!blocks[0].lines[1].is_match());
+}
+
+#[test]
+fn test_before_context_clamped_at_start() {
+    let pattern = Pattern::literal("ERROR disk");
+    let blocks = search_context(&pattern, LOG, false, 5, 0);
+    assert_eq!(numbers(&blocks[0]), vec![1, 2]);
+}
+
+#[test]
+fn test_overlapping_context_is_merged() {
+    let pattern = Pattern::literal("ERROR");
+    let blocks = search_context(&pattern, LOG, false, 3, 3);
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(numbers(&blocks[0]), (1..=9).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_adjacent_context_is_merged() {
+    // 블록 [1..=4] 와 [5..=9] 는 맞닿아 있으므로 구분선 없이 합쳐집니다.
+    let pattern = Pattern::literal("ERROR");
+    let blocks = search_context(&pattern, LOG, false, 3, 2);
+    assert_eq!(blocks.len(), 1);
+}
+
+#[test]
+fn test_separator_between_groups() {
+    let pattern = Pattern::literal("ERROR");
+    let blocks: Vec<ContextBlock> = search_context(&pattern, LOG, false, 0, 1)
+        .into_iter()
+        .map(|b| b.with_path("app.log"))
+        .collect();
+    let style = OutputStyle {
+        with_path: true,
+        line_number: true,
+        ..OutputStyle::default()
+    };
+    assert_eq!(
+        format_results_styled(&blocks, &style),
+        "app.log:2:ERROR disk\napp.log-3-  at mount\n--\napp.log:8:ERROR net\napp.log-9-  at connect"
+    );
+}
+
+#[test]
+fn test_line_match_has_no_separator() {
+    let matches = vec![LineMatch::new(1, "a"), LineMatch::new(5, "b")];
+    assert_eq!(format_results(&matches), "[Line1], a\n[Line5], b");
+}
+
+#[test]
+fn test_run_with_context() {
+    let args: Vec<String> = ["minigrep", "-C", "1", "frog", "tests/fixtures/poem.txt"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let config = Config::build(&args).unwrap();
+    assert!(run(config).is_ok());
+}
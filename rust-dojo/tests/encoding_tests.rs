@@ -0,0 +1,190 @@
+//! 입력 인코딩 감지(BOM, UTF-16, EUC-KR/CP949)와 `--binary` 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test encoding_tests
+//! ```
+
+use minigrep::encoding::{self, Encoding};
+use minigrep::*;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn minigrep(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("minigrep-{}-{}", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+/// BOM 을 붙인 UTF-16 바이트
+fn utf16(text: &str, big_endian: bool) -> Vec<u8> {
+    let mut bytes = match big_endian {
+        true => vec![0xFE, 0xFF],
+        false => vec![0xFF, 0xFE],
+    };
+    for unit in text.encode_utf16() {
+        match big_endian {
+            true => bytes.extend(unit.to_be_bytes()),
+            false => bytes.extend(unit.to_le_bytes()),
+        }
+    }
+    bytes
+}
+
+/// "한글 검색\n똠방각하 漢字 ※\n" 의 CP949 바이트.
+/// `똠` 은 KS X 1001 에 없어 CP949 확장 영역(0x8C63)에 있습니다.
+const CP949_TEXT: &[u8] = b"\xC7\xD1\xB1\xDB \xB0\xCB\xBB\xF6\n\x8C\x63\xB9\xE6\xB0\xA2\xC7\xCF \xF9\xD3\xED\xAE \xA1\xD8\n";
+
+#[test]
+fn test_encoding_parse() {
+    assert_eq!(Encoding::parse("UTF-8"), Some(Encoding::Utf8));
+    assert_eq!(Encoding::parse("utf-16le"), Some(Encoding::Utf16Le));
+    assert_eq!(Encoding::parse("UTF16BE"), Some(Encoding::Utf16Be));
+    assert_eq!(Encoding::parse("euc-kr"), Some(Encoding::Cp949));
+    assert_eq!(Encoding::parse("cp949"), Some(Encoding::Cp949));
+    assert_eq!(Encoding::parse("latin1"), None);
+}
+
+#[test]
+fn test_detect_bom_utf8_and_cp949() {
+    assert_eq!(
+        encoding::sniff_bom(b"\xEF\xBB\xBFabc"),
+        Some((Encoding::Utf8, 3))
+    );
+    assert_eq!(encoding::detect(&utf16("a", false)), Encoding::Utf16Le);
+    assert_eq!(encoding::detect(&utf16("a", true)), Encoding::Utf16Be);
+    assert_eq!(encoding::detect("한글".as_bytes()), Encoding::Utf8);
+    // 앞부분이 문자 중간에서 잘려도 UTF-8 입니다.
+    assert_eq!(encoding::detect(&"한글".as_bytes()[..4]), Encoding::Utf8);
+    assert_eq!(encoding::detect(CP949_TEXT), Encoding::Cp949);
+    assert_eq!(encoding::detect(&CP949_TEXT[..3]), Encoding::Cp949);
+    // NUL 이 있으면 바이너리이므로 바꾸지 않습니다.
+    assert_eq!(encoding::detect(b"\xC7\xD1\0"), Encoding::Utf8);
+    // 어느 인코딩으로도 올바르지 않으면 UTF-8 로 둡니다.
+    assert_eq!(encoding::detect(b"\xFF\xFF\xFF"), Encoding::Utf8);
+}
+
+#[test]
+fn test_decode() {
+    assert_eq!(
+        encoding::decode(CP949_TEXT, Encoding::Cp949),
+        "한글 검색\n똠방각하 漢字 ※\n"
+    );
+    assert_eq!(
+        encoding::decode(&utf16("BOM 뒤 😀", false), Encoding::Utf16Le),
+        "BOM 뒤 😀"
+    );
+    assert_eq!(
+        encoding::decode(&utf16("big", true), Encoding::Utf16Be),
+        "big"
+    );
+    assert_eq!(
+        encoding::decode(b"\xEF\xBB\xBFtext", Encoding::Utf8),
+        "text"
+    );
+    // 잘못된 바이트는 U+FFFD 가 되고, 뒤따르는 ASCII 는 살아 있습니다.
+    assert_eq!(
+        encoding::decode(b"\xC7A\xC7", Encoding::Cp949),
+        "\u{FFFD}A\u{FFFD}"
+    );
+    assert_eq!(encoding::decode(b"a\0b", Encoding::Utf16Le), "a\u{FFFD}");
+}
+
+#[test]
+fn test_read_file_decodes_non_utf8() {
+    let path = temp_file("read-cp949", CP949_TEXT);
+    let contents = read_file_safe(path.to_str().unwrap()).unwrap();
+    assert_eq!(find_first_match(&contents, "똠"), Some("똠방각하 漢字 ※"));
+    fs::remove_file(&path).unwrap();
+
+    let path = temp_file("read-utf16", &utf16("첫 줄\n둘째 줄\n", false));
+    assert_eq!(read_file(path.to_str().unwrap()), "첫 줄\n둘째 줄\n");
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_cli_searches_detected_encodings() {
+    let cp949 = temp_file("cli-cp949", CP949_TEXT);
+    let output = minigrep(&["-n", "漢字", cp949.to_str().unwrap()]);
+    assert_eq!(stdout(output), "2:똠방각하 漢字 ※\n");
+
+    let utf16 = temp_file("cli-utf16", &utf16("alpha\n베타\ngamma\n", true));
+    let output = minigrep(&["-n", "^베타$", "-E", utf16.to_str().unwrap()]);
+    assert_eq!(stdout(output), "2:베타\n");
+
+    // BOM 은 첫 줄의 일부로 검색되지 않습니다.
+    let bom = temp_file("cli-bom", b"\xEF\xBB\xBFfirst\nsecond\n");
+    let output = minigrep(&["-E", "^first", bom.to_str().unwrap()]);
+    assert_eq!(stdout(output), "first\n");
+
+    for path in [cp949, utf16, bom] {
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_cli_explicit_encoding() {
+    // BOM 없는 UTF-16 은 NUL 때문에 바이너리로 보이므로 인코딩을 지정해야 합니다.
+    let text: Vec<u8> = "검색 대상\n"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    let path = temp_file("cli-utf16-nobom", &text);
+    let path = path.to_str().unwrap();
+    assert_eq!(stdout(minigrep(&["대상", path])), "");
+    let output = minigrep(&["--encoding", "utf-16le", "대상", path]);
+    assert_eq!(stdout(output), "검색 대상\n");
+    fs::remove_file(path).unwrap();
+
+    let output = minigrep(&["--encoding", "latin1", "x", "-"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_binary_file_matches() {
+    let path = temp_file("cli-binary", b"\x7fELF\0\0header\nneedle in binary\n");
+    let path = path.to_str().unwrap();
+    // 기본값은 바이너리 파일을 건너뜁니다.
+    assert_eq!(stdout(minigrep(&["needle", path])), "");
+    assert_eq!(
+        stdout(minigrep(&["--binary", "needle", path])),
+        format!("{path}: binary file matches\n")
+    );
+    assert_eq!(stdout(minigrep(&["--binary", "missing", path])), "");
+    assert_eq!(stdout(minigrep(&["--binary", "-c", "needle", path])), "1\n");
+    fs::remove_file(path).unwrap();
+
+    let output = minigrep(&["--binary", "--fuzzy", "1", "x", "-"]);
+    assert!(!output.status.success());
+    // 색인에는 바이너리 파일이 없으므로 `--index` 와 함께 쓰면 매치를 놓칩니다.
+    let output = minigrep(&["--binary", "--index", "needle", "."]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("'--binary' cannot be used with '--index'"));
+}
+
+#[test]
+fn test_cli_refuses_to_write_decoded_inputs() {
+    let path = temp_file("cli-write-cp949", CP949_TEXT);
+    let output = minigrep(&["-r", "표", "--write", "검색", path.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("cannot write"));
+    assert_eq!(fs::read(&path).unwrap(), CP949_TEXT);
+    fs::remove_file(&path).unwrap();
+}
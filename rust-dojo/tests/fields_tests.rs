@@ -0,0 +1,211 @@
+//! 구조화된 로그의 필드 검색(`--jsonl-field`, `--csv-column`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test fields_tests
+//! ```
+
+use minigrep::fields::{FieldQuery, FieldSelector};
+use minigrep::*;
+use std::fs;
+use std::process::Command;
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+/// 필드 값으로 찾은 내용 (이스케이프를 푼 값)
+fn field_value(query: &FieldQuery, line: &str) -> Option<String> {
+    let field = query.find_field(line.as_bytes())?;
+    Some(String::from_utf8(field.value.into_owned()).unwrap())
+}
+
+const JSONL: &str = r#"{"level":"INFO","msg":"ERROR budget ok"}
+{"level":"ERROR","msg":"disk full","req":{"path":"/api/users","status":500}}
+{"level":"error","msg":"said \"hi\"é"}
+not json ERROR
+{"level":"ERRORS","msg":"x"}
+"#;
+
+const CSV: &str = r#"ts,level,msg
+10:00,INFO,"connect timeout, retrying"
+10:01,WARN,timeout
+10:02,ERROR,"said ""timeout"""
+"#;
+
+#[test]
+fn test_parse_field_specs() {
+    let query = FieldQuery::parse_jsonl("req.status=500").unwrap();
+    assert_eq!(
+        query.selector,
+        FieldSelector::Json(vec![String::from("req"), String::from("status")])
+    );
+    assert!(query.exact);
+    assert_eq!(query.value, "500");
+
+    // 첫 `=` 나 `~` 에서 나누므로 값에는 둘 다 들어갈 수 있습니다.
+    let query = FieldQuery::parse_csv("3~a=b~c").unwrap();
+    assert_eq!(query.selector, FieldSelector::Csv(2));
+    assert!(!query.exact);
+    assert_eq!(query.value, "a=b~c");
+
+    for spec in ["level", "=x", "level=", "a..b=x"] {
+        assert_eq!(FieldQuery::parse_jsonl(spec), None, "{spec}");
+    }
+    for spec in ["0=x", "x=1", "-1~x"] {
+        assert_eq!(FieldQuery::parse_csv(spec), None, "{spec}");
+    }
+}
+
+#[test]
+fn test_find_json_fields() {
+    let lines: Vec<&str> = JSONL.lines().collect();
+    let level = FieldQuery::parse_jsonl("level=x").unwrap();
+    assert_eq!(field_value(&level, lines[1]).as_deref(), Some("ERROR"));
+    assert_eq!(field_value(&level, lines[3]), None);
+
+    // 중첩 키와 문자열이 아닌 값, 이스케이프
+    let status = FieldQuery::parse_jsonl("req.status=x").unwrap();
+    assert_eq!(field_value(&status, lines[1]).as_deref(), Some("500"));
+    assert_eq!(field_value(&status, lines[0]), None);
+    let msg = FieldQuery::parse_jsonl("msg~x").unwrap();
+    assert_eq!(field_value(&msg, lines[2]).as_deref(), Some("said \"hi\"é"));
+    let req = FieldQuery::parse_jsonl("req~x").unwrap();
+    assert_eq!(
+        field_value(&req, lines[1]).as_deref(),
+        Some(r#"{"path":"/api/users","status":500}"#)
+    );
+
+    // 앞선 값 안의 같은 이름 키에 속지 않습니다.
+    let line = r#"{"msg":"\"level\":\"ERROR\"","nested":{"level":"ERROR"},"level":"DEBUG"}"#;
+    assert_eq!(field_value(&level, line).as_deref(), Some("DEBUG"));
+}
+
+#[test]
+fn test_find_csv_columns() {
+    let lines: Vec<&str> = CSV.lines().collect();
+    let msg = FieldQuery::parse_csv("3~x").unwrap();
+    assert_eq!(
+        field_value(&msg, lines[1]).as_deref(),
+        Some("connect timeout, retrying")
+    );
+    assert_eq!(
+        field_value(&msg, lines[3]).as_deref(),
+        Some("said \"timeout\"")
+    );
+    assert_eq!(field_value(&msg, "a,b,").as_deref(), Some(""));
+    assert_eq!(field_value(&msg, "a,b"), None);
+
+    let field = msg.find_field(lines[2].as_bytes()).unwrap();
+    assert_eq!(&lines[2][field.span], "timeout");
+}
+
+#[test]
+fn test_field_matcher_spans() {
+    let pattern = Pattern::new("api").unwrap();
+    let query = FieldQuery::parse_jsonl("req.path~api").unwrap();
+    let matcher = query.matcher(&pattern);
+    let line = JSONL.lines().nth(1).unwrap();
+    assert!(matcher.is_match_line(line.as_bytes()));
+    let spans = matcher.find_spans(line.as_bytes());
+    assert_eq!(spans.len(), 1);
+    assert_eq!(&line[spans[0].clone()], "api");
+    // 다른 필드에만 있는 단어는 매칭되지 않습니다.
+    assert!(!matcher.is_match_line(br#"{"msg":"api","req":{"path":"/"}}"#));
+
+    // `=` 는 필드 값 전체와 같아야 합니다.
+    let pattern = Pattern::new("ERROR").unwrap();
+    let exact = FieldQuery::parse_jsonl("level=ERROR").unwrap();
+    let matched: Vec<usize> = JSONL
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| exact.matcher(&pattern).is_match_line(line.as_bytes()))
+        .map(|(i, _)| i + 1)
+        .collect();
+    assert_eq!(matched, [2]);
+
+    // 이스케이프를 푼 값은 원본 위치로 옮길 수 없으므로 필드 전체가 매치 구간입니다.
+    let pattern = Pattern::new("hi").unwrap();
+    let msg = FieldQuery::parse_jsonl("msg~hi").unwrap();
+    let line = JSONL.lines().nth(2).unwrap();
+    let spans = msg.matcher(&pattern).find_spans(line.as_bytes());
+    assert_eq!(&line[spans[0].clone()], r#"said \"hi\"é"#);
+}
+
+#[test]
+fn test_field_option_conflicts() {
+    let config = build(&["--jsonl-field", "level=ERROR", "a.log", "b.log"]).unwrap();
+    assert_eq!(config.query, "ERROR");
+    assert_eq!(config.paths, ["a.log", "b.log"]);
+    // `-E` 와 `=` 면 값 전체에 맞도록 패턴을 고정합니다.
+    let config = build(&["-E", "--csv-column", "2=WARN|ERROR", "a.csv"]).unwrap();
+    assert_eq!(config.query, r"\A(?:WARN|ERROR)\z");
+
+    assert!(matches!(
+        build(&["--jsonl-field", "level", "a.log"]),
+        Err(ConfigError::InvalidValue { .. })
+    ));
+    assert_eq!(
+        build(&["--jsonl-field", "level=x"]).unwrap().paths,
+        vec!["-"]
+    );
+    for args in [
+        &["--jsonl-field", "a=b", "--csv-column", "1=b", "f"][..],
+        &["--jsonl-field", "a=b", "-e", "x", "f"],
+        &["--csv-column", "1=b", "-U", "f"],
+        &["--csv-column", "1=b", "--follow", "f"],
+    ] {
+        assert!(
+            matches!(build(args), Err(ConfigError::Conflicts { .. })),
+            "{args:?}"
+        );
+    }
+}
+
+#[test]
+fn test_cli_field_search() {
+    let dir = std::env::temp_dir();
+    let jsonl = dir.join(format!("minigrep-fields-{}.jsonl", std::process::id()));
+    let csv = dir.join(format!("minigrep-fields-{}.csv", std::process::id()));
+    fs::write(&jsonl, JSONL).unwrap();
+    fs::write(&csv, CSV).unwrap();
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let jsonl = jsonl.to_str().unwrap();
+    let csv = csv.to_str().unwrap();
+
+    let out = run(&["-n", "--jsonl-field", "level=ERROR", jsonl]);
+    assert!(out.starts_with("2:{\"level\":\"ERROR\""), "{out}");
+    assert_eq!(out.lines().count(), 1);
+    assert_eq!(
+        run(&["-c", "-i", "--jsonl-field", "level=error", jsonl]),
+        "2\n"
+    );
+    assert_eq!(
+        run(&["-n", "--csv-column", "3~timeout", csv]),
+        "2:10:00,INFO,\"connect timeout, retrying\"\n3:10:01,WARN,timeout\n4:10:02,ERROR,\"said \"\"timeout\"\"\"\n"
+    );
+    assert_eq!(
+        run(&["-n", "-E", "--csv-column", "2=WARN|ERROR", csv]),
+        "3:10:01,WARN,timeout\n4:10:02,ERROR,\"said \"\"timeout\"\"\"\n"
+    );
+    // 컨텍스트와 `-v` 도 라인 검색과 같이 동작합니다.
+    assert_eq!(
+        run(&["-n", "-B", "1", "--csv-column", "2=WARN", csv]),
+        "2-10:00,INFO,\"connect timeout, retrying\"\n3:10:01,WARN,timeout\n"
+    );
+    assert_eq!(run(&["-c", "-v", "--csv-column", "2=INFO", csv]), "3\n");
+
+    fs::remove_file(jsonl).unwrap();
+    fs::remove_file(csv).unwrap();
+}
@@ -0,0 +1,189 @@
+//! 파일 따라가기(`--follow`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test follow_tests
+//! ```
+
+use minigrep::follow::{Event, Follower};
+use minigrep::*;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("minigrep-{}-{}", name, std::process::id()))
+}
+
+fn append(path: &Path, text: &str) {
+    let mut file = OpenOptions::new().append(true).open(path).unwrap();
+    file.write_all(text.as_bytes()).unwrap();
+}
+
+/// `poll` 한 번에 나온 이벤트를 문자열로 모읍니다.
+fn poll(follower: &mut Follower) -> Vec<String> {
+    let mut events = Vec::new();
+    follower
+        .poll(|event| {
+            events.push(match event {
+                Event::Line { number, bytes } => {
+                    format!("{number}:{}", String::from_utf8_lossy(bytes))
+                }
+                other => format!("{other:?}"),
+            });
+            Ok(())
+        })
+        .unwrap();
+    events
+}
+
+/// 백그라운드에서 실행 중인 `minigrep --follow`. 출력은 줄 단위로 채널에 모입니다.
+struct Running {
+    child: Child,
+    lines: mpsc::Receiver<String>,
+}
+
+impl Running {
+    fn spawn(args: &[&str]) -> Running {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line.unwrap()).is_err() {
+                    break;
+                }
+            }
+        });
+        Running { child, lines }
+    }
+
+    fn next_line(&self) -> String {
+        self.lines
+            .recv_timeout(Duration::from_secs(10))
+            .expect("no output from --follow")
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_follow_option_conflicts() {
+    assert!(build(&["--follow", "ERROR", "app.log"]).unwrap().follow);
+    for conflict in [&["-c"][..], &["-A", "1"], &["--json"], &["--fuzzy", "1"]] {
+        let mut args = vec!["--follow"];
+        args.extend_from_slice(conflict);
+        args.extend(["ERROR", "app.log"]);
+        assert!(
+            matches!(build(&args), Err(ConfigError::Conflicts { .. })),
+            "{args:?}"
+        );
+    }
+}
+
+#[test]
+fn test_follower_starts_at_last_lines_and_reads_appends() {
+    let path = temp_path("follow-tail");
+    let initial: String = (1..=12).map(|i| format!("line {i}\n")).collect();
+    fs::write(&path, initial).unwrap();
+    let mut follower = Follower::new(&path);
+
+    let first = poll(&mut follower);
+    assert_eq!(first.len(), 10);
+    assert_eq!(first[0], "3:line 3");
+    assert!(poll(&mut follower).is_empty());
+
+    // 줄바꿈이 올 때까지 라인 조각은 내보내지 않습니다.
+    append(&path, "line 13\nline ");
+    assert_eq!(poll(&mut follower), ["13:line 13"]);
+    append(&path, "14\n");
+    assert_eq!(poll(&mut follower), ["14:line 14"]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_follower_detects_truncation() {
+    let path = temp_path("follow-truncate");
+    fs::write(&path, "a long first line\nanother long line\n").unwrap();
+    let mut follower = Follower::new(&path);
+    assert_eq!(poll(&mut follower).len(), 2);
+
+    fs::write(&path, "short\n").unwrap();
+    assert_eq!(poll(&mut follower), ["Truncated", "1:short"]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_follower_follows_rotation() {
+    let path = temp_path("follow-rotate");
+    let rotated = temp_path("follow-rotate.1");
+    fs::write(&path, "old 1\n").unwrap();
+    let mut follower = Follower::new(&path);
+    assert_eq!(poll(&mut follower), ["1:old 1"]);
+
+    // 로테이션 직전에 쓴 내용과 줄바꿈 없는 마지막 조각도 빠뜨리지 않습니다.
+    fs::rename(&path, &rotated).unwrap();
+    append(&rotated, "old 2\nold tail");
+    assert_eq!(poll(&mut follower), ["2:old 2"]);
+    fs::write(&path, "new 1\n").unwrap();
+    assert_eq!(poll(&mut follower), ["3:old tail", "Replaced", "1:new 1"]);
+
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&rotated).unwrap();
+}
+
+#[test]
+fn test_follower_waits_for_missing_file() {
+    let path = temp_path("follow-missing");
+    let mut follower = Follower::new(&path);
+    let waiting = poll(&mut follower);
+    assert!(waiting[0].starts_with("Waiting"), "{waiting:?}");
+    assert!(poll(&mut follower).is_empty());
+
+    // 나중에 생긴 파일은 처음부터 읽습니다.
+    let lines: String = (1..=12).map(|i| format!("{i}\n")).collect();
+    fs::write(&path, lines).unwrap();
+    let events = poll(&mut follower);
+    assert_eq!(events[..2], ["Appeared", "1:1"]);
+    assert_eq!(events.len(), 13);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_cli_follow_prints_matches_as_they_arrive() {
+    let path = temp_path("follow-cli");
+    fs::write(&path, "INFO boot\nERROR disk full\n").unwrap();
+    let running = Running::spawn(&["--follow", "-n", "ERROR", path.to_str().unwrap()]);
+    assert_eq!(running.next_line(), "2:ERROR disk full");
+
+    append(&path, "INFO ok\nERROR timeout\n");
+    assert_eq!(running.next_line(), "4:ERROR timeout");
+
+    fs::write(&path, "ERROR again\n").unwrap();
+    assert_eq!(running.next_line(), "1:ERROR again");
+    drop(running);
+    fs::remove_file(&path).unwrap();
+}
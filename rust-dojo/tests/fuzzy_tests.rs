@@ -0,0 +1,167 @@
+//! 근사 검색(`--fuzzy`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test fuzzy_tests
+//! ```
+
+use minigrep::*;
+use std::process::Command;
+
+/// 질의와 `text` 의 모든 부분 문자열 사이의 최소 편집 거리 (단순 DP)
+fn naive_distance(query: &str, text: &str) -> usize {
+    let query: Vec<char> = query.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let mut best = query.len();
+    for start in 0..=text.len() {
+        for end in start..=text.len() {
+            let sub = &text[start..end];
+            let mut row: Vec<usize> = (0..=sub.len()).collect();
+            for (i, &q) in query.iter().enumerate() {
+                let mut diagonal = row[0];
+                row[0] = i + 1;
+                for (j, &c) in sub.iter().enumerate() {
+                    let next = (diagonal + usize::from(q != c))
+                        .min(row[j] + 1)
+                        .min(row[j + 1] + 1);
+                    diagonal = row[j + 1];
+                    row[j + 1] = next;
+                }
+            }
+            best = best.min(row[sub.len()]);
+        }
+    }
+    best
+}
+
+fn random_text(state: &mut u32, len: usize, alphabet: &[char]) -> String {
+    (0..len)
+        .map(|_| {
+            *state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            alphabet[(*state >> 16) as usize % alphabet.len()]
+        })
+        .collect()
+}
+
+#[test]
+fn test_distance_agrees_with_naive_dp() {
+    let alphabet = ['a', 'b', 'c', '가', '나'];
+    let mut state = 1;
+    for _ in 0..300 {
+        let query_len = 1 + state as usize % 6;
+        let query = random_text(&mut state, query_len, &alphabet);
+        let text_len = state as usize % 14;
+        let text = random_text(&mut state, text_len, &alphabet);
+        let expected = naive_distance(&query, &text);
+        let pattern = FuzzyPattern::new(&query, usize::MAX, false);
+        assert_eq!(
+            pattern.distance(&text),
+            Some(expected),
+            "query {query:?} text {text:?}"
+        );
+    }
+}
+
+#[test]
+fn test_long_queries_use_the_same_distance() {
+    // 64 글자를 넘는 질의는 비트 병렬 대신 일반 DP 로 계산합니다.
+    let query = "abcdefghij".repeat(7);
+    let mut text = format!("xx{}yy", query);
+    text.replace_range(10..11, "Z");
+    text.remove(40);
+    let pattern = FuzzyPattern::new(&query, 3, false);
+    assert_eq!(pattern.distance(&text), Some(2));
+    assert_eq!(FuzzyPattern::new(&query, 1, false).distance(&text), None);
+
+    // 접으면 UTF-8 길이가 바뀌는 글자(KELVIN SIGN → k)에서도 글자 경계에서 끝납니다.
+    let query = format!("{}k", "a".repeat(70));
+    let text = format!("{}\u{212A} tail", "a".repeat(70));
+    let found = FuzzyPattern::new(&query, 2, true).find(&text).unwrap();
+    assert_eq!(found.distance, 0);
+    assert_eq!(&text[found.range], format!("{}\u{212A}", "a".repeat(70)));
+}
+
+#[test]
+fn test_find_reports_closest_substring() {
+    let pattern = FuzzyPattern::new("receive", 2, false);
+    let text = "we recieve typos";
+    let found = pattern.find(text).unwrap();
+    assert_eq!(found.distance, 2);
+    assert_eq!(&text[found.range], "recieve");
+
+    let pattern = FuzzyPattern::new("서울특별시", 1, false);
+    let text = "주소: 서울특벌시 중구";
+    let found = pattern.find(text).unwrap();
+    assert_eq!(found.distance, 1);
+    assert_eq!(&text[found.range], "서울특벌시");
+}
+
+#[test]
+fn test_case_insensitive_fuzzy() {
+    assert_eq!(
+        FuzzyPattern::new("Straße", 0, true).distance("STRAẞE"),
+        Some(0)
+    );
+    assert_eq!(FuzzyPattern::new("Hello", 0, false).distance("hello"), None);
+    assert_eq!(
+        FuzzyPattern::new("Hello", 0, true).distance("hello"),
+        Some(0)
+    );
+}
+
+#[test]
+fn test_search_fuzzy_ranks_by_distance() {
+    let contents = "\
+the weather is nice
+whether or not
+wether report
+weather balloon
+";
+    let pattern = FuzzyPattern::new("weather", 2, false);
+    let results = search_fuzzy(&pattern, contents);
+    let ranked: Vec<(usize, usize)> = results
+        .iter()
+        .map(|r| (r.line.line_number, r.distance))
+        .collect();
+    // 거리가 같으면 원래 순서를 유지합니다.
+    assert_eq!(ranked, vec![(1, 0), (4, 0), (3, 1), (2, 2)]);
+    assert_eq!(results[2].rank(), 1);
+    assert_eq!(results[0].line.matches[0].bytes, 4..11);
+}
+
+#[test]
+fn test_fuzzy_argument_conflicts() {
+    let build = |args: &[&str]| {
+        let args: Vec<String> = std::iter::once("minigrep")
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect();
+        Config::build(&args)
+    };
+    assert_eq!(
+        build(&["--fuzzy", "2", "query", "a.txt"]).unwrap().fuzzy,
+        Some(2)
+    );
+    assert!(matches!(
+        build(&["--fuzzy", "2", "-E", "query", "a.txt"]),
+        Err(ConfigError::Conflicts { .. })
+    ));
+    assert!(matches!(
+        build(&["--fuzzy=x", "query", "a.txt"]),
+        Err(ConfigError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn test_cli_fuzzy_ranked_output() {
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-n", "--fuzzy", "1", "somebody", "tests/fixtures/poem.txt"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(!lines.is_empty());
+    // 정확히 맞는 라인이 먼저 나옵니다.
+    assert!(lines[0].contains("somebody"));
+}
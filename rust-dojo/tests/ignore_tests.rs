@@ -0,0 +1,206 @@
+//! 글롭과 무시 파일(`.gitignore` / `.ignore`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test ignore_tests
+//! ```
+
+use minigrep::ignore::{Gitignore, Match};
+use minigrep::walk::{collect_files_with, WalkOptions};
+use minigrep::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 테스트마다 겹치지 않는 임시 디렉토리에 파일들을 만듭니다.
+fn make_tree(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("minigrep-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for (path, contents) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+    root
+}
+
+fn relative(root: &Path, files: Vec<PathBuf>) -> Vec<String> {
+    files
+        .iter()
+        .map(|f| {
+            f.strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect()
+}
+
+mod globs {
+    use super::*;
+
+    #[test]
+    fn test_star_does_not_cross_separator() {
+        let glob = Glob::new("src/*.rs").unwrap();
+        assert!(glob.is_match("src/lib.rs"));
+        assert!(!glob.is_match("src/bin/main.rs"));
+    }
+
+    #[test]
+    fn test_double_star() {
+        let glob = Glob::new("**/tests/**").unwrap();
+        assert!(glob.is_match("tests/a.rs"));
+        assert!(glob.is_match("crates/x/tests/deep/b.rs"));
+        assert!(!glob.is_match("src/tests.rs"));
+    }
+
+    #[test]
+    fn test_classes_alternates_and_escapes() {
+        let glob = Glob::new("[!.]*.{rs,toml}").unwrap();
+        assert!(glob.is_match("Cargo.toml"));
+        assert!(!glob.is_match(".hidden.rs"));
+        assert!(Glob::new(r"a\*b").unwrap().is_match("a*b"));
+        assert!(Glob::new("file?.txt").unwrap().is_match("file1.txt"));
+    }
+
+    #[test]
+    fn test_invalid_globs() {
+        assert_eq!(Glob::new("[abc").unwrap_err(), GlobError::UnclosedClass(0));
+        assert_eq!(
+            Glob::new("*.{rs").unwrap_err(),
+            GlobError::UnclosedAlternates(2)
+        );
+    }
+}
+
+mod gitignore {
+    use super::*;
+
+    #[test]
+    fn test_gitignore_rules() {
+        let dir = Path::new("/repo");
+        let gitignore = Gitignore::parse(
+            dir,
+            "# 빌드 결과\ntarget/\n*.log\n!keep.log\n/root-only.txt\ndocs/*.html\n",
+        );
+        let check = |path: &str, is_dir| gitignore.matched(&dir.join(path), is_dir);
+        assert_eq!(check("target", true), Match::Ignore);
+        assert_eq!(check("target", false), Match::None);
+        assert_eq!(check("a/b/debug.log", false), Match::Ignore);
+        assert_eq!(check("a/keep.log", false), Match::Whitelist);
+        assert_eq!(check("root-only.txt", false), Match::Ignore);
+        assert_eq!(check("sub/root-only.txt", false), Match::None);
+        assert_eq!(check("docs/index.html", false), Match::Ignore);
+        assert_eq!(check("docs/api/index.html", false), Match::None);
+    }
+
+    #[test]
+    fn test_overrides_include_and_exclude() {
+        let overrides = Overrides::new(&["*.rs", "!target/**"]).unwrap();
+        assert_eq!(overrides.matched("src/lib.rs", false), Match::Whitelist);
+        assert_eq!(
+            overrides.matched("target/debug/build.rs", false),
+            Match::Ignore
+        );
+        assert_eq!(overrides.matched("README.md", false), Match::Ignore);
+        // 포함 규칙은 디렉토리를 막지 않습니다.
+        assert_eq!(overrides.matched("src", true), Match::None);
+    }
+
+    #[test]
+    fn test_unknown_file_type() {
+        assert_eq!(
+            FileTypes::new(&["klingon"], &[]).unwrap_err(),
+            FilterError::UnknownType(String::from("klingon"))
+        );
+    }
+}
+
+mod walking {
+    use super::*;
+
+    fn workspace(name: &str) -> PathBuf {
+        make_tree(
+            name,
+            &[
+                (".gitignore", "target/\n*.tmp\n"),
+                (".hidden/secret.rs", "fn secret() {}"),
+                ("src/lib.rs", "fn lib() {}"),
+                ("src/.ignore", "generated.rs\n"),
+                ("src/generated.rs", "fn generated() {}"),
+                ("src/notes.md", "# notes"),
+                ("scratch.tmp", "tmp"),
+                ("target/debug/out.rs", "fn out() {}"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_default_walk_skips_ignored_and_hidden() {
+        let root = workspace("walk-default");
+        let files = collect_files_with(&[&root], &WalkOptions::default()).unwrap();
+        assert_eq!(relative(&root, files), vec!["src/lib.rs", "src/notes.md"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_with_type_and_hidden() {
+        let root = workspace("walk-type");
+        let options = WalkOptions {
+            hidden: true,
+            types: FileTypes::new(&["rust"], &[]).unwrap(),
+            ..WalkOptions::default()
+        };
+        let files = collect_files_with(&[&root], &options).unwrap();
+        assert_eq!(
+            relative(&root, files),
+            vec![".hidden/secret.rs", "src/lib.rs"]
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_with_no_ignore_and_glob() {
+        let root = workspace("walk-glob");
+        let options = WalkOptions {
+            no_ignore: true,
+            overrides: Overrides::new(&["*.rs", "!target/**"]).unwrap(),
+            ..WalkOptions::default()
+        };
+        let files = collect_files_with(&[&root], &options).unwrap();
+        assert_eq!(
+            relative(&root, files),
+            vec!["src/generated.rs", "src/lib.rs"]
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_config_options() {
+        let args: Vec<String> = [
+            "minigrep",
+            "-g",
+            "!target/**",
+            "-trust",
+            "--hidden",
+            "fn",
+            ".",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let config = Config::build(&args).unwrap();
+        assert_eq!(config.globs, vec!["!target/**"]);
+        assert_eq!(config.types, vec!["rust"]);
+        assert!(config.hidden);
+        assert!(config.walk_options().is_ok());
+
+        let args: Vec<String> = ["minigrep", "--type", "klingon", "fn", "."]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(matches!(
+            Config::build(&args),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+    }
+}
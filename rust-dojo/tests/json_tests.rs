@@ -0,0 +1,118 @@
+//! JSON Lines 출력(`--json`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test json_tests
+//! ```
+
+use minigrep::json::{self, Stats};
+use minigrep::*;
+use std::process::Command;
+use std::time::Duration;
+
+fn minigrep(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_escape_str_handles_quotes_and_control_characters() {
+    assert_eq!(json::escape_str(r#"say "hi"\n"#), r#""say \"hi\"\\n""#);
+    assert_eq!(json::escape_str("a\tb\u{1}"), r#""a\tb\u0001""#);
+    assert_eq!(json::escape_str("에러"), "\"에러\"");
+}
+
+#[test]
+fn test_base64_padding() {
+    assert_eq!(json::base64(b""), "");
+    assert_eq!(json::base64(b"f"), "Zg==");
+    assert_eq!(json::base64(b"fo"), "Zm8=");
+    assert_eq!(json::base64(b"foo"), "Zm9v");
+    assert_eq!(json::base64(b"caf\xe9"), "Y2Fm6Q==");
+}
+
+#[test]
+fn test_match_record_has_submatch_offsets() {
+    let pattern = Pattern::literal("o");
+    let line = LineMatch::with_matches(3, "foo", &pattern);
+    let mut out = Vec::new();
+    json::write_line(&mut out, b"a.txt", &line, true).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        concat!(
+            r#"{"type":"match","data":{"path":{"text":"a.txt"},"lines":{"text":"foo"},"#,
+            r#""line_number":3,"submatches":[{"match":{"text":"o"},"start":1,"end":2},"#,
+            r#"{"match":{"text":"o"},"start":2,"end":3}]}}"#,
+            "\n"
+        )
+    );
+}
+
+#[test]
+fn test_non_utf8_line_is_base64_with_raw_offsets() {
+    let pattern = Pattern::literal("error");
+    let line = LineMatch::from_bytes(1, b"caf\xe9 error", Some(&pattern));
+    assert_eq!(line.matches[0].bytes, 5..10);
+    assert_eq!(line.column(), Some(6));
+
+    let mut out = Vec::new();
+    json::write_line(&mut out, b"x", &line, true).unwrap();
+    let record = String::from_utf8(out).unwrap();
+    assert!(record.contains(r#""lines":{"bytes":"Y2Fm6SBlcnJvcg=="}"#));
+    assert!(record.contains(r#"{"match":{"text":"error"},"start":5,"end":10}"#));
+}
+
+#[test]
+fn test_stats_add() {
+    let summary = StreamSummary {
+        bytes_read: 10,
+        matched_lines: 2,
+        matches: 3,
+    };
+    let mut total = Stats::default();
+    total.add(&Stats::from_summary(&summary, Duration::from_millis(1)));
+    total.add(&Stats::from_summary(
+        &StreamSummary::default(),
+        Duration::ZERO,
+    ));
+    assert_eq!(total.searches, 2);
+    assert_eq!(total.searches_with_match, 1);
+    assert_eq!(total.bytes_searched, 10);
+    assert_eq!(total.matches, 3);
+}
+
+#[test]
+fn test_json_output_record_sequence() {
+    let stdout = minigrep(&["--json", "-j1", "-B1", "error", "tests/fixtures/tree"]);
+    let types: Vec<&str> = stdout
+        .lines()
+        .map(|line| {
+            let start = line.find(r#""type":""#).unwrap() + 8;
+            &line[start..start + line[start..].find('"').unwrap()]
+        })
+        .collect();
+    // a.txt, sub/b.log, sub/deeper/c.txt 순서. bin.dat 는 건너뜁니다.
+    assert_eq!(
+        types,
+        vec![
+            "begin", "match", "end", "begin", "context", "match", "end", "begin", "match", "end",
+            "summary"
+        ]
+    );
+    let summary = stdout.lines().last().unwrap();
+    assert!(summary.contains(r#""searches":3,"searches_with_match":3"#));
+    assert!(summary.contains(r#""matched_lines":3,"matches":3"#));
+}
+
+#[test]
+fn test_json_output_for_non_utf8_file() {
+    let stdout = minigrep(&["--json", "error", "tests/fixtures/latin1.txt"]);
+    let record = stdout.lines().nth(1).unwrap();
+    assert!(record.starts_with(r#"{"type":"match""#));
+    assert!(record.contains(r#""lines":{"bytes":"Y2Fm6SBlcnJvcg=="}"#));
+    assert!(record.contains(r#""start":5,"end":10"#));
+}
@@ -0,0 +1,68 @@
+//! 라인 번호와 매치 컬럼 보고 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test line_match_tests
+//! ```
+
+use minigrep::*;
+
+#[test]
+fn test_search_lines_uses_source_line_numbers() {
+    let contents = "alpha\nbeta error\ngamma\ndelta error";
+    let pattern = Pattern::literal("error");
+    let numbers: Vec<usize> = search_lines(&pattern, contents, false)
+        .iter()
+        .map(|m| m.line_number)
+        .collect();
+    assert_eq!(numbers, vec![2, 4]);
+}
+
+#[test]
+fn test_search_lines_inverted() {
+    let contents = "alpha\nbeta error\ngamma";
+    let pattern = Pattern::literal("error");
+    let results = search_lines(&pattern, contents, true);
+    let numbers: Vec<usize> = results.iter().map(|m| m.line_number).collect();
+    assert_eq!(numbers, vec![1, 3]);
+    assert!(results.iter().all(|m| m.matches.is_empty()));
+}
+
+#[test]
+fn test_every_match_span_is_recorded() {
+    let pattern = Pattern::literal("ab");
+    let m = LineMatch::with_matches(1, "ab-ab", &pattern);
+    let bytes: Vec<_> = m.matches.iter().map(|s| s.bytes.clone()).collect();
+    assert_eq!(bytes, vec![0..2, 3..5]);
+}
+
+#[test]
+fn test_byte_and_char_columns_differ_for_multibyte_text() {
+    let pattern = Pattern::literal("timeout");
+    let m = LineMatch::with_matches(3, "연결 timeout 발생 timeout", &pattern);
+    assert_eq!(m.matches[0].bytes, 7..14);
+    assert_eq!(m.matches[0].chars, 3..10);
+    assert_eq!(m.matches[1].bytes, 22..29);
+    assert_eq!(m.matches[1].chars, 14..21);
+    assert_eq!(m.column(), Some(4));
+}
+
+#[test]
+fn test_format_with_path_includes_column() {
+    let pattern = Pattern::literal("error");
+    let m = LineMatch::with_matches(12, "an error", &pattern).with_path("app.log");
+    assert_eq!(m.format(), "app.log:12:4:an error");
+    assert_eq!(format_results(&[m]), "app.log:12:4:an error");
+}
+
+#[test]
+fn test_styled_column() {
+    let pattern = Pattern::literal("error");
+    let m = LineMatch::with_matches(12, "an error", &pattern).with_path("app.log");
+    let style = OutputStyle {
+        with_path: true,
+        line_number: true,
+        column: true,
+    };
+    assert_eq!(m.format_styled(&style), "app.log:12:4:an error");
+}
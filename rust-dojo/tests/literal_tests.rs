@@ -0,0 +1,124 @@
+//! 리터럴 검색(`literal` 모듈) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test literal_tests
+//! ```
+
+use minigrep::literal::{count_byte, matching_lines, memchr, memrchr, Finder};
+use minigrep::*;
+use std::io::Cursor;
+
+/// 테스트용 의사 난수 (xorshift)
+fn pseudo_random_text(seed: u64, len: usize, alphabet: &[u8]) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            alphabet[(state % alphabet.len() as u64) as usize]
+        })
+        .collect()
+}
+
+fn naive_find(haystack: &[u8], needle: &[u8], start: usize) -> Option<usize> {
+    (start..=haystack.len().checked_sub(needle.len())?)
+        .find(|&i| haystack[i..i + needle.len()] == *needle)
+}
+
+#[test]
+fn test_byte_scans_at_every_alignment() {
+    let haystack = b"0123456789abcdef0123456789abcdef!";
+    for start in 0..haystack.len() {
+        let slice = &haystack[start..];
+        for byte in [b'0', b'f', b'!', b'x'] {
+            assert_eq!(memchr(byte, slice), slice.iter().position(|&b| b == byte));
+            assert_eq!(memrchr(byte, slice), slice.iter().rposition(|&b| b == byte));
+            assert_eq!(
+                count_byte(byte, slice),
+                slice.iter().filter(|&&b| b == byte).count()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_finder_matches_naive_search() {
+    let haystack = pseudo_random_text(7, 20_000, b"abcd \n");
+    for needle in [&b"a"[..], b"ab", b"dcba", b"abcab", b"d d", b"zz"] {
+        let finder = Finder::new(needle);
+        for start in [0, 1, 999, 19_990] {
+            assert_eq!(
+                finder.find_at(&haystack, start),
+                naive_find(&haystack, needle, start),
+                "needle {:?} start {}",
+                needle,
+                start
+            );
+        }
+    }
+}
+
+#[test]
+fn test_finder_switches_on_pathological_input() {
+    // 후보 바이트가 계속 나와서 Horspool 로 넘어가는 경우
+    let mut haystack = vec![b'a'; 100_000];
+    haystack.extend_from_slice(b"aab");
+    let finder = Finder::new(b"aaab");
+    assert_eq!(finder.find(&haystack), Some(99_999));
+    assert_eq!(Finder::new(b"aaac").find(&haystack), None);
+}
+
+#[test]
+fn test_matching_lines_reports_each_line_once() {
+    let haystack = b"foo foo\nbar\nxfoo\nfoo";
+    let finder = Finder::new(b"foo");
+    let lines: Vec<_> = matching_lines(&finder, haystack).collect();
+    assert_eq!(lines, vec![0..7, 12..16, 17..20]);
+}
+
+#[test]
+fn test_search_matches_line_by_line_contains() {
+    let mut text = String::from_utf8(pseudo_random_text(42, 50_000, b"ab \r\n")).unwrap();
+    text.push_str("\nlast ab\r");
+    for query in ["ab", "a b", "bba", "", "\r", "b\r"] {
+        let expected: Vec<&str> = text.lines().filter(|l| l.contains(query)).collect();
+        assert_eq!(search(query, &text), expected, "query {:?}", query);
+    }
+}
+
+#[test]
+fn test_chunked_stream_matches_line_mode() {
+    // 청크 크기(256 KiB)를 넘는 입력과 아주 긴 라인, CRLF, 끝 개행 없음
+    let mut data = pseudo_random_text(3, 600_000, b"xyz needle\n\r");
+    data.extend_from_slice(&vec![b'q'; 300_000]);
+    data.extend_from_slice(b"needle at the very end\r");
+
+    let literal = Pattern::literal("needle");
+    let regex = Pattern::new("(?:needle)").unwrap();
+    assert!(literal.literal_finder().is_some());
+    assert!(regex.literal_finder().is_none());
+
+    let run = |pattern: &Pattern| {
+        let mut lines = Vec::new();
+        let summary = stream::search_reader(
+            pattern,
+            Cursor::new(&data),
+            &StreamOptions::default(),
+            |item| {
+                if let StreamItem::Line(line) = item {
+                    lines.push(line);
+                }
+                Ok(())
+            },
+        )
+        .unwrap();
+        (lines, summary)
+    };
+    let (chunked, chunked_summary) = run(&literal);
+    let (by_line, line_summary) = run(&regex);
+    assert!(!chunked.is_empty());
+    assert_eq!(chunked, by_line);
+    assert_eq!(chunked_summary, line_summary);
+}
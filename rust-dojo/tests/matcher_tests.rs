@@ -0,0 +1,309 @@
+//! 여러 패턴(`-e`, `-f`)과 패턴 조합(`--and`, `--or`, `--not`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test matcher_tests
+//! ```
+
+use minigrep::aho_corasick::AhoCorasick;
+use minigrep::matcher::{parse_expr, Expr};
+use minigrep::*;
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+fn selected(matcher: &PatternSet, contents: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    stream::search_reader(
+        matcher,
+        Cursor::new(contents),
+        &StreamOptions::default(),
+        |item| {
+            if let StreamItem::Line(line) = item {
+                lines.push(line.content);
+            }
+            Ok(())
+        },
+    )
+    .unwrap();
+    lines
+}
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("minigrep-{}-{}", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_aho_corasick_reports_overlapping_matches() {
+    let automaton = AhoCorasick::new(&["he", "she", "his", "hers"]);
+    let found: Vec<(usize, std::ops::Range<usize>)> = automaton
+        .find_overlapping_iter(b"ushers")
+        .map(|m| (m.pattern, m.range))
+        .collect();
+    assert_eq!(found, vec![(1, 1..4), (0, 2..4), (3, 2..6)]);
+
+    let first = automaton.find_at(b"ushers", 0).unwrap();
+    assert_eq!((first.pattern, first.range), (1, 1..4));
+    assert!(automaton.find_at(b"xyz", 0).is_none());
+}
+
+#[test]
+fn test_aho_corasick_agrees_with_naive_search() {
+    let patterns = ["ab", "abc", "bca", "c", "aaa", "cab"];
+    let automaton = AhoCorasick::new(&patterns);
+    // 작은 알파벳으로 만든 입력에서 모든 매치를 단순 비교 결과와 맞춰 봅니다.
+    let mut state = 7u32;
+    let haystack: Vec<u8> = (0..500)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            b"abc"[(state >> 16) as usize % 3]
+        })
+        .collect();
+
+    let mut expected = Vec::new();
+    for end in 1..=haystack.len() {
+        for (id, pattern) in patterns.iter().enumerate() {
+            if haystack[..end].ends_with(pattern.as_bytes()) {
+                expected.push((id, end - pattern.len()..end));
+            }
+        }
+    }
+    let mut found: Vec<_> = automaton
+        .find_overlapping_iter(&haystack)
+        .map(|m| (m.pattern, m.range))
+        .collect();
+    // 같은 위치에서 끝나는 매치끼리는 순서를 따지지 않습니다.
+    found.sort_by_key(|(id, range)| (range.end, *id));
+    expected.sort_by_key(|(id, range)| (range.end, *id));
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn test_parse_expr_precedence() {
+    let terms = [
+        ExprTerm::Pattern("a".into()),
+        ExprTerm::Or,
+        ExprTerm::Pattern("b".into()),
+        ExprTerm::And,
+        ExprTerm::Not,
+        ExprTerm::Pattern("c".into()),
+        ExprTerm::Pattern("d".into()),
+    ];
+    // a --or (b --and (--not c)) --or d
+    assert_eq!(
+        parse_expr(&terms).unwrap(),
+        Expr::Any(vec![
+            Expr::Term(0),
+            Expr::All(vec![Expr::Term(2), Expr::Not(Box::new(Expr::Term(5)))]),
+            Expr::Term(6),
+        ])
+    );
+}
+
+#[test]
+fn test_parse_expr_missing_operand() {
+    let pattern = || ExprTerm::Pattern("a".into());
+    assert_eq!(
+        parse_expr(&[pattern(), ExprTerm::And]),
+        Err(ExprError::MissingOperand("--and"))
+    );
+    assert_eq!(
+        parse_expr(&[ExprTerm::Or, pattern()]),
+        Err(ExprError::MissingOperand("--or"))
+    );
+    assert_eq!(
+        parse_expr(&[pattern(), ExprTerm::Not]),
+        Err(ExprError::MissingOperand("--not"))
+    );
+}
+
+#[test]
+fn test_boolean_combinations() {
+    let contents = "\
+let x = a.unwrap();
+unsafe { a.unwrap() }
+unsafe { ptr.read() }
+// unwrap is fine in tests
+";
+    let matcher = |args: &[&str]| build(args).unwrap().matcher().unwrap();
+
+    assert_eq!(
+        selected(
+            &matcher(&["-e", "unsafe", "--and", "-e", "unwrap", "x"]),
+            contents
+        ),
+        vec!["unsafe { a.unwrap() }"]
+    );
+    assert_eq!(
+        selected(
+            &matcher(&["-e", "unwrap", "--and", "--not", "-e", "//", "x"]),
+            contents
+        ),
+        vec!["let x = a.unwrap();", "unsafe { a.unwrap() }"]
+    );
+    assert_eq!(
+        selected(&matcher(&["-e", "read", "-e", "let", "x"]), contents),
+        vec!["let x = a.unwrap();", "unsafe { ptr.read() }"]
+    );
+    // 정규식 패턴과 리터럴 패턴이 섞여 있어도 같은 규칙입니다.
+    assert_eq!(
+        selected(
+            &matcher(&["-E", "-e", r"^\w+ \{", "--and", "--not", "-e", "unwrap", "x"]),
+            contents
+        ),
+        vec!["unsafe { ptr.read() }"]
+    );
+}
+
+#[test]
+fn test_spans_cover_every_positive_pattern() {
+    let matcher = PatternSet::any(vec![
+        Pattern::literal("foo"),
+        Pattern::literal("foobar"),
+        Pattern::literal("baz"),
+    ]);
+    let line = b"foobar baz foo";
+    assert!(matcher.is_match_line(line));
+    // 겹치면 먼저 시작하고 더 긴 구간이 남습니다.
+    assert_eq!(matcher.find_spans(line), vec![0..6, 7..10, 11..14]);
+
+    let terms = [
+        ExprTerm::Pattern("foo".into()),
+        ExprTerm::Not,
+        ExprTerm::Pattern("qux".into()),
+    ];
+    let matcher = PatternSet::build(&terms, &PatternOptions::default()).unwrap();
+    assert_eq!(matcher.find_spans(b"foo qux"), vec![0..3]);
+}
+
+#[test]
+fn test_pattern_file_with_many_literals() {
+    let banned: Vec<String> = (0..300).map(|i| format!("banned_ident_{i:03}")).collect();
+    let path = temp_file("patterns.txt", &format!("{}\n\n", banned.join("\n")));
+    let config = build(&["-f", path.to_str().unwrap(), "src/"]).unwrap();
+    assert_eq!(config.query, "");
+    assert_eq!(config.paths, vec!["src/"]);
+
+    let matcher = config.matcher().unwrap();
+    // 빈 줄은 패턴이 되지 않습니다.
+    assert_eq!(matcher.len(), 300);
+    let contents =
+        "call(banned_ident_042);\nok_ident_042();\nx = banned_ident_299 + banned_ident_000\n";
+    assert_eq!(
+        selected(&matcher, contents),
+        vec![
+            "call(banned_ident_042);",
+            "x = banned_ident_299 + banned_ident_000"
+        ]
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_case_insensitive_and_word_sets_use_the_automaton() {
+    let needles = ["kelvin", "Straße", "unwrap", "ab", "b_c"];
+    let lines: [&[u8]; 8] = [
+        b"x.UNWRAP() and \xE2\x84\xAAELVIN",
+        "STRASSE vs STRAẞE straße".as_bytes(),
+        b"unwrap_or(ab) b_cd",
+        b"ab\xFFab b_c",
+        b"kelvinkelvin unwrapped",
+        "é_ab ab_é Ab".as_bytes(),
+        b"\xE2\x84 ab",
+        b"nothing here",
+    ];
+    for (case_insensitive, word) in [(true, false), (false, true), (true, true)] {
+        let options = PatternOptions {
+            case_insensitive,
+            word,
+            ..PatternOptions::default()
+        };
+        let literals = PatternSet::any(
+            needles
+                .iter()
+                .map(|n| Pattern::with_options(n, &options).unwrap())
+                .collect(),
+        );
+        // `(?:...)` 로 감싸면 리터럴로 보지 않으므로 모든 패턴을 정규식 엔진으로 확인합니다.
+        let regex_options = PatternOptions {
+            regex: true,
+            ..options.clone()
+        };
+        let regexes = PatternSet::any(
+            needles
+                .iter()
+                .map(|n| Pattern::with_options(&format!("(?:{n})"), &regex_options).unwrap())
+                .collect(),
+        );
+        assert_eq!(literals.literal_patterns(), needles.len());
+        assert_eq!(regexes.literal_patterns(), 0);
+        for line in lines {
+            assert_eq!(
+                literals.is_match_line(line),
+                regexes.is_match_line(line),
+                "{options:?} {line:?}"
+            );
+            assert_eq!(
+                literals.find_spans(line),
+                regexes.find_spans(line),
+                "{options:?} {line:?}"
+            );
+        }
+        // 대소문자나 단어 경계 때문에 바이트 검색만으로는 라인을 고를 수 없습니다.
+        assert!(literals.buffer_finder().is_none());
+        assert_eq!(literals.required_literals().is_some(), !case_insensitive);
+    }
+}
+
+#[test]
+fn test_expression_argument_errors() {
+    assert_eq!(
+        build(&["-e", "a", "--and", "x"]),
+        Err(ConfigError::Expression(ExprError::MissingOperand("--and")))
+    );
+    assert!(matches!(
+        build(&["-r", "x", "-e", "a", "-e", "b", "file"]),
+        Err(ConfigError::Conflicts { .. })
+    ));
+    assert!(build(&["-r", "x", "-e", "a", "file"]).is_ok());
+    assert_eq!(build(&["-e", "a"]).unwrap().paths, vec!["-"]);
+}
+
+#[test]
+fn test_cli_multiple_patterns() {
+    let path = temp_file("multi.txt", "alpha\nbeta\ngamma\nalpha beta\n");
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args([
+            "-n", "-e", "alpha", "--and", "--not", "-e", "beta", "-e", "gamma",
+        ])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "1:alpha\n3:gamma\n"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-f", "/nonexistent/patterns.txt"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("/nonexistent/patterns.txt"));
+    fs::remove_file(path).unwrap();
+}
@@ -10,6 +10,9 @@
 //! cargo test mission_2          # 임무 2만
 //! ```
 
+// 학습용 원본 그대로 `&vec![...]` 로 인자를 만듭니다.
+#![allow(clippy::useless_vec)]
+
 use minigrep::*;
 
 // ============================================================================
@@ -126,7 +129,7 @@ mod mission_3_struct {
 
         let config = config.unwrap();
         assert_eq!(config.query, "needle");
-        assert_eq!(config.filename, "haystack.txt");
+        assert_eq!(config.paths, vec!["haystack.txt"]);
     }
 
     #[test]
@@ -138,9 +141,10 @@ mod mission_3_struct {
 
     #[test]
     fn test_config_build_missing_filename() {
+        // 파일 이름이 없으면 표준 입력(`-`)을 읽습니다.
         let args = vec![String::from("minigrep"), String::from("query")];
-        let config = Config::build(&args);
-        assert!(config.is_err());
+        let config = Config::build(&args).unwrap();
+        assert_eq!(config.paths, vec!["-"]);
     }
 
     #[test]
@@ -0,0 +1,204 @@
+//! 여러 줄 매칭(`-U`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test multiline_tests
+//! ```
+
+use minigrep::multiline::find_matches;
+use minigrep::*;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+fn multiline(pattern: &str) -> Pattern {
+    let options = PatternOptions {
+        regex: true,
+        multiline: true,
+        ..PatternOptions::default()
+    };
+    Pattern::with_options(pattern, &options).unwrap()
+}
+
+const CONFIG: &str = "\
+# nginx
+server {
+    listen 80;
+}
+upstream api {
+    server 10.0.0.1;
+}
+";
+
+#[test]
+fn test_multiline_anchors_match_at_line_boundaries() {
+    let text = b"first\nsecond\n";
+    assert_eq!(multiline("^second$").find_at(text, 0), Some(6..12));
+    assert_eq!(Pattern::new("^second$").unwrap().find_at(text, 0), None);
+    // `\A` 와 `\z` 는 여전히 텍스트 전체의 시작과 끝입니다.
+    assert_eq!(multiline(r"\Asecond").find_at(text, 0), None);
+    assert_eq!(multiline(r"second\n\z").find_at(text, 0), Some(6..13));
+}
+
+#[test]
+fn test_find_matches_reports_line_ranges() {
+    let found = find_matches(&multiline(r"server \{[^}]*\}"), CONFIG.as_bytes());
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].line_number, 2);
+    assert_eq!(found[0].end_line_number, 4);
+    assert_eq!(found[0].content, "server {\n    listen 80;\n}");
+    assert_eq!(found[0].matches[0].bytes, 0..25);
+
+    // 매치가 줄 중간에서 시작해도 그 줄 전체가 결과에 들어갑니다.
+    let found = find_matches(&multiline(r"api \{\n\s+server"), CONFIG.as_bytes());
+    assert_eq!((found[0].line_number, found[0].end_line_number), (5, 6));
+    assert_eq!(found[0].content, "upstream api {\n    server 10.0.0.1;");
+    assert_eq!(found[0].column(), Some(10));
+}
+
+#[test]
+fn test_find_matches_merges_matches_on_shared_lines() {
+    let text = "a {\n} b {\n}\nc\n";
+    let found = find_matches(&multiline(r"\{\n\}"), text.as_bytes());
+    assert_eq!(found.len(), 1);
+    assert_eq!((found[0].line_number, found[0].end_line_number), (1, 3));
+    assert_eq!(found[0].matches.len(), 2);
+
+    // 단일 라인 매치는 보통 라인과 같고, CRLF 의 `\r` 은 내용에서 뺍니다.
+    let found = find_matches(&multiline("^c$"), b"a\r\nb\r\nc\r\nc");
+    let lines: Vec<usize> = found.iter().map(|m| m.line_number).collect();
+    assert_eq!(lines, [4]);
+    let found = find_matches(&multiline(r"b\r\n"), b"a\r\nb\r\nc\r\n");
+    assert_eq!(found[0].content, "b");
+    assert_eq!(found[0].end_line_number, 2);
+}
+
+#[test]
+fn test_multiline_crlf_lines() {
+    // 매치가 걸친 모든 줄에서 `\r` 을 빼고, 매치 구간도 뺀 만큼 당깁니다.
+    let text = b"a\r\nb\r\nc\r\n";
+    let found = find_matches(&multiline(r"a\r?\nb"), text);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].content, "a\nb");
+    assert_eq!(found[0].matches[0].bytes, 0..3);
+    let found = find_matches(&multiline(r"b\r\nc"), text);
+    assert_eq!(found[0].content, "b\nc");
+    assert_eq!(found[0].matches[0].bytes, 0..3);
+
+    let path = std::env::temp_dir().join(format!("minigrep-crlf-{}", std::process::id()));
+    fs::write(&path, text).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-U", "-n", "-E", r"a\r?\nb"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "1:a\n2:b\n");
+}
+
+#[test]
+fn test_multiline_format() {
+    let found = find_matches(&multiline(r"server \{[^}]*\}"), CONFIG.as_bytes());
+    let line = found[0].clone().with_path("nginx.conf");
+    assert_eq!(
+        line.format(),
+        "nginx.conf:2-4:1:server {\n    listen 80;\n}"
+    );
+
+    let style = OutputStyle {
+        with_path: false,
+        line_number: true,
+        column: false,
+    };
+    assert_eq!(
+        line.format_styled(&style),
+        "2:server {\n3:    listen 80;\n4:}"
+    );
+    // 색은 줄마다 닫혀 다음 줄의 라인 번호에 번지지 않습니다.
+    let palette = Palette::new(true);
+    let colored = line.format_colored(&style, &palette);
+    for line in colored.lines() {
+        assert!(line.matches("\x1b[").count() % 2 == 0, "{line:?}");
+    }
+}
+
+#[test]
+fn test_multiline_option_conflicts() {
+    assert!(build(&["-U", "-E", "a\\nb", "f"]).unwrap().multiline);
+    assert!(build(&["--multiline", "-e", "a", "f"]).is_ok());
+    for args in [
+        &["-U", "-v", "a", "f"][..],
+        &["-U", "-C", "1", "a", "f"],
+        &["-U", "-e", "a", "-e", "b", "f"],
+        &["-U", "-r", "x", "a", "f"],
+    ] {
+        assert!(
+            matches!(build(args), Err(ConfigError::Conflicts { .. })),
+            "{args:?}"
+        );
+    }
+}
+
+#[test]
+fn test_cli_multiline_search() {
+    let path = std::env::temp_dir().join(format!("minigrep-multiline-{}", std::process::id()));
+    fs::write(&path, CONFIG).unwrap();
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+            .args(args)
+            .arg(&path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(
+        run(&["-U", "-n", "-E", r"^\w+ \w* ?\{\n\s+\w+"]),
+        "2:server {\n3:    listen 80;\n5:upstream api {\n6:    server 10.0.0.1;\n"
+    );
+    // 라인 단위 검색으로는 줄바꿈을 넘을 수 없습니다.
+    assert_eq!(run(&["-E", r"\{\n"]), "");
+    assert_eq!(run(&["-U", "-c", "-E", r"\{\n[^}]*\}"]), "6\n");
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_cli_multiline_lists_files_without_reading_everything() {
+    // `yes needle | minigrep -l -U needle -` 처럼 끝나지 않는 입력에서도 첫 매치에서 끝나야 합니다.
+    for (flag, expected) in [("-l", "(standard input)\n"), ("-L", "")] {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+            .args([flag, "-U", "-E", r"needle\nneedle", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdin = child.stdin.take().unwrap();
+        let writer = thread::spawn(move || while stdin.write_all(b"needle\n").is_ok() {});
+
+        let started = Instant::now();
+        while child.try_wait().unwrap().is_none() {
+            if started.elapsed() > Duration::from_secs(10) {
+                child.kill().unwrap();
+                panic!("minigrep {flag} -U did not stop after the first match");
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let output = child.wait_with_output().unwrap();
+        writer.join().unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            expected,
+            "{flag}"
+        );
+    }
+}
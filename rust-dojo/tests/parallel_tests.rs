@@ -0,0 +1,105 @@
+//! 병렬 검색 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test parallel_tests
+//! ```
+
+use minigrep::parallel::ordered_map;
+use minigrep::*;
+use std::io;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_ordered_map_keeps_input_order() {
+    let items: Vec<u64> = (0..32).collect();
+    let mut seen = Vec::new();
+    ordered_map(
+        &items,
+        4,
+        |&n| {
+            // 앞쪽 작업일수록 오래 걸리게 해서 완료 순서를 뒤섞습니다.
+            thread::sleep(Duration::from_millis(32 - n));
+            n * 10
+        },
+        |result| {
+            seen.push(result);
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(seen, (0..32).map(|n| n * 10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_ordered_map_stops_on_sink_error() {
+    let items: Vec<u32> = (0..100).collect();
+    let mut delivered = 0;
+    let result = ordered_map(
+        &items,
+        3,
+        |&n| n,
+        |n| {
+            delivered += 1;
+            if n == 5 {
+                Err(io::Error::other("stop"))
+            } else {
+                Ok(())
+            }
+        },
+    );
+    assert!(result.is_err());
+    assert_eq!(delivered, 6);
+}
+
+#[test]
+fn test_ordered_map_single_thread_and_empty() {
+    let mut seen = Vec::new();
+    ordered_map(
+        &[1, 2, 3],
+        1,
+        |n| n + 1,
+        |n| {
+            seen.push(n);
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(seen, vec![2, 3, 4]);
+
+    let empty: [u8; 0] = [];
+    ordered_map(&empty, 8, |n| *n, |_| Ok(())).unwrap();
+}
+
+#[test]
+fn test_threads_option() {
+    let args: Vec<String> = ["minigrep", "--threads", "3", "x", "."]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(Config::build(&args).unwrap().threads, 3);
+    let args: Vec<String> = ["minigrep", "-j8", "x", "."]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(Config::build(&args).unwrap().threads, 8);
+}
+
+#[test]
+fn test_parallel_output_matches_sequential() {
+    let grep = |threads: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+            .args(["-n", "-C1", "-j", threads, "o", "tests/fixtures"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let sequential = grep("1");
+    assert!(sequential.contains("tests/fixtures/poem.txt:"));
+    for _ in 0..5 {
+        assert_eq!(grep("4"), sequential);
+    }
+}
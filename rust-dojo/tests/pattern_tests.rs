@@ -0,0 +1,228 @@
+//! 정규 표현식 엔진(`Pattern`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test pattern_tests
+//! ```
+
+use minigrep::*;
+
+mod regex_syntax {
+    use super::*;
+
+    #[test]
+    fn test_literal_pattern_ignores_metacharacters() {
+        let pattern = Pattern::literal("a.b");
+        assert!(pattern.is_match("xa.by"));
+        assert!(!pattern.is_match("axb"));
+    }
+
+    #[test]
+    fn test_anchors_and_dot_star() {
+        let pattern = Pattern::new("^ERROR .*timeout$").unwrap();
+        assert!(pattern.is_match("ERROR db: connection timeout"));
+        assert!(!pattern.is_match("WARN db: connection timeout"));
+        assert!(!pattern.is_match("ERROR db: timeout exceeded"));
+    }
+
+    #[test]
+    fn test_character_classes() {
+        let pattern = Pattern::new(r"[A-Z]\d{3}-[^x\s]+").unwrap();
+        assert_eq!(pattern.find("id: A123-abc def"), Some(4..12));
+        assert!(!pattern.is_match("a123-abc"));
+        assert!(!pattern.is_match("A123-xyz"));
+    }
+
+    #[test]
+    fn test_alternation_and_groups() {
+        let pattern = Pattern::new("(cat|dog)s?$").unwrap();
+        assert!(pattern.is_match("raining cats"));
+        assert!(pattern.is_match("hot dog"));
+        assert!(!pattern.is_match("cow"));
+    }
+
+    #[test]
+    fn test_leftmost_first_and_lazy_repetition() {
+        let greedy = Pattern::new("<.+>").unwrap();
+        let lazy = Pattern::new("<.+?>").unwrap();
+        let text = "<a><b>";
+        assert_eq!(greedy.find(text), Some(0..6));
+        assert_eq!(lazy.find(text), Some(0..3));
+    }
+
+    #[test]
+    fn test_counted_repetition() {
+        let pattern = Pattern::new("^a{2,3}$").unwrap();
+        assert!(!pattern.is_match("a"));
+        assert!(pattern.is_match("aa"));
+        assert!(pattern.is_match("aaa"));
+        assert!(!pattern.is_match("aaaa"));
+    }
+
+    #[test]
+    fn test_captures_and_named_groups() {
+        let pattern = Pattern::new(r"(?P<key>\w+)=(\d+)").unwrap();
+        let caps = pattern.captures("retries=42").unwrap();
+        assert_eq!(caps.get(0), Some(0..10));
+        assert_eq!(caps.name("key"), Some(0..7));
+        assert_eq!(caps.get(2), Some(8..10));
+    }
+
+    #[test]
+    fn test_find_iter_non_overlapping() {
+        let pattern = Pattern::new(r"\d+").unwrap();
+        let found: Vec<_> = pattern.find_iter(b"a1 b22 c333").collect();
+        assert_eq!(found, vec![1..2, 4..6, 8..11]);
+    }
+
+    #[test]
+    fn test_word_boundary_and_unicode() {
+        let pattern = Pattern::new(r"\b에러\b").unwrap();
+        assert!(pattern.is_match("치명적 에러 발생"));
+        assert!(!pattern.is_match("치명적에러발생"));
+    }
+
+    #[test]
+    fn test_case_insensitive_option() {
+        let options = PatternOptions {
+            regex: true,
+            case_insensitive: true,
+            ..PatternOptions::default()
+        };
+        let pattern = Pattern::with_options("^error [a-c]+", &options).unwrap();
+        assert!(pattern.is_match("ERROR ABC"));
+    }
+
+    #[test]
+    fn test_word_option_requires_non_word_neighbours() {
+        let options = PatternOptions {
+            word: true,
+            ..PatternOptions::default()
+        };
+        let pattern = Pattern::with_options("@err", &options).unwrap();
+        assert!(pattern.is_match("x @err y"));
+        assert!(!pattern.is_match("x @errors"));
+        // 첫 후보가 실패해도 뒤쪽 후보를 계속 찾아야 합니다.
+        assert_eq!(pattern.find("@errno @err"), Some(7..11));
+    }
+
+    #[test]
+    fn test_invalid_patterns_are_errors() {
+        assert!(matches!(
+            Pattern::new("(abc"),
+            Err(PatternError::UnclosedGroup(0))
+        ));
+        assert!(matches!(
+            Pattern::new("abc)"),
+            Err(PatternError::UnopenedGroup(3))
+        ));
+        assert!(matches!(
+            Pattern::new("[abc"),
+            Err(PatternError::UnclosedClass(0))
+        ));
+        assert!(matches!(
+            Pattern::new("*a"),
+            Err(PatternError::NothingToRepeat(0))
+        ));
+        assert!(matches!(
+            Pattern::new("[z-a]"),
+            Err(PatternError::InvalidRange(1))
+        ));
+    }
+
+    #[test]
+    fn test_program_size_and_nesting_limits() {
+        // 반복 하나하나는 상한 안이어도 중첩하면 프로그램이 곱절로 커집니다.
+        assert_eq!(
+            Pattern::new("((a?){1000}){200}b").unwrap_err(),
+            PatternError::TooLarge
+        );
+        let deep = format!("{}a{}", "(".repeat(300), ")".repeat(300));
+        assert!(matches!(
+            Pattern::new(&deep),
+            Err(PatternError::NestingTooDeep(_))
+        ));
+        assert!(matches!(
+            Pattern::new(&format!("a{}", "*".repeat(300))),
+            Err(PatternError::NestingTooDeep(_))
+        ));
+        // 리터럴은 길이만큼만 커지므로 제한하지 않습니다.
+        assert!(Pattern::literal(&"x".repeat(200_000)).is_match(&"x".repeat(200_000)));
+    }
+
+    #[test]
+    fn test_long_epsilon_chains_do_not_overflow_the_stack() {
+        // 선택 반복 8 만 개가 epsilon 전이로 한 줄로 이어집니다.
+        let pattern = Pattern::new("((?:a?){1000}){40}b").unwrap();
+        assert_eq!(pattern.find("xaab"), Some(1..4));
+        assert!(!pattern.is_match("aaa"));
+    }
+}
+
+mod regex_mode {
+    use super::*;
+
+    #[test]
+    fn test_config_regex_flag() {
+        let args = vec![
+            String::from("minigrep"),
+            String::from("--regex"),
+            String::from("^How"),
+            String::from("tests/fixtures/poem.txt"),
+        ];
+        let config = Config::build(&args).unwrap();
+        assert!(config.regex);
+        assert_eq!(config.query, "^How");
+        assert_eq!(config.paths, vec!["tests/fixtures/poem.txt"]);
+    }
+
+    #[test]
+    fn test_search_pattern() {
+        let contents = read_file_safe("tests/fixtures/poem.txt").unwrap();
+        let pattern = Pattern::new("^How").unwrap();
+        let results = search_pattern(&pattern, &contents);
+        assert_eq!(
+            results,
+            vec!["How dreary to be somebody!", "How public, like a frog"]
+        );
+    }
+
+    #[test]
+    fn test_run_reports_invalid_regex() {
+        let args = vec![
+            String::from("minigrep"),
+            String::from("--regex"),
+            String::from("(unclosed"),
+            String::from("tests/fixtures/poem.txt"),
+        ];
+        let config = Config::build(&args).unwrap();
+        assert!(run(config).is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_oversized_regex() {
+        // 크기 제한도 문법 에러와 같이 `Config::matcher` 에서 한 번에 보고합니다.
+        let oversized = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            let error = Config::build(&args).unwrap().matcher().unwrap_err();
+            error.downcast_ref::<PatternError>() == Some(&PatternError::TooLarge)
+        };
+        for pattern in ["((a?){1000}){200}b", "x(((a{1000}){1000}))"] {
+            assert!(oversized(&[
+                "minigrep",
+                "-E",
+                pattern,
+                "tests/fixtures/poem.txt"
+            ]));
+        }
+        assert!(oversized(&[
+            "minigrep",
+            "-E",
+            "-e",
+            "ok",
+            "-e",
+            "(a{1000}){1000}",
+            "f"
+        ]));
+    }
+}
@@ -0,0 +1,191 @@
+//! 검색 후 치환(`--replace`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test replace_tests
+//! ```
+
+use minigrep::replace::{replace_line, write_atomic, Rewrite};
+use minigrep::*;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn regex(pattern: &str) -> Pattern {
+    Pattern::new(pattern).unwrap()
+}
+
+/// 테스트마다 겹치지 않는 임시 디렉토리
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("minigrep-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_template_numbered_and_named_groups() {
+    let pattern = regex(r"(?P<key>\w+)=(\d+)");
+    let template = Template::new("${key}: $2 ($$)", &pattern).unwrap();
+    assert_eq!(
+        replace_line(&pattern, &template, b"a=1, bb=22").unwrap(),
+        b"a: 1 ($), bb: 22 ($)"
+    );
+    assert_eq!(replace_line(&pattern, &template, b"no match"), None);
+}
+
+#[test]
+fn test_template_errors() {
+    let pattern = regex(r"(\w+)");
+    assert_eq!(
+        Template::new("$2", &pattern),
+        Err(TemplateError::UnknownGroup(String::from("2")))
+    );
+    assert_eq!(
+        Template::new("$name", &pattern),
+        Err(TemplateError::UnknownGroup(String::from("name")))
+    );
+    assert_eq!(
+        Template::new("x ${1", &pattern),
+        Err(TemplateError::UnclosedBrace(2))
+    );
+}
+
+#[test]
+fn test_rewrite_keeps_line_endings() {
+    let pattern = Pattern::literal("foo");
+    let template = Template::new("bar", &pattern).unwrap();
+    let contents = b"foo\r\nkeep\nfoo";
+    let rewrite = Rewrite::new(&pattern, &template, contents);
+    assert_eq!(rewrite.changed_lines(), 2);
+    assert_eq!(rewrite.apply(), b"bar\r\nkeep\nbar");
+}
+
+#[test]
+fn test_unified_diff_hunks() {
+    let pattern = Pattern::literal("x");
+    let template = Template::new("y", &pattern).unwrap();
+    let contents = b"x\n2\n3\n4\n5\n6\n7\n8\n9\nx\n";
+    let diff = Rewrite::new(&pattern, &template, contents).unified_diff("f.txt");
+    assert_eq!(
+        String::from_utf8(diff).unwrap(),
+        "--- f.txt\n+++ f.txt\n\
+         @@ -1,4 +1,4 @@\n-x\n+y\n 2\n 3\n 4\n\
+         @@ -7,4 +7,4 @@\n 7\n 8\n 9\n-x\n+y\n"
+    );
+}
+
+#[test]
+fn test_unified_diff_marks_missing_newline() {
+    let pattern = Pattern::literal("end");
+    let template = Template::new("END", &pattern).unwrap();
+    let diff = Rewrite::new(&pattern, &template, b"a\nend").unified_diff("f");
+    assert!(String::from_utf8(diff)
+        .unwrap()
+        .ends_with("-end\n\\ No newline at end of file\n+END\n\\ No newline at end of file\n"));
+}
+
+#[test]
+fn test_write_atomic_replaces_contents() {
+    let dir = temp_dir("write-atomic");
+    let path = dir.join("a.txt");
+    fs::write(&path, "old").unwrap();
+    write_atomic(&path, b"new").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    // 임시 파일이 남지 않아야 합니다.
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_atomic_through_symlink() {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    let dir = temp_dir("write-symlink");
+    fs::create_dir(dir.join("real")).unwrap();
+    let target = dir.join("real/a.sh");
+    fs::write(&target, "old").unwrap();
+    fs::set_permissions(&target, fs::Permissions::from_mode(0o750)).unwrap();
+    let link = dir.join("link.sh");
+    symlink("real/a.sh", &link).unwrap();
+
+    write_atomic(&link, b"new").unwrap();
+    // 링크는 그대로 남고 가리키던 파일의 내용과 권한이 바뀝니다.
+    assert!(fs::symlink_metadata(&link).unwrap().is_symlink());
+    assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("real/a.sh"));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+    let mode = fs::metadata(&target).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o750);
+    // 임시 파일은 실제 파일 옆에 만들었다가 지웁니다.
+    assert_eq!(fs::read_dir(dir.join("real")).unwrap().count(), 1);
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_replace_mode_prints_diff_without_writing() {
+    let dir = temp_dir("dry-run");
+    let path = dir.join("conf.ini");
+    fs::write(&path, "port=80\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-E", "-r", "$1 = $2", r"(\w+)=(\d+)"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("@@ -1,1 +1,1 @@\n-port=80\n+port = 80\n"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "port=80\n");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-E", "-r", "$1 = $2", "--write", r"(\w+)=(\d+)"])
+        .arg(&path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "port = 80\n");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_write_requires_replace() {
+    let args: Vec<String> = ["minigrep", "--write", "a", "b.txt"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(matches!(
+        Config::build(&args),
+        Err(ConfigError::Requires { .. })
+    ));
+}
+
+#[test]
+fn test_replace_option_conflicts() {
+    let build = |args: &[&str]| {
+        let args: Vec<String> = std::iter::once("minigrep")
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect();
+        Config::build(&args)
+    };
+    assert!(build(&["-r", "X", "-i", "-w", "foo", "f"]).is_ok());
+    for (args, with) in [
+        (&["-r", "X", "-v", "foo", "f"][..], "'--invert-match'"),
+        (&["-r", "X", "--json", "foo", "f"], "'--json'"),
+        (&["-r", "X", "-c", "foo", "f"], "'--count'"),
+        (&["-r", "X", "-l", "foo", "f"], "'--files-with-matches'"),
+        (&["-r", "X", "-C", "2", "foo", "f"], "'--context'"),
+        (&["-r", "X", "-A1", "foo", "f"], "'--context'"),
+        (&["-r", "X", "-e", "a", "-e", "b", "f"], "multiple patterns"),
+    ] {
+        assert_eq!(
+            build(args),
+            Err(ConfigError::Conflicts {
+                option: String::from("--replace"),
+                with: String::from(with),
+            }),
+            "{args:?}"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! 라이브러리 검색 API(`Searcher`, `Sink`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test searcher_tests
+//! ```
+
+use minigrep::*;
+use std::fs;
+use std::io;
+
+fn build(args: &[&str]) -> Config {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args).unwrap()
+}
+
+fn searcher(pattern: &str, options: StreamOptions) -> Searcher {
+    Searcher::new(PatternSet::single(Pattern::new(pattern).unwrap())).with_options(options)
+}
+
+/// 콜백이 불린 순서를 기록하는 싱크
+#[derive(Default)]
+struct Events {
+    events: Vec<String>,
+    search_binary: bool,
+}
+
+impl Sink for Events {
+    fn matched(&mut self, line: &LineMatch) -> io::Result<()> {
+        self.events.push(format!("match {}", line.line_number));
+        Ok(())
+    }
+
+    fn context(&mut self, line: &LineMatch) -> io::Result<()> {
+        self.events.push(format!("context {}", line.line_number));
+        Ok(())
+    }
+
+    fn context_break(&mut self) -> io::Result<()> {
+        self.events.push(String::from("break"));
+        Ok(())
+    }
+
+    fn binary_detected(&mut self) -> io::Result<bool> {
+        self.events.push(String::from("binary"));
+        Ok(self.search_binary)
+    }
+
+    fn finish(&mut self, summary: &StreamSummary) -> io::Result<()> {
+        self.events
+            .push(format!("finish {}", summary.matched_lines));
+        Ok(())
+    }
+}
+
+const LOG: &str = "\
+INFO start
+ERROR disk
+INFO a
+INFO b
+INFO c
+INFO d
+ERROR net
+";
+
+#[test]
+fn test_collector_gathers_structured_results() {
+    let searcher = searcher("ERROR", StreamOptions::default());
+    let mut collector = Collector::new();
+    let summary = searcher
+        .search_slice(LOG.as_bytes(), &mut collector)
+        .unwrap();
+    assert_eq!(summary.matched_lines, 2);
+    assert_eq!(collector.summary, summary);
+    let lines: Vec<(usize, &str)> = collector
+        .matches
+        .iter()
+        .map(|m| (m.line_number, m.content.as_str()))
+        .collect();
+    assert_eq!(lines, [(2, "ERROR disk"), (7, "ERROR net")]);
+    assert_eq!(collector.matches[0].matches[0].bytes, 0..5);
+    assert!(collector.context.is_empty());
+
+    // 같은 검색기를 다른 입력에 재사용하면 요약이 누적됩니다.
+    searcher
+        .search_reader(&b"ERROR again\n"[..], &mut collector)
+        .unwrap();
+    assert_eq!(collector.matches.len(), 3);
+    assert_eq!(collector.summary.matched_lines, 3);
+    assert_eq!(
+        format_results(&collector.matches[..1]),
+        "[Line2], ERROR disk"
+    );
+}
+
+#[test]
+fn test_sink_callbacks_with_context() {
+    let options = StreamOptions {
+        before: 1,
+        ..StreamOptions::default()
+    };
+    let mut sink = Events::default();
+    searcher("ERROR", options)
+        .search_slice(LOG.as_bytes(), &mut sink)
+        .unwrap();
+    assert_eq!(
+        sink.events,
+        [
+            "context 1",
+            "match 2",
+            "break",
+            "context 6",
+            "match 7",
+            "finish 2"
+        ]
+    );
+}
+
+#[test]
+fn test_binary_detected() {
+    let binary = b"\0\0header\nERROR inside\n";
+    let searcher = searcher("ERROR", StreamOptions::default());
+
+    // 기본값은 건너뛰고 `finish` 도 부르지 않습니다.
+    let mut collector = Collector::new();
+    let summary = searcher.search_slice(binary, &mut collector).unwrap();
+    assert_eq!(summary, StreamSummary::default());
+    assert_eq!(collector.binary_inputs, 1);
+    assert!(collector.matches.is_empty());
+
+    let mut sink = Events {
+        search_binary: true,
+        ..Events::default()
+    };
+    searcher.search_slice(binary, &mut sink).unwrap();
+    assert_eq!(sink.events, ["binary", "match 2", "finish 1"]);
+}
+
+#[test]
+fn test_search_path_fills_paths() {
+    let path = std::env::temp_dir().join(format!("minigrep-searcher-{}", std::process::id()));
+    fs::write(&path, LOG).unwrap();
+    let config = build(&["-i", "error", "unused"]);
+    let mut collector = Collector::new();
+    config
+        .searcher()
+        .unwrap()
+        .search_path(&path, &mut collector)
+        .unwrap();
+    let display = path.display().to_string();
+    assert_eq!(collector.matches.len(), 2);
+    assert_eq!(collector.matches[1].path.as_deref(), Some(display.as_str()));
+    fs::remove_file(&path).unwrap();
+
+    let error = config
+        .searcher()
+        .unwrap()
+        .search_path(&path, &mut Collector::new())
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_printer_matches_cli_output() {
+    let style = OutputStyle {
+        with_path: false,
+        line_number: true,
+        column: false,
+    };
+    let options = StreamOptions {
+        after: 1,
+        ..StreamOptions::default()
+    };
+    let searcher = searcher("ERROR", options);
+    let mut printer = Printer::new(Vec::new(), style, Palette::PLAIN).with_context(true);
+    searcher.search_slice(LOG.as_bytes(), &mut printer).unwrap();
+    assert!(printer.has_blocks());
+    let text = String::from_utf8(printer.into_inner()).unwrap();
+    assert_eq!(text, "2:ERROR disk\n3-INFO a\n--\n7:ERROR net\n");
+
+    // 앞 입력에서 블록을 출력했다면 첫 블록 앞에도 구분선이 들어갑니다.
+    let mut printer = Printer::new(Vec::new(), style, Palette::PLAIN)
+        .with_context(true)
+        .after_block(true);
+    searcher.search_slice(b"ERROR x\n", &mut printer).unwrap();
+    assert_eq!(printer.into_inner(), b"--\n1:ERROR x\n");
+
+    // `quiet` 은 아무것도 출력하지 않고 요약만 남깁니다.
+    let mut printer = Printer::new(Vec::new(), style, Palette::PLAIN).quiet(true);
+    let summary = searcher.search_slice(LOG.as_bytes(), &mut printer).unwrap();
+    assert_eq!(summary.matched_lines, 2);
+    assert!(printer.into_inner().is_empty());
+}
+
+#[test]
+fn test_multiline_searcher() {
+    let options = PatternOptions {
+        regex: true,
+        multiline: true,
+        ..PatternOptions::default()
+    };
+    let pattern = Pattern::with_options(r"disk\nINFO a", &options).unwrap();
+    let mut collector = Collector::new();
+    Searcher::new(PatternSet::single(pattern))
+        .with_multiline(true)
+        .search_slice(LOG.as_bytes(), &mut collector)
+        .unwrap();
+    assert_eq!(collector.matches.len(), 1);
+    assert_eq!(collector.matches[0].line_number, 2);
+    assert_eq!(collector.matches[0].end_line_number, 3);
+
+    // 콜백의 에러는 그대로 전파됩니다.
+    struct Failing;
+    impl Sink for Failing {
+        fn matched(&mut self, _line: &LineMatch) -> io::Result<()> {
+            Err(io::Error::other("stop"))
+        }
+    }
+    let error = searcher("ERROR", StreamOptions::default())
+        .search_slice(LOG.as_bytes(), &mut Failing)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "stop");
+}
@@ -0,0 +1,187 @@
+//! 스트리밍 검색 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test stream_tests
+//! ```
+
+use minigrep::stream::search_reader;
+use minigrep::*;
+use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
+use std::process::{Command, Stdio};
+
+fn collect(pattern: &Pattern, input: &[u8], options: &StreamOptions) -> Vec<StreamItem> {
+    let mut items = Vec::new();
+    search_reader(pattern, Cursor::new(input), options, |item| {
+        items.push(item);
+        Ok(())
+    })
+    .unwrap();
+    items
+}
+
+/// 몇 줄을 돌려준 뒤 에러를 내는 리더
+struct FailingReader {
+    data: Cursor<Vec<u8>>,
+}
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.data.read(buf)? {
+            0 => Err(io::Error::other("disk on fire")),
+            n => Ok(n),
+        }
+    }
+}
+
+#[test]
+fn test_streaming_matches_with_line_numbers() {
+    let pattern = Pattern::literal("error");
+    let items = collect(
+        &pattern,
+        b"ok\nerror a\nok\nerror b\n",
+        &StreamOptions::default(),
+    );
+    let numbers: Vec<usize> = items
+        .iter()
+        .map(|item| match item {
+            StreamItem::Line(line) => line.line_number,
+            StreamItem::Block(_) => unreachable!(),
+        })
+        .collect();
+    assert_eq!(numbers, vec![2, 4]);
+}
+
+#[test]
+fn test_summary_counts_bytes_and_lines() {
+    let pattern = Pattern::literal("o");
+    let summary = search_reader(
+        &pattern,
+        Cursor::new("one\ntwo\nthree\n"),
+        &StreamOptions::default(),
+        |_| Ok(()),
+    )
+    .unwrap();
+    assert_eq!(summary.bytes_read, 14);
+    assert_eq!(summary.matched_lines, 2);
+}
+
+#[test]
+fn test_crlf_and_invalid_utf8_lines() {
+    let pattern = Pattern::literal("caf");
+    let items = collect(
+        &pattern,
+        b"caf\xe9 latin1\r\nnothing\r\n",
+        &StreamOptions::default(),
+    );
+    match &items[0] {
+        StreamItem::Line(line) => assert_eq!(line.content, "caf\u{FFFD} latin1"),
+        StreamItem::Block(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn test_context_blocks_emitted_while_streaming() {
+    let pattern = Pattern::literal("ERROR");
+    let options = StreamOptions {
+        before: 1,
+        after: 1,
+        ..StreamOptions::default()
+    };
+    let items = collect(&pattern, b"a\nERROR 1\nb\nc\nd\nERROR 2\ne\n", &options);
+    assert_eq!(items.len(), 2);
+    match &items[1] {
+        StreamItem::Block(block) => {
+            assert_eq!(block.first_line_number(), Some(5));
+            assert_eq!(block.last_line_number(), Some(7));
+        }
+        StreamItem::Line(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn test_results_before_read_error_are_delivered() {
+    let reader = BufReader::new(FailingReader {
+        data: Cursor::new(b"error one\nerror two\n".to_vec()),
+    });
+    let pattern = Pattern::literal("error");
+    let mut seen = 0;
+    let result = search_reader(&pattern, reader, &StreamOptions::default(), |_| {
+        seen += 1;
+        Ok(())
+    });
+    assert_eq!(seen, 2);
+    assert_eq!(result.unwrap_err().to_string(), "disk on fire");
+}
+
+#[test]
+fn test_emit_error_stops_search() {
+    let pattern = Pattern::literal("x");
+    let mut calls = 0;
+    let result = search_reader(
+        &pattern,
+        Cursor::new("x\nx\nx\n"),
+        &StreamOptions::default(),
+        |_| {
+            calls += 1;
+            Err(io::Error::from(io::ErrorKind::BrokenPipe))
+        },
+    );
+    assert_eq!(calls, 1);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_long_input_is_not_buffered_whole() {
+    // 매 줄마다 결과가 바로 나오는지 확인: 첫 결과를 받을 때까지 읽은 바이트는 한 줄 분량뿐입니다.
+    let line = "error line\n";
+    let input = line.repeat(10_000);
+    let mut reader = BufReader::with_capacity(64, Cursor::new(input.as_bytes()));
+    assert!(!reader.fill_buf().unwrap().is_empty());
+    let pattern = Pattern::literal("error");
+    let mut first_seen = None;
+    let mut count = 0;
+    search_reader(&pattern, reader, &StreamOptions::default(), |item| {
+        count += 1;
+        if let (None, StreamItem::Line(line)) = (&first_seen, &item) {
+            first_seen = Some(line.line_number);
+        }
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(first_seen, Some(1));
+    assert_eq!(count, 10_000);
+}
+
+#[test]
+fn test_run_propagates_missing_file_error() {
+    let args: Vec<String> = ["minigrep", "nobody", "tests/fixtures/missing.txt"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let config = Config::build(&args).unwrap();
+    let err = run(config).unwrap_err();
+    assert!(err.to_string().contains("tests/fixtures/missing.txt"));
+}
+
+#[test]
+fn test_cli_reads_stdin_without_path() {
+    // `printf ... | minigrep x` 처럼 PATH 가 없으면 `-` 를 준 것과 같이 표준 입력을 읽습니다.
+    for args in [&["-n", "x"][..], &["-n", "x", "-"]] {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"x\ny\nyx\n")
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        assert_eq!(output.status.code(), Some(0), "{output:?}");
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "1:x\n3:yx\n");
+    }
+}
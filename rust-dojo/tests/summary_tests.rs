@@ -0,0 +1,185 @@
+//! 요약 출력(`-c`, `-l`, `-L`, `--stats`)과 첫 매치에서 멈추는 검색 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test summary_tests
+//! ```
+
+use minigrep::*;
+use std::fs;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("minigrep-{}-{}", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn minigrep(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// 첫 줄에만 매치가 있는 큰 입력
+fn needle_then_hay() -> String {
+    let mut text = String::from("needle first\n");
+    text.push_str(&"hay\n".repeat(200_000));
+    text.push_str("needle last\n");
+    text
+}
+
+#[test]
+fn test_max_count_stops_reading() {
+    let text = needle_then_hay();
+    let options = StreamOptions {
+        max_count: Some(1),
+        ..StreamOptions::default()
+    };
+    // 리터럴 청크 검색과 라인 단위 정규식 검색 모두 첫 매치에서 멈춥니다.
+    for pattern in [Pattern::literal("needle"), Pattern::new("ne+dle").unwrap()] {
+        let mut lines = Vec::new();
+        // 파일처럼 버퍼 크기만큼씩 읽는 입력
+        let reader = BufReader::new(text.as_bytes());
+        let summary = stream::search_reader(&pattern, reader, &options, |item| {
+            lines.push(item);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(summary.matched_lines, 1);
+        assert!(summary.bytes_read < text.len() as u64 / 2, "{summary:?}");
+    }
+
+    let unlimited = stream::search_reader(
+        &Pattern::literal("needle"),
+        text.as_bytes(),
+        &StreamOptions::default(),
+        |_| Ok(()),
+    )
+    .unwrap();
+    assert_eq!(unlimited.matched_lines, 2);
+    assert_eq!(unlimited.bytes_read, text.len() as u64);
+}
+
+#[test]
+fn test_max_count_keeps_trailing_context() {
+    let options = StreamOptions {
+        after: 1,
+        max_count: Some(1),
+        ..StreamOptions::default()
+    };
+    let mut blocks = Vec::new();
+    let text = "a\nERROR 1\nERROR 2\nb\nERROR 3\n";
+    let summary = stream::search_reader(
+        &Pattern::literal("ERROR"),
+        text.as_bytes(),
+        &options,
+        |item| {
+            blocks.push(item);
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(summary.matched_lines, 1);
+    // 한도를 채운 뒤의 매치는 뒤쪽 컨텍스트로만 나옵니다.
+    let StreamItem::Block(block) = &blocks[0] else {
+        panic!("expected a block: {blocks:?}");
+    };
+    let kinds: Vec<(usize, bool)> = block
+        .lines
+        .iter()
+        .map(|line| (line.line().line_number, line.is_match()))
+        .collect();
+    assert_eq!(kinds, [(2, true), (3, false)]);
+}
+
+#[test]
+fn test_summary_option_conflicts() {
+    let config = build(&["-L", "--stats", "x", "f"]).unwrap();
+    assert!(config.files_without_match && config.stats);
+    // `-l` 과 `-L` 은 첫 매치에서 멈추는 검색기를 만듭니다.
+    let searcher = build(&["-l", "x", "f"]).unwrap().searcher().unwrap();
+    assert_eq!(searcher.options().max_count, Some(1));
+    let searcher = build(&["-c", "x", "f"]).unwrap().searcher().unwrap();
+    assert_eq!(searcher.options().max_count, None);
+
+    for args in [
+        &["-L", "-l", "x", "f"][..],
+        &["-L", "-c", "x", "f"],
+        &["-L", "--index", "x", "f"],
+        &["--stats", "--json", "x", "f"],
+        &["--stats", "--fuzzy", "1", "x", "f"],
+    ] {
+        assert!(
+            matches!(build(args), Err(ConfigError::Conflicts { .. })),
+            "{args:?}"
+        );
+    }
+}
+
+#[test]
+fn test_cli_files_with_and_without_match() {
+    let big = temp_file("summary-big", &needle_then_hay());
+    let none = temp_file("summary-none", "nothing here\n");
+    let (big, none) = (big.to_str().unwrap(), none.to_str().unwrap());
+
+    assert_eq!(minigrep(&["-l", "needle", big, none]), format!("{big}\n"));
+    assert_eq!(minigrep(&["-L", "needle", big, none]), format!("{none}\n"));
+    assert_eq!(
+        minigrep(&["-c", "needle", big, none]),
+        format!("{big}:2\n{none}:0\n")
+    );
+    // fuzzy 모드에서도 같은 의미입니다.
+    assert_eq!(
+        minigrep(&["-L", "--fuzzy", "1", "needel", big, none]),
+        format!("{none}\n")
+    );
+
+    fs::remove_file(big).unwrap();
+    fs::remove_file(none).unwrap();
+}
+
+#[test]
+fn test_cli_stats_footer() {
+    let log = temp_file("summary-stats", "ERROR a\nINFO\nERROR b ERROR c\n");
+    let empty = temp_file("summary-stats-empty", "INFO\n");
+    let (log, empty) = (log.to_str().unwrap(), empty.to_str().unwrap());
+
+    let out = minigrep(&["--stats", "ERROR", log, empty]);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(
+        lines[..8],
+        [
+            format!("{log}:ERROR a").as_str(),
+            format!("{log}:ERROR b ERROR c").as_str(),
+            "",
+            "3 matches",
+            "2 matched lines",
+            "1 files contained matches",
+            "2 files searched",
+            "34 bytes searched",
+        ]
+    );
+    assert!(lines[8].ends_with(" seconds"), "{out}");
+
+    // `-l` 은 첫 매치까지만 읽으므로 통계에도 읽은 만큼만 나옵니다.
+    let out = minigrep(&["-l", "--stats", "ERROR", log]);
+    assert!(out.contains("\n1 matched lines\n"), "{out}");
+
+    fs::remove_file(log).unwrap();
+    fs::remove_file(empty).unwrap();
+}
@@ -0,0 +1,215 @@
+//! 트라이그램 색인(`minigrep index build`, `--index`) 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test trigram_tests
+//! ```
+
+use minigrep::trigram::{self, BuildStats, INDEX_FILE};
+use minigrep::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn build(args: &[&str]) -> Result<Config, ConfigError> {
+    let args: Vec<String> = std::iter::once("minigrep")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+    Config::build(&args)
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("minigrep-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn minigrep(args: &[&str], dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(args)
+        .arg(dir)
+        .output()
+        .unwrap()
+}
+
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    walk::collect_files(&[dir]).unwrap()
+}
+
+#[test]
+fn test_trigrams_are_sorted_and_unique() {
+    assert_eq!(trigram::trigrams(b"aaaab"), vec![0x61_61_61, 0x61_61_62]);
+    assert!(trigram::trigrams(b"ab").is_empty());
+}
+
+#[test]
+fn test_candidates_intersect_postings() {
+    let dir = temp_dir("trigram-candidates");
+    fs::write(dir.join("a.rs"), "fn unwrap_or_default() {}\n").unwrap();
+    fs::write(dir.join("b.rs"), "let x = wrap(or);\n").unwrap();
+    fs::write(dir.join("c.bin"), b"unwrap\0binary").unwrap();
+    let (index, stats) = TrigramIndex::build(&dir, &files_in(&dir), None).unwrap();
+    assert_eq!(
+        stats,
+        BuildStats {
+            files: 3,
+            updated: 3,
+            removed: 0
+        }
+    );
+    let names: Vec<&str> = index
+        .files()
+        .iter()
+        .map(|entry| entry.path.to_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["a.rs", "b.rs", "c.bin"]);
+
+    // b.rs 에는 "wra", "rap" 는 있지만 "unw" 가 없습니다. 바이너리 파일은 후보가 되지 않습니다.
+    assert_eq!(
+        index.candidates(&[b"unwrap"]),
+        Some(vec![true, false, false])
+    );
+    assert_eq!(
+        index.candidates(&[b"unwrap", b"wrap("]),
+        Some(vec![true, true, false])
+    );
+    assert_eq!(index.candidates(&[b"zzz"]), Some(vec![false; 3]));
+    // 3 바이트보다 짧은 리터럴은 거를 수 없습니다.
+    assert_eq!(index.candidates(&[b"fn"]), None);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_index_round_trips_and_rejects_corruption() {
+    let dir = temp_dir("trigram-roundtrip");
+    fs::create_dir(dir.join("sub")).unwrap();
+    fs::write(dir.join("sub/한글.txt"), "서울특별시 중구\n").unwrap();
+    fs::write(dir.join("x.txt"), "hello world\n").unwrap();
+    let (index, _) = TrigramIndex::build(&dir, &files_in(&dir), None).unwrap();
+    index.save(&dir).unwrap();
+    assert_eq!(TrigramIndex::load(&dir).unwrap(), index);
+
+    let bytes = index.to_bytes();
+    for len in [0, 10, bytes.len() - 1] {
+        assert!(TrigramIndex::from_bytes(&bytes[..len]).is_err());
+    }
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(TrigramIndex::from_bytes(&trailing).is_err());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_incremental_rebuild_reads_only_changed_files() {
+    let dir = temp_dir("trigram-incremental");
+    fs::write(dir.join("keep.txt"), "unchanged contents\n").unwrap();
+    fs::write(dir.join("edit.txt"), "old text\n").unwrap();
+    fs::write(dir.join("gone.txt"), "to be removed\n").unwrap();
+    let (first, _) = TrigramIndex::build(&dir, &files_in(&dir), None).unwrap();
+
+    fs::write(dir.join("edit.txt"), "new text, longer\n").unwrap();
+    fs::remove_file(dir.join("gone.txt")).unwrap();
+    fs::write(dir.join("added.txt"), "fresh file\n").unwrap();
+    let (second, stats) = TrigramIndex::build(&dir, &files_in(&dir), Some(&first)).unwrap();
+    assert_eq!(
+        stats,
+        BuildStats {
+            files: 3,
+            updated: 2,
+            removed: 1
+        }
+    );
+    // 재사용한 결과는 처음부터 다시 만든 색인과 같습니다.
+    let (scratch, _) = TrigramIndex::build(&dir, &files_in(&dir), None).unwrap();
+    assert_eq!(second, scratch);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_index_argument_parsing() {
+    let config = build(&["index", "build", "vendor/", "third_party/"]).unwrap();
+    assert!(config.build_index);
+    assert_eq!(config.paths, vec!["vendor/", "third_party/"]);
+    assert!(matches!(
+        build(&["index", "build"]),
+        Err(ConfigError::NotEnoughArguments)
+    ));
+    // `index build` 가 아니면 평소처럼 "index" 를 검색합니다.
+    let config = build(&["index", "src/"]).unwrap();
+    assert_eq!(
+        (config.query.as_str(), config.build_index),
+        ("index", false)
+    );
+
+    assert!(build(&["--index", "unwrap", "vendor/"]).unwrap().index);
+    assert!(matches!(
+        build(&["--index", "-c", "unwrap", "vendor/"]),
+        Err(ConfigError::Conflicts { .. })
+    ));
+}
+
+#[test]
+fn test_cli_index_search() {
+    let dir = temp_dir("trigram-cli");
+    fs::write(dir.join("a.txt"), "needle here\n").unwrap();
+    fs::write(dir.join("b.txt"), "nothing\n").unwrap();
+
+    // 색인이 없으면 만드는 방법을 알려줍니다.
+    let output = minigrep(&["--index", "needle"], &dir);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("minigrep index build"));
+
+    let output = minigrep(&["index", "build"], &dir);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("indexed 2 files (2 updated, 0 removed)"));
+    assert!(dir.join(INDEX_FILE).is_file());
+
+    let search = |args: &[&str]| String::from_utf8(minigrep(args, &dir).stdout).unwrap();
+    assert_eq!(search(&["--index", "needle"]), search(&["needle"]));
+    // 색인 뒤에 바뀐 파일도 빠지지 않습니다.
+    fs::write(dir.join("b.txt"), "now a needle too\n").unwrap();
+    let found = search(&["--index", "needle"]);
+    assert_eq!(found.lines().count(), 2);
+    assert_eq!(found, search(&["needle"]));
+
+    let output = minigrep(&["index", "build"], &dir);
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("indexed 2 files (1 updated, 0 removed)"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_cli_index_skips_files_without_trigrams() {
+    let dir = temp_dir("trigram-skip");
+    fs::write(dir.join("a.txt"), "needle\n").unwrap();
+    fs::write(dir.join("b.txt"), "hay...\n").unwrap();
+    assert!(minigrep(&["index", "build"], &dir).status.success());
+
+    // 크기와 수정 시각을 그대로 두고 내용만 바꾸면 색인은 b.txt 를 후보에서 뺍니다.
+    // 이렇게 건너뛴 파일이 있다는 것이 색인이 실제로 검색 대상을 줄인다는 증거입니다.
+    let path = dir.join("b.txt");
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    fs::write(&path, "needle\n").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+
+    let output = minigrep(&["-l", "--index", "needle"], &dir);
+    let listed = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(listed.lines().count(), 1);
+    assert!(listed.contains("a.txt"));
+    // 정규식은 꼭 들어 있어야 할 리터럴을 모르므로 색인을 쓰지 않습니다.
+    let output = minigrep(&["-l", "--index", "-E", "need.e"], &dir);
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 2);
+    fs::remove_dir_all(dir).unwrap();
+}
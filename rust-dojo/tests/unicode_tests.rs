@@ -0,0 +1,112 @@
+//! 유니코드 대소문자 접기와 단어 경계 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test unicode_tests
+//! ```
+
+use minigrep::unicode::{contains_folded, is_word_char, simple_fold};
+use minigrep::*;
+
+fn ignore_case(query: &str) -> Pattern {
+    let options = PatternOptions {
+        case_insensitive: true,
+        ..PatternOptions::default()
+    };
+    Pattern::with_options(query, &options).unwrap()
+}
+
+fn word(query: &str) -> Pattern {
+    let options = PatternOptions {
+        word: true,
+        ..PatternOptions::default()
+    };
+    Pattern::with_options(query, &options).unwrap()
+}
+
+#[test]
+fn test_simple_fold_special_cases() {
+    assert_eq!(simple_fold('ẞ'), 'ß');
+    assert_eq!(simple_fold('ſ'), 's');
+    assert_eq!(simple_fold('ς'), 'σ');
+    assert_eq!(simple_fold('Σ'), 'σ');
+    assert_eq!(simple_fold('\u{212A}'), 'k');
+    // 튀르키예어 규칙은 적용하지 않습니다.
+    assert_eq!(simple_fold('I'), 'i');
+    assert_eq!(simple_fold('ı'), 'ı');
+    assert_eq!(simple_fold('İ'), 'İ');
+    // 한글은 대소문자가 없습니다.
+    assert_eq!(simple_fold('한'), '한');
+}
+
+#[test]
+fn test_search_case_insensitive_german_and_greek() {
+    let contents = "STRAẞE gesperrt\nΟΔΥΣΣΕΥΣ\nStrasse offen";
+    assert_eq!(
+        search_case_insensitive("straße", contents),
+        vec!["STRAẞE gesperrt"]
+    );
+    assert_eq!(
+        search_case_insensitive("οδυσσευς", contents),
+        vec!["ΟΔΥΣΣΕΥΣ"]
+    );
+}
+
+#[test]
+fn test_contains_folded_does_not_treat_dotless_i_as_i() {
+    let query: Vec<char> = "file".chars().map(simple_fold).collect();
+    assert!(contains_folded("FILE", &query));
+    assert!(!contains_folded("fıle", &query));
+}
+
+#[test]
+fn test_pattern_ignore_case_uses_simple_folding() {
+    assert!(ignore_case("straße").is_match("STRAẞE"));
+    assert!(ignore_case("\u{212A}elvin").is_match("kelvin"));
+    assert!(!ignore_case("straße").is_match("STRASSE"));
+}
+
+#[test]
+fn test_class_ignore_case_matches_all_variants() {
+    let options = PatternOptions {
+        regex: true,
+        case_insensitive: true,
+        ..PatternOptions::default()
+    };
+    let pattern = Pattern::with_options("^[a-z]+$", &options).unwrap();
+    assert!(pattern.is_match("\u{212A}ſ"));
+    let negated = Pattern::with_options("^[^a]$", &options).unwrap();
+    assert!(!negated.is_match("A"));
+}
+
+#[test]
+fn test_is_word_char_unicode() {
+    assert!(is_word_char('한'));
+    assert!(is_word_char('ü'));
+    assert!(is_word_char('\u{0301}'));
+    assert!(is_word_char('‿'));
+    assert!(!is_word_char('·'));
+    assert!(!is_word_char('!'));
+}
+
+#[test]
+fn test_word_mode_with_korean_and_german() {
+    assert!(word("에러").is_match("치명적 에러!"));
+    assert!(!word("에러").is_match("에러가 발생"));
+    assert!(word("Größe").is_match("die Größe."));
+    assert!(!word("Größe").is_match("Größen"));
+}
+
+#[test]
+fn test_word_mode_respects_combining_marks() {
+    // "café" 를 e + U+0301 로 쓴 경우 "cafe" 는 단어 전체가 아닙니다.
+    assert!(!word("cafe").is_match("cafe\u{0301} au lait"));
+    assert!(word("cafe").is_match("cafe au lait"));
+}
+
+#[test]
+fn test_unicode_word_class() {
+    let pattern = Pattern::new(r"^\w+$").unwrap();
+    assert!(pattern.is_match("변수_이름1"));
+    assert!(!pattern.is_match("a-b"));
+}
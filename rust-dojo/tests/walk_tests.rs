@@ -0,0 +1,166 @@
+//! 다중 파일 / 디렉토리 재귀 검색 테스트
+//!
+//! 실행 방법:
+//! ```bash
+//! cargo test --test walk_tests
+//! ```
+
+use minigrep::walk::{collect_files, collect_files_reporting, is_binary, WalkOptions};
+use minigrep::*;
+use std::path::PathBuf;
+
+#[test]
+fn test_collect_files_recursive_sorted() {
+    let files = collect_files(&["tests/fixtures/tree"]).unwrap();
+    let expected: Vec<PathBuf> = [
+        "tests/fixtures/tree/a.txt",
+        "tests/fixtures/tree/bin.dat",
+        "tests/fixtures/tree/sub/b.log",
+        "tests/fixtures/tree/sub/deeper/c.txt",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .collect();
+    assert_eq!(files, expected);
+}
+
+#[test]
+fn test_collect_files_mixed_paths_keep_order() {
+    let files = collect_files(&["tests/fixtures/poem.txt", "tests/fixtures/tree/sub"]).unwrap();
+    assert_eq!(files.len(), 3);
+    assert_eq!(files[0], PathBuf::from("tests/fixtures/poem.txt"));
+}
+
+#[test]
+fn test_collect_files_missing_path_is_error() {
+    let err = collect_files(&["tests/fixtures/nope"]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(err.to_string().contains("tests/fixtures/nope"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unreadable_paths_are_reported_and_skipped() {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::process::Command;
+
+    let root = std::env::temp_dir().join(format!("minigrep-walk-{}", std::process::id()));
+    let locked = root.join("locked");
+    let secret = root.join("secret.txt");
+    fs::create_dir_all(&locked).unwrap();
+    fs::write(root.join("a.txt"), "needle\n").unwrap();
+    fs::write(locked.join("b.txt"), "needle\n").unwrap();
+    fs::write(&secret, "needle\n").unwrap();
+    fs::write(root.join("z.txt"), "needle\n").unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+    fs::set_permissions(&secret, fs::Permissions::from_mode(0o000)).unwrap();
+    // root 로 실행하면 권한과 상관없이 읽을 수 있으므로 에러는 확인하지 않습니다.
+    let readable = fs::read_dir(&locked).is_ok();
+
+    let mut errors = Vec::new();
+    let files = collect_files_reporting(&[&root], &WalkOptions::all(), |e| {
+        errors.push(e.to_string());
+        Ok(())
+    });
+    let strict = collect_files(&[&root]);
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-c", "needle"])
+        .arg(&root)
+        .output()
+        .unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let files = files.unwrap();
+    assert!(files.contains(&root.join("a.txt")));
+    assert!(files.contains(&root.join("z.txt")));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("{}:1", root.join("a.txt").display())));
+    assert!(stdout.contains(&format!("{}:1", root.join("z.txt").display())));
+    if readable {
+        assert!(output.status.success(), "{:?}", output.status);
+        return;
+    }
+    // 읽을 수 없는 디렉토리는 건너뛰고, 파일은 검색할 때 알린 뒤 다음 파일로 넘어갑니다.
+    assert_eq!(files.len(), 3);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with(&format!("{}: ", locked.display())));
+    assert!(strict.is_err());
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    for path in [&locked, &secret] {
+        assert!(
+            stderr.contains(&format!("minigrep: {}: ", path.display())),
+            "{stderr}"
+        );
+    }
+}
+
+#[test]
+fn test_missing_path_does_not_stop_other_paths() {
+    let mut errors = Vec::new();
+    let files = collect_files_reporting(
+        &["tests/fixtures/nope", "tests/fixtures/poem.txt"],
+        &WalkOptions::all(),
+        |e| {
+            errors.push(e.to_string());
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(files, vec![PathBuf::from("tests/fixtures/poem.txt")]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with("tests/fixtures/nope: "));
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["-U", "-c", "nobody", "tests/fixtures/nope"])
+        .arg("tests/fixtures/poem.txt")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "tests/fixtures/poem.txt:2\n"
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("minigrep: tests/fixtures/nope: "),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_is_binary() {
+    assert!(is_binary(b"abc\0def"));
+    assert!(!is_binary("텍스트 파일".as_bytes()));
+}
+
+#[test]
+fn test_config_accepts_multiple_paths() {
+    let args = vec![
+        String::from("minigrep"),
+        String::from("error"),
+        String::from("a.txt"),
+        String::from("logs"),
+    ];
+    let config = Config::build(&args).unwrap();
+    assert_eq!(config.paths, vec!["a.txt", "logs"]);
+}
+
+#[test]
+fn test_line_match_with_path_format() {
+    let m = LineMatch::new(3, "delta error two").with_path("tree/sub/b.log");
+    assert_eq!(m.format(), "tree/sub/b.log:3:delta error two");
+}
+
+#[test]
+fn test_run_directory() {
+    let args = vec![
+        String::from("minigrep"),
+        String::from("error"),
+        String::from("tests/fixtures/tree"),
+    ];
+    let config = Config::build(&args).unwrap();
+    assert!(run(config).is_ok());
+}
@@ -444,6 +444,212 @@ pub fn move_file(src: &str, dst: &str) -> io::Result<()> {
     fs::rename(src, dst)
 }
 
+/// 디렉토리 트리 복사/이동의 진행 상황
+///
+/// 파일이나 심볼릭 링크를 하나 복사할 때마다 콜백으로 전달됩니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyProgress {
+    /// 방금 복사한 원본 경로
+    pub path: PathBuf,
+    pub files_copied: u64,
+    pub total_files: u64,
+    pub bytes_copied: u64,
+    pub total_bytes: u64,
+}
+
+/// 디렉토리를 하위 내용까지 재귀적으로 복사합니다.
+///
+/// 권한과 수정 시각을 보존하고, 심볼릭 링크는 따라가지 않고 같은 대상을 가리키는
+/// 링크로 다시 만듭니다. `dst` 가 이미 있는 디렉토리면 그 안에 합쳐서 복사하고
+/// 같은 이름의 파일은 덮어씁니다. 디렉토리를 자기 자신의 하위로 복사하려 하면
+/// `InvalidInput` 에러를 반환합니다.
+///
+/// # Arguments
+/// * `src` - 원본 디렉토리 경로 (파일이나 심볼릭 링크도 가능)
+/// * `dst` - 대상 경로
+/// * `progress` - 파일 하나를 복사할 때마다 불리는 콜백
+///
+/// # Returns
+/// * `io::Result<u64>` - 복사된 바이트 수
+pub fn copy_dir_recursive(
+    src: &str,
+    dst: &str,
+    mut progress: impl FnMut(&CopyProgress),
+) -> io::Result<u64> {
+    let src = Path::new(src);
+    let dst = Path::new(dst);
+    let metadata = fs::symlink_metadata(src)?;
+    if metadata.is_dir() && is_inside(dst, src)? {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot copy {} into itself", src.display()),
+        ));
+    }
+
+    let (total_files, total_bytes) = tree_totals(src, &metadata)?;
+    let mut state = CopyProgress {
+        path: PathBuf::new(),
+        files_copied: 0,
+        total_files,
+        bytes_copied: 0,
+        total_bytes,
+    };
+    copy_entry(src, dst, &metadata, &mut state, &mut progress)?;
+    Ok(state.bytes_copied)
+}
+
+/// 파일이나 디렉토리 트리를 이동합니다.
+///
+/// 먼저 `fs::rename` 을 시도하고, 원본과 대상이 다른 파일 시스템에 있어 실패하면
+/// `copy_dir_recursive` 로 복사한 뒤 원본을 삭제합니다. 이름만 바꾼 경우에는
+/// 콜백이 불리지 않습니다. 복사 도중 실패하면 새로 만들던 대상을 지우고
+/// 원본은 그대로 둡니다.
+///
+/// # Arguments
+/// * `src` - 원본 경로
+/// * `dst` - 대상 경로
+/// * `progress` - 복사로 이동할 때 파일 하나마다 불리는 콜백
+///
+/// # Returns
+/// * `io::Result<()>` - 성공 또는 에러
+pub fn move_tree(src: &str, dst: &str, progress: impl FnMut(&CopyProgress)) -> io::Result<()> {
+    match fs::rename(src, dst) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {}
+        result => return result,
+    }
+
+    // rename 과 같이 비어 있지 않은 디렉토리 위로는 옮기지 않습니다.
+    if fs::symlink_metadata(src)?.is_dir() {
+        if let Ok(mut entries) = fs::read_dir(dst) {
+            if entries.next().is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::DirectoryNotEmpty,
+                    format!("{} is not empty", dst),
+                ));
+            }
+        }
+    }
+    let existed = fs::symlink_metadata(dst).is_ok();
+    if let Err(e) = copy_dir_recursive(src, dst, progress) {
+        if !existed {
+            let _ = remove_tree(Path::new(dst));
+        }
+        return Err(e);
+    }
+    remove_tree(Path::new(src))
+}
+
+/// 항목 하나를 복사하고, 디렉토리면 하위 항목까지 복사합니다.
+fn copy_entry(
+    src: &Path,
+    dst: &Path,
+    metadata: &fs::Metadata,
+    state: &mut CopyProgress,
+    progress: &mut impl FnMut(&CopyProgress),
+) -> io::Result<()> {
+    if metadata.is_dir() {
+        if !dst.is_dir() {
+            fs::create_dir(dst)?;
+        }
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            // DirEntry::metadata 는 심볼릭 링크를 따라가지 않습니다.
+            let child = entry.metadata()?;
+            copy_entry(
+                &entry.path(),
+                &dst.join(entry.file_name()),
+                &child,
+                state,
+                progress,
+            )?;
+        }
+        // 내용을 채우는 동안 바뀌는 시각과 쓰기 권한은 마지막에 맞춥니다.
+        copy_times(&File::open(dst)?, metadata)?;
+        return fs::set_permissions(dst, metadata.permissions());
+    }
+
+    if metadata.is_symlink() {
+        if fs::symlink_metadata(dst).is_ok() {
+            fs::remove_file(dst)?;
+        }
+        copy_symlink(src, dst)?;
+    } else {
+        // 시각은 쓰던 핸들로 맞추고 권한은 마지막에 복사해서, 읽기 전용이나
+        // 쓰기 전용 파일도 다시 열 필요가 없습니다.
+        let mut writer = File::create(dst)?;
+        state.bytes_copied += io::copy(&mut File::open(src)?, &mut writer)?;
+        copy_times(&writer, metadata)?;
+        fs::set_permissions(dst, metadata.permissions())?;
+    }
+    state.files_copied += 1;
+    state.path = src.to_path_buf();
+    progress(state);
+    Ok(())
+}
+
+/// 트리 안의 파일(심볼릭 링크 포함) 수와 총 바이트 수를 셉니다.
+fn tree_totals(path: &Path, metadata: &fs::Metadata) -> io::Result<(u64, u64)> {
+    if !metadata.is_dir() {
+        let bytes = if metadata.is_file() {
+            metadata.len()
+        } else {
+            0
+        };
+        return Ok((1, bytes));
+    }
+    let mut totals = (0, 0);
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let (files, bytes) = tree_totals(&entry.path(), &entry.metadata()?)?;
+        totals.0 += files;
+        totals.1 += bytes;
+    }
+    Ok(totals)
+}
+
+/// `path` 가 `dir` 자신이거나 그 하위인지 확인합니다. `path` 는 아직 없어도 됩니다.
+fn is_inside(path: &Path, dir: &Path) -> io::Result<bool> {
+    let dir = fs::canonicalize(dir)?;
+    let existing = match path.ancestors().find_map(|p| fs::canonicalize(p).ok()) {
+        Some(existing) => existing,
+        None => std::env::current_dir()?,
+    };
+    Ok(existing.starts_with(dir))
+}
+
+/// 원본의 접근/수정 시각을 열어 둔 대상에 그대로 적용합니다.
+fn copy_times(dst: &File, metadata: &fs::Metadata) -> io::Result<()> {
+    let times = fs::FileTimes::new()
+        .set_accessed(metadata.accessed()?)
+        .set_modified(metadata.modified()?);
+    dst.set_times(times)
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(src)?, dst)
+}
+
+#[cfg(windows)]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    use std::os::windows::fs::{symlink_dir, symlink_file};
+    let target = fs::read_link(src)?;
+    if fs::metadata(src).is_ok_and(|metadata| metadata.is_dir()) {
+        symlink_dir(target, dst)
+    } else {
+        symlink_file(target, dst)
+    }
+}
+
+/// 디렉토리면 통째로, 아니면 항목 하나만 삭제합니다.
+fn remove_tree(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
 /// 디렉토리와 그 내용을 재귀적으로 삭제합니다.
 ///
 /// # Arguments
@@ -94,11 +94,21 @@ fn main() {
             }
         }
         "copy" => {
-            if args.len() < 4 {
-                eprintln!("Usage: fstools copy <src> <dst>");
+            let recursive = matches!(args.get(2).map(|s| s.as_str()), Some("-r" | "--recursive"));
+            let paths = &args[if recursive { 3 } else { 2 }.min(args.len())..];
+            if paths.len() < 2 {
+                eprintln!("Usage: fstools copy [-r] <src> <dst>");
                 return;
             }
-            match copy_file(&args[2], &args[3]) {
+            let result = if recursive {
+                let mut progress = ProgressLine::default();
+                let result = copy_dir_recursive(&paths[0], &paths[1], |p| progress.update(p));
+                progress.finish();
+                result
+            } else {
+                copy_file(&paths[0], &paths[1])
+            };
+            match result {
                 Ok(bytes) => println!("Copied {} bytes", bytes),
                 Err(e) => eprintln!("Error: {}", e),
             }
@@ -108,7 +118,11 @@ fn main() {
                 eprintln!("Usage: fstools move <src> <dst>");
                 return;
             }
-            match move_file(&args[2], &args[3]) {
+            // 다른 파일 시스템으로 옮길 때만 복사 진행 상황이 표시됩니다.
+            let mut progress = ProgressLine::default();
+            let result = move_tree(&args[2], &args[3], |p| progress.update(p));
+            progress.finish();
+            match result {
                 Ok(()) => println!("Moved {} -> {}", args[2], args[3]),
                 Err(e) => eprintln!("Error: {}", e),
             }
@@ -137,8 +151,34 @@ COMMANDS:
     info <path>             Show file/directory information
     size [dir]              Calculate directory size
     copy <src> <dst>        Copy file
-    move <src> <dst>        Move file
+    copy -r <src> <dst>     Copy directory tree (keeps permissions, mtimes, symlinks)
+    move <src> <dst>        Move file or directory tree (works across filesystems)
     help                    Show this help message
 "#
     );
 }
+
+/// 복사 진행 상황을 stderr 의 한 줄에 덮어쓰며 표시합니다.
+#[derive(Default)]
+struct ProgressLine {
+    shown: bool,
+}
+
+impl ProgressLine {
+    fn update(&mut self, progress: &CopyProgress) {
+        eprint!(
+            "\r{}/{} files, {}/{} bytes",
+            progress.files_copied,
+            progress.total_files,
+            progress.bytes_copied,
+            progress.total_bytes
+        );
+        self.shown = true;
+    }
+
+    fn finish(&self) {
+        if self.shown {
+            eprintln!();
+        }
+    }
+}
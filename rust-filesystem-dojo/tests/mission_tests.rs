@@ -507,4 +507,129 @@ mod final_mission_utilities {
         let size = calculate_dir_size(dir.path().to_str().unwrap()).unwrap();
         assert_eq!(size, 13);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_recursive() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+        use std::time::{Duration, SystemTime};
+
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("tree");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("a.txt"), "12345").unwrap();
+        fs::write(src.join("sub/b.sh"), "echo hi").unwrap();
+        fs::set_permissions(src.join("sub/b.sh"), fs::Permissions::from_mode(0o750)).unwrap();
+        symlink("../a.txt", src.join("sub/link")).unwrap();
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        fs::File::open(src.join("a.txt"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let dst = dir.path().join("copy");
+        let mut updates = Vec::new();
+        let bytes = copy_dir_recursive(src.to_str().unwrap(), dst.to_str().unwrap(), |p| {
+            updates.push(p.clone())
+        })
+        .unwrap();
+        assert_eq!(bytes, 12);
+
+        assert_eq!(fs::read_to_string(dst.join("sub/b.sh")).unwrap(), "echo hi");
+        let mode = fs::metadata(dst.join("sub/b.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o750);
+        let modified = fs::metadata(dst.join("a.txt")).unwrap().modified().unwrap();
+        assert_eq!(modified, old);
+        // 심볼릭 링크는 따라가지 않고 링크 그대로 복사됩니다.
+        let link = dst.join("sub/link");
+        assert!(fs::symlink_metadata(&link).unwrap().is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("../a.txt"));
+        assert_eq!(fs::read_to_string(&link).unwrap(), "12345");
+
+        assert_eq!(updates.len(), 3);
+        let last = updates.last().unwrap();
+        assert_eq!((last.files_copied, last.total_files), (3, 3));
+        assert_eq!((last.bytes_copied, last.total_bytes), (12, 12));
+        assert!(src.join("a.txt").exists());
+    }
+
+    #[test]
+    fn test_copy_dir_into_itself() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("tree");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("a.txt"), "abc").unwrap();
+
+        let dst = src.join("nested/copy");
+        let err =
+            copy_dir_recursive(src.to_str().unwrap(), dst.to_str().unwrap(), |_| {}).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!src.join("nested").exists());
+    }
+
+    #[test]
+    fn test_move_tree() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("tree");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("sub/nested.txt"), "move me").unwrap();
+
+        // 같은 파일 시스템에서는 이름만 바꾸므로 콜백이 불리지 않습니다.
+        let dst = dir.path().join("moved");
+        let mut calls = 0;
+        move_tree(src.to_str().unwrap(), dst.to_str().unwrap(), |_| calls += 1).unwrap();
+        assert_eq!(calls, 0);
+        assert!(!src.exists());
+        assert_eq!(
+            fs::read_to_string(dst.join("sub/nested.txt")).unwrap(),
+            "move me"
+        );
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_move_tree_across_file_systems() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        use std::time::{Duration, SystemTime};
+
+        // 다른 파일 시스템(tmpfs)이 없으면 rename 으로 끝나므로 건너뜁니다.
+        let dir = tempdir().unwrap();
+        let Ok(other) = tempfile::tempdir_in("/dev/shm") else {
+            return;
+        };
+        let device = |path: &std::path::Path| fs::metadata(path).unwrap().dev();
+        if device(dir.path()) == device(other.path()) {
+            return;
+        }
+
+        let src = dir.path().join("tree");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("sub/nested.txt"), "move me").unwrap();
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        fs::File::options()
+            .write(true)
+            .open(src.join("sub/nested.txt"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        fs::set_permissions(
+            src.join("sub/nested.txt"),
+            fs::Permissions::from_mode(0o444),
+        )
+        .unwrap();
+
+        // 복사한 뒤 원본을 지우므로 파일마다 콜백이 불립니다.
+        let dst = other.path().join("moved");
+        let mut calls = 0;
+        move_tree(src.to_str().unwrap(), dst.to_str().unwrap(), |_| calls += 1).unwrap();
+        assert_eq!(calls, 1);
+        assert!(!src.exists());
+        let moved = dst.join("sub/nested.txt");
+        assert_eq!(fs::read_to_string(&moved).unwrap(), "move me");
+        let metadata = fs::metadata(&moved).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o444);
+        assert_eq!(metadata.modified().unwrap(), old);
+    }
 }